@@ -36,9 +36,11 @@ where
   C: Send + Sync + 'static,
   C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
   C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+  C::Api: pallet_non_fungible_assets_rpc::NonFungibleAssetsRuntimeApi<Block, AccountId, Index>,
   C::Api: BlockBuilder<Block>,
   P: TransactionPool + 'static,
 {
+  use pallet_non_fungible_assets_rpc::{NonFungibleAssets, NonFungibleAssetsApiServer};
   use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
   use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -50,7 +52,8 @@ where
   } = deps;
 
   module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
-  module.merge(TransactionPayment::new(client).into_rpc())?;
+  module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+  module.merge(NonFungibleAssets::new(client).into_rpc())?;
 
   // Extend this RPC with a custom API by using the following syntax.
   // `YourRpcStruct` should have a reference to a client, which is needed
@@ -132,6 +132,7 @@ impl<T: Config> Pallet<T> {
           maybe_account @ None => {
             *maybe_account = Some(AssetAccount {
               balance: amount,
+              reserved: Zero::zero(),
               reason: Self::new_account(beneficiary, details, None)?,
             });
           },
@@ -176,7 +177,7 @@ impl<T: Config> Pallet<T> {
         account.balance = account.balance.saturating_sub(&actual);
 
         // Check if asset is top upped
-        target_topup = details.next_step_topup(account.balance);
+        target_topup = details.next_step_topup(account.total_balance());
 
         *maybe_account = Some(account);
         Ok(())
@@ -196,6 +197,139 @@ impl<T: Config> Pallet<T> {
     Ok(actual)
   }
 
+  /// Moves `amount` of the asset `id` from `source` to `dest`.  \
+  /// The total supply of the asset stays unchanged.  \
+  /// Reads O(4), Writes(3)
+  pub(super) fn do_transfer(
+    id: AssetId,
+    source: &T::AccountId,
+    dest: &T::AccountId,
+    amount: AssetBalance,
+  ) -> DispatchResultAs<AssetBalance> {
+    if amount.is_zero() || source == dest {
+      return Ok(amount);
+    }
+    let actual = Self::prep_debit(id, source, amount, false)?;
+    Self::can_increase(id, dest, actual).into_result()?;
+
+    let mut source_topup: TopUpConsequence = TopUpConsequence::None;
+
+    Assets::<T>::try_mutate(id, |maybe_details| -> DispatchResult {
+      let details = maybe_details.as_mut().ok_or(TokenError::UnknownAsset)?;
+
+      Accounts::<T>::try_mutate(source, id, |maybe_account| -> DispatchResult {
+        let mut account = maybe_account.take().ok_or(Error::<T>::NoAccount)?;
+        account.balance = account.balance.saturating_sub(&actual);
+
+        // Check if asset is top upped
+        source_topup = details.next_step_topup(account.total_balance());
+
+        *maybe_account = Some(account);
+        Ok(())
+      })?;
+
+      Accounts::<T>::try_mutate(dest, id, |maybe_account| -> DispatchResult {
+        match maybe_account {
+          Some(ref mut account) => {
+            account.balance = account.balance.saturating_add(&actual);
+          },
+          maybe_account @ None => {
+            *maybe_account = Some(AssetAccount {
+              balance: actual,
+              reserved: Zero::zero(),
+              reason: Self::new_account(dest, details, None)?,
+            });
+          },
+        }
+        Ok(())
+      })?;
+      Ok(())
+    })?;
+    // Put the source to the queue for top upped of the balance if it needed
+    if source_topup != TopUpConsequence::None {
+      TopUpQueue::<T>::insert(id, source, ());
+    }
+
+    Self::deposit_event(Event::Transferred {
+      asset_id: id,
+      from: source.clone(),
+      to: dest.clone(),
+      amount: actual,
+    });
+    Ok(actual)
+  }
+
+  /// Moves `amount` of the asset `id` from the balance of `who` to the reserved balance.  \
+  /// Reads O(3), Writes(1)
+  pub(super) fn do_reserve(
+    id: AssetId,
+    who: &T::AccountId,
+    amount: AssetBalance,
+  ) -> DispatchResult {
+    if amount.is_zero() {
+      return Ok(());
+    }
+    let actual = Self::prep_debit(id, who, amount, false)?;
+
+    // the total balance stays the same, so the account isn't put to the top up queue
+    Accounts::<T>::try_mutate(who, id, |maybe_account| -> DispatchResult {
+      let account = maybe_account.as_mut().ok_or(Error::<T>::NoAccount)?;
+      account.reserved = account
+        .reserved
+        .checked_add(&actual)
+        .ok_or(ArithmeticError::Overflow)?;
+      account.balance = account.balance.saturating_sub(&actual);
+      Ok(())
+    })?;
+
+    Self::deposit_event(Event::Reserved {
+      asset_id: id,
+      who: who.clone(),
+      amount: actual,
+    });
+    Ok(())
+  }
+
+  /// Moves up to `amount` of the asset `id` from the reserved balance of `who` back to the balance.
+  ///
+  /// Returns the amount which was actually unreserved.  \
+  /// Reads O(1), Writes(1)
+  pub(super) fn do_unreserve(
+    id: AssetId,
+    who: &T::AccountId,
+    amount: AssetBalance,
+  ) -> DispatchResultAs<AssetBalance> {
+    if amount.is_zero() {
+      return Ok(amount);
+    }
+    let actual = Accounts::<T>::try_mutate(who, id, |maybe_account| -> DispatchResultAs<_> {
+      let account = maybe_account.as_mut().ok_or(Error::<T>::NoAccount)?;
+      let actual = account.reserved.min(amount);
+      account.reserved = account.reserved.saturating_sub(&actual);
+      account.balance = account
+        .balance
+        .checked_add(&actual)
+        .ok_or(ArithmeticError::Overflow)?;
+      Ok(actual)
+    })?;
+
+    Self::deposit_event(Event::Unreserved {
+      asset_id: id,
+      who: who.clone(),
+      amount: actual,
+    });
+    Ok(actual)
+  }
+
+  /// Get the reserved asset `id` balance of `who` if the asset-account exists. \
+  /// Reads O(1), Writes(0)
+  pub fn maybe_reserved_balance(
+    id: AssetId,
+    who: impl sp_std::borrow::Borrow<T::AccountId>,
+  ) -> Option<AssetBalance> {
+    Accounts::<T>::get(who.borrow(), id).map(|a| a.reserved)
+  }
+
   /// Adds asset to TopUppedAssets storage.  \
   /// It adds only unique ids  \
   /// WARN: method doesn't check characteristics of the asset.  
@@ -252,7 +386,7 @@ impl<T: Config> Pallet<T> {
       for (target, _) in TopUpQueue::<T>::drain_prefix(id) {
         reads.saturating_accrue(2); // drain + get details
         let account = Accounts::<T>::get(&target, id).unwrap();
-        let target_topup = details.next_step_topup(account.balance);
+        let target_topup = details.next_step_topup(account.total_balance());
 
         match target_topup {
           TopUpConsequence::TopUpFinal(amount) => {
@@ -279,6 +413,27 @@ impl<T: Config> Pallet<T> {
     T::DbWeight::get().reads_writes(reads, writes)
   }
 
+  /// Destroys the fungible asset.  \
+  /// The asset can't be destroyed while it is referenced by other assets
+  pub(super) fn do_destroy(id: AssetId, maybe_check_owner: Option<T::AccountId>) -> DispatchResult {
+    let details = Assets::<T>::get(id).ok_or(TokenError::UnknownAsset)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    ensure!(details.references == 0, Error::<T>::Referenced);
+
+    Assets::<T>::remove(id);
+    AssetsOf::<T>::remove(&details.owner, id);
+    OwnershipOffers::<T>::remove(id);
+    Self::top_upped_asset_remove(&id);
+
+    Self::deposit_event(Event::Destroyed {
+      asset_id: id,
+      owner: details.owner,
+    });
+    Ok(())
+  }
+
   /// Increment the references counter on an asset.
   pub fn inc_references(asset: &AssetId) -> DispatchResult {
     Assets::<T>::try_mutate(asset, |maybe_details| -> DispatchResult {
@@ -324,4 +479,61 @@ impl<T: Config> Pallet<T> {
   ) -> DispatchResult {
     Self::increase_balance(id, beneficiary, amount)
   }
+
+  /// Offers to transfer the ownership of the asset to the `new_owner` organization
+  pub(super) fn do_offer_ownership(
+    asset_id: AssetId,
+    maybe_check_owner: Option<T::AccountId>,
+    new_owner: T::AccountId,
+  ) -> DispatchResult {
+    let details = Assets::<T>::get(asset_id).ok_or(TokenError::UnknownAsset)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    OwnershipOffers::<T>::insert(asset_id, &new_owner);
+    Self::deposit_event(Event::OwnershipOffered {
+      asset_id,
+      owner: details.owner,
+      new_owner,
+    });
+    Ok(())
+  }
+
+  /// Cancels the offer to transfer the ownership of the asset
+  pub(super) fn do_cancel_ownership_offer(
+    asset_id: AssetId,
+    maybe_check_owner: Option<T::AccountId>,
+  ) -> DispatchResult {
+    let details = Assets::<T>::get(asset_id).ok_or(TokenError::UnknownAsset)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    OwnershipOffers::<T>::take(asset_id).ok_or(Error::<T>::NoOwnershipOffer)?;
+    Self::deposit_event(Event::OwnershipOfferCancelled {
+      asset_id,
+      owner: details.owner,
+    });
+    Ok(())
+  }
+
+  /// Transfers the ownership of the asset to the organization which the ownership was offered to
+  pub(super) fn do_accept_ownership(asset_id: AssetId, new_owner: T::AccountId) -> DispatchResult {
+    ensure!(
+      OwnershipOffers::<T>::get(asset_id).as_ref() == Some(&new_owner),
+      Error::<T>::NoOwnershipOffer
+    );
+    Assets::<T>::try_mutate(asset_id, |maybe_details| -> DispatchResult {
+      let details = maybe_details.as_mut().ok_or(TokenError::UnknownAsset)?;
+      let old_owner = sp_std::mem::replace(&mut details.owner, new_owner.clone());
+      OwnershipOffers::<T>::remove(asset_id);
+      AssetsOf::<T>::remove(&old_owner, asset_id);
+      AssetsOf::<T>::insert(&new_owner, asset_id, ());
+      Self::deposit_event(Event::OwnerChanged {
+        asset_id,
+        old_owner,
+        new_owner,
+      });
+      Ok(())
+    })
+  }
 }
@@ -1,6 +1,10 @@
 use super::*;
 
 impl<T: Config> pallet_support::traits::FungibleAssets<AccountIdOf<T>> for Pallet<T> {
+  fn owner_of(asset: &AssetId) -> Option<AccountIdOf<T>> {
+    Assets::<T>::get(asset).map(|details| details.owner)
+  }
+
   fn can_withdraw(
     asset: AssetId,
     who: &AccountIdOf<T>,
@@ -21,6 +25,27 @@ impl<T: Config> pallet_support::traits::FungibleAssets<AccountIdOf<T>> for Palle
     Self::decrease_balance(asset, who, amount, false)
   }
 
+  fn transfer(
+    asset: AssetId,
+    source: &AccountIdOf<T>,
+    dest: &AccountIdOf<T>,
+    amount: AssetBalance,
+  ) -> DispatchResultAs<AssetBalance> {
+    Self::do_transfer(asset, source, dest, amount)
+  }
+
+  fn reserve(asset: AssetId, who: &AccountIdOf<T>, amount: AssetBalance) -> DispatchResult {
+    Self::do_reserve(asset, who, amount)
+  }
+
+  fn unreserve(
+    asset: AssetId,
+    who: &AccountIdOf<T>,
+    amount: AssetBalance,
+  ) -> DispatchResultAs<AssetBalance> {
+    Self::do_unreserve(asset, who, amount)
+  }
+
   /// Increment the references counter on an asset.
   fn inc_references(asset: &AssetId) -> DispatchResult {
     Self::inc_references(asset)
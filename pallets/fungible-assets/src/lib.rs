@@ -15,6 +15,7 @@ use sp_std::prelude::*;
 
 mod functions;
 mod impl_fungible_assets;
+pub mod migration;
 mod types;
 
 use pallet_support::{
@@ -43,8 +44,12 @@ use sp_std::{fmt::Debug, vec::Vec};
 pub mod pallet {
   use super::*;
 
+  /// The current storage version.
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
   #[pallet::pallet]
   #[pallet::generate_store(pub(super) trait Store)]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
   /// Configure the pallet by specifying the parameters and types on which it depends.
@@ -93,6 +98,11 @@ pub mod pallet {
   pub(super) type AssetsOf<T: Config> =
     StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, AssetId, ()>;
 
+  #[pallet::storage]
+  /// Organizations to which the owners of assets offered to transfer ownership of the assets.
+  pub(super) type OwnershipOffers<T: Config> =
+    StorageMap<_, Blake2_128Concat, AssetId, T::AccountId>;
+
   #[pallet::storage]
   /// The holdings of a specific account for a specific asset
   pub(super) type Accounts<T: Config> = StorageDoubleMap<
@@ -141,6 +151,25 @@ pub mod pallet {
       owner: T::AccountId,
       balance: FungibleAssetBalance,
     },
+    /// Some assets were transferred.
+    Transferred {
+      asset_id: AssetId,
+      from: T::AccountId,
+      to: T::AccountId,
+      amount: FungibleAssetBalance,
+    },
+    /// Some assets were reserved by mechanics.
+    Reserved {
+      asset_id: AssetId,
+      who: T::AccountId,
+      amount: FungibleAssetBalance,
+    },
+    /// Some reserved assets were returned to the balance.
+    Unreserved {
+      asset_id: AssetId,
+      who: T::AccountId,
+      amount: FungibleAssetBalance,
+    },
     /// Event documentation should end with an array that provides descriptive names for event
     /// parameters. [something, who]
     SomethingStored(u32, T::AccountId),
@@ -149,6 +178,23 @@ pub mod pallet {
       asset_id: AssetId,
       owner: T::AccountId,
     },
+    /// The owner of the asset offered to transfer the ownership to another organization.
+    OwnershipOffered {
+      asset_id: AssetId,
+      owner: T::AccountId,
+      new_owner: T::AccountId,
+    },
+    /// The offer to transfer the ownership of the asset was cancelled.
+    OwnershipOfferCancelled {
+      asset_id: AssetId,
+      owner: T::AccountId,
+    },
+    /// The ownership of the asset was transferred to another organization.
+    OwnerChanged {
+      asset_id: AssetId,
+      old_owner: T::AccountId,
+      new_owner: T::AccountId,
+    },
   }
 
   #[pallet::error]
@@ -177,6 +223,10 @@ pub mod pallet {
     TopUppedWithNoCup,
     /// The account to alter does not exist.
     NoAccount,
+    /// The asset has no offer to transfer the ownership to the organization.
+    NoOwnershipOffer,
+    /// The asset is referenced by other assets and can't be destroyed.
+    Referenced,
   }
 
   // Implement the pallet hooks.
@@ -249,7 +299,8 @@ pub mod pallet {
 
     /// Destroy a fungible asset.
     ///
-    /// The origin must be Signed and must be a member of the organization
+    /// The origin must be Signed and must be a member of the organization.
+    /// The asset can't be destroyed while it is referenced by characteristics of NFA classes.
     #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
     pub fn destroy(
       origin: OriginFor<T>,
@@ -260,13 +311,63 @@ pub mod pallet {
       let owner = T::Lookup::lookup(organization_id)?;
       // Only member if the organization can create an asset
       T::CreateOrigin::ensure_origin(origin, &owner)?;
-      // TODO: set limits on the number of assets created by each organization
-      Assets::<T>::remove(asset_id);
-      AssetsOf::<T>::remove(&owner, asset_id);
-      Self::top_upped_asset_remove(&asset_id);
+      Self::do_destroy(asset_id, Some(owner))
+    }
 
-      Self::deposit_event(Event::Destroyed { asset_id, owner });
-      Ok(())
+    /// Offers to transfer the ownership of the asset to another organization.
+    /// The ownership is transferred when the new organization accepts the offer.
+    /// Any previous offer of the asset is replaced.
+    ///
+    /// The origin must be Signed and must be a member of the organization which owns the asset.
+    ///
+    /// Emits `OwnershipOffered` event when successful.
+    #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+    pub fn offer_ownership(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] asset_id: AssetId,
+      new_owner: <T::Lookup as StaticLookup>::Source,
+    ) -> DispatchResult {
+      let owner = T::Lookup::lookup(organization_id)?;
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+      let new_owner = T::Lookup::lookup(new_owner)?;
+
+      Self::do_offer_ownership(asset_id, Some(owner), new_owner)
+    }
+
+    /// Cancels the offer to transfer the ownership of the asset.
+    ///
+    /// The origin must be Signed and must be a member of the organization which owns the asset.
+    ///
+    /// Emits `OwnershipOfferCancelled` event when successful.
+    #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+    pub fn cancel_ownership_offer(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] asset_id: AssetId,
+    ) -> DispatchResult {
+      let owner = T::Lookup::lookup(organization_id)?;
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_cancel_ownership_offer(asset_id, Some(owner))
+    }
+
+    /// Accepts the offer to transfer the ownership of the asset.
+    ///
+    /// The origin must be Signed and must be a member of the organization
+    /// to which the ownership was offered.
+    ///
+    /// Emits `OwnerChanged` event when successful.
+    #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 4))]
+    pub fn accept_ownership(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] asset_id: AssetId,
+    ) -> DispatchResult {
+      let new_owner = T::Lookup::lookup(organization_id)?;
+      T::CreateOrigin::ensure_origin(origin, &new_owner)?;
+
+      Self::do_accept_ownership(asset_id, new_owner)
     }
   }
 }
@@ -0,0 +1,52 @@
+//! Storage migrations for the Fungible Assets pallet.
+use super::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use sp_std::marker::PhantomData;
+
+/// Migration of the asset accounts to the layout with the reserved balance added after the
+/// version 0.
+pub mod v1 {
+  use super::*;
+
+  /// Asset account stored by the version 0
+  #[derive(Encode, Decode)]
+  pub struct OldAssetAccount {
+    pub balance: FungibleAssetBalance,
+    pub reason: ExistenceReason,
+  }
+
+  impl OldAssetAccount {
+    /// Nothing is reserved yet
+    fn migrate(self) -> AssetAccount {
+      AssetAccount {
+        balance: self.balance,
+        reserved: Zero::zero(),
+        reason: self.reason,
+      }
+    }
+  }
+
+  pub struct MigrateToV1<T>(PhantomData<T>);
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let onchain_version = Pallet::<T>::on_chain_storage_version();
+      if onchain_version >= 1 {
+        log::info!(
+          "🧹 Fungible assets migration to v1 skipped [on-chain version: {:?}]",
+          onchain_version
+        );
+        return T::DbWeight::get().reads(1);
+      }
+
+      let mut count = 0u64;
+      Accounts::<T>::translate::<OldAssetAccount, _>(|_, _, old| {
+        count += 1;
+        Some(old.migrate())
+      });
+      StorageVersion::new(1).put::<Pallet<T>>();
+      log::info!("🧹 Fungible assets migrated to v1 [accounts: {}]", count);
+
+      T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+  }
+}
@@ -737,6 +737,54 @@ fn decrease_balance_max_allowed() {
   })
 }
 
+#[test]
+fn transfer_works() {
+  new_test_ext().execute_with(|| {
+    let id = create_fa("fa name", 2);
+
+    let source = 1;
+    let dest = 333; // account has no fa
+    assert_ok!(FungibleAssets::do_mint(id, &source, 1000.into()));
+    let fa_sup = Assets::<Test>::get(id).unwrap().supply;
+    let accounts = Assets::<Test>::get(id).unwrap().accounts;
+
+    assert_eq!(
+      FungibleAssets::do_transfer(id, &source, &dest, 300.into()),
+      Ok(300.into())
+    );
+    assert_eq!(
+      Accounts::<Test>::get(source, id).unwrap().balance,
+      700.into()
+    );
+    assert_eq!(Accounts::<Test>::get(dest, id).unwrap().balance, 300.into());
+    assert_eq!(Assets::<Test>::get(id).unwrap().supply, fa_sup);
+    assert_eq!(Assets::<Test>::get(id).unwrap().accounts, accounts + 1);
+    assert_eq!(
+      System::events().last().unwrap().event,
+      FaEvent::Transferred {
+        asset_id: id,
+        from: source,
+        to: dest,
+        amount: 300.into(),
+      }
+      .into()
+    );
+
+    assert_noop!(
+      FungibleAssets::do_transfer(id, &source, &dest, 701.into()),
+      TokenError::NoFunds
+    );
+    assert_noop!(
+      FungibleAssets::do_transfer(id, &444, &dest, 1.into()),
+      Error::<Test>::NoAccount
+    );
+    assert_noop!(
+      FungibleAssets::do_transfer(20.into(), &source, &dest, 1.into()),
+      TokenError::UnknownAsset
+    );
+  })
+}
+
 #[test]
 fn decrease_balance_max_allowed_2() {
   new_test_ext().execute_with(|| {
@@ -961,6 +1009,33 @@ fn dec_references_err() {
   })
 }
 
+#[test]
+fn destroy_referenced_asset_fails() {
+  new_test_ext().execute_with(|| {
+    let id = create_topupped_fa("fa name", 2);
+    assert_noop!(
+      FungibleAssets::destroy(Origin::signed(1), 3, id),
+      Error::<Test>::NoPermission
+    );
+
+    assert_ok!(FungibleAssets::inc_references(&id));
+    assert_noop!(
+      FungibleAssets::destroy(Origin::signed(1), 2, id),
+      Error::<Test>::Referenced
+    );
+
+    assert_ok!(FungibleAssets::dec_references(&id));
+    assert_ok!(FungibleAssets::destroy(Origin::signed(1), 2, id));
+    assert!(Assets::<Test>::get(id).is_none());
+    assert!(AssetsOf::<Test>::get(2, id).is_none());
+    assert!(!TopUppedAssets::<Test>::get().contains(&id));
+    assert_noop!(
+      FungibleAssets::destroy(Origin::signed(1), 2, id),
+      TokenError::UnknownAsset
+    );
+  })
+}
+
 #[test]
 fn increase_balance_topup_check() {
   new_test_ext().execute_with(|| {
@@ -994,3 +1069,198 @@ fn increase_balance_topup_check() {
     assert!(TopUpQueue::<Test>::get(id, beneficiary).is_none());
   })
 }
+
+#[test]
+fn ownership_transfer_works() {
+  new_test_ext().execute_with(|| {
+    let (org, new_org) = (2, 3);
+    let id = create_fa("fa name", org);
+
+    assert_noop!(
+      FungibleAssets::offer_ownership(Origin::signed(1), new_org, id, new_org),
+      Error::<Test>::NoPermission
+    );
+    assert_noop!(
+      FungibleAssets::accept_ownership(Origin::signed(1), new_org, id),
+      Error::<Test>::NoOwnershipOffer
+    );
+    assert_ok!(FungibleAssets::offer_ownership(
+      Origin::signed(1),
+      org,
+      id,
+      new_org
+    ));
+    assert_eq!(OwnershipOffers::<Test>::get(id), Some(new_org));
+    // only the organization the ownership offered to can accept it
+    assert_noop!(
+      FungibleAssets::accept_ownership(Origin::signed(1), 4, id),
+      Error::<Test>::NoOwnershipOffer
+    );
+
+    System::reset_events();
+    assert_ok!(FungibleAssets::accept_ownership(
+      Origin::signed(1),
+      new_org,
+      id
+    ));
+    assert_eq!(Assets::<Test>::get(id).unwrap().owner, new_org);
+    assert!(AssetsOf::<Test>::get(org, id).is_none());
+    assert!(AssetsOf::<Test>::get(new_org, id).is_some());
+    assert!(OwnershipOffers::<Test>::get(id).is_none());
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: FaEvent::OwnerChanged {
+          asset_id: id,
+          old_owner: org,
+          new_owner: new_org,
+        }
+        .into(),
+        topics: vec![],
+      }]
+    );
+  })
+}
+
+#[test]
+fn ownership_offer_cancel_works() {
+  new_test_ext().execute_with(|| {
+    let (org, new_org) = (2, 3);
+    let id = create_fa("fa name", org);
+
+    assert_noop!(
+      FungibleAssets::cancel_ownership_offer(Origin::signed(1), org, id),
+      Error::<Test>::NoOwnershipOffer
+    );
+    assert_ok!(FungibleAssets::offer_ownership(
+      Origin::signed(1),
+      org,
+      id,
+      new_org
+    ));
+    assert_noop!(
+      FungibleAssets::cancel_ownership_offer(Origin::signed(1), new_org, id),
+      Error::<Test>::NoPermission
+    );
+    assert_ok!(FungibleAssets::cancel_ownership_offer(
+      Origin::signed(1),
+      org,
+      id
+    ));
+    assert_noop!(
+      FungibleAssets::accept_ownership(Origin::signed(1), new_org, id),
+      Error::<Test>::NoOwnershipOffer
+    );
+    assert_eq!(Assets::<Test>::get(id).unwrap().owner, org);
+  })
+}
+
+#[test]
+fn reserve_unreserve_works() {
+  new_test_ext().execute_with(|| {
+    let fa_id = create_fa("fa name", 2);
+    assert_ok!(FungibleAssets::increase_balance(fa_id, &1, 100.into()));
+
+    System::reset_events();
+    assert_ok!(FungibleAssets::do_reserve(fa_id, &1, 30.into()));
+    assert_eq!(FungibleAssets::maybe_balance(fa_id, 1), Some(70.into()));
+    assert_eq!(
+      FungibleAssets::maybe_reserved_balance(fa_id, 1),
+      Some(30.into())
+    );
+    // the supply includes the reserved balance
+    assert_eq!(Assets::<Test>::get(fa_id).unwrap().supply, 100.into());
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: FaEvent::Reserved {
+          asset_id: fa_id,
+          who: 1,
+          amount: 30.into(),
+        }
+        .into(),
+        topics: vec![],
+      }]
+    );
+    // the reserved balance can't be spent
+    assert_noop!(
+      FungibleAssets::do_transfer(fa_id, &1, &3, 80.into()),
+      TokenError::NoFunds
+    );
+    assert_noop!(
+      FungibleAssets::do_reserve(fa_id, &1, 80.into()),
+      TokenError::NoFunds
+    );
+    // no more than the reserved balance is unreserved
+    assert_eq!(
+      FungibleAssets::do_unreserve(fa_id, &1, 50.into()),
+      Ok(30.into())
+    );
+    assert_eq!(FungibleAssets::maybe_balance(fa_id, 1), Some(100.into()));
+    assert_eq!(
+      FungibleAssets::maybe_reserved_balance(fa_id, 1),
+      Some(0.into())
+    );
+    assert_noop!(
+      FungibleAssets::do_unreserve(fa_id, &3, 50.into()),
+      Error::<Test>::NoAccount
+    );
+  })
+}
+
+#[test]
+fn reserve_doesnt_topup_above_cup() {
+  new_test_ext().execute_with(|| {
+    let id = create_topupped_fa("fa name", 2);
+    assert_ok!(FungibleAssets::increase_balance(id, &1, 20.into()));
+
+    // the reserved balance is counted by the top up
+    assert_ok!(FungibleAssets::do_reserve(id, &1, 15.into()));
+    assert!(!TopUpQueue::<Test>::contains_key(&id, &1));
+    FungibleAssets::process_top_upped_assets();
+    assert_eq!(FungibleAssets::maybe_balance(id, 1), Some(5.into()));
+
+    // spending from the balance tops up to the cup only
+    assert_ok!(FungibleAssets::do_transfer(id, &1, &3, 5.into()));
+    assert!(TopUpQueue::<Test>::contains_key(&id, &1));
+    FungibleAssets::process_top_upped_assets();
+    assert_eq!(FungibleAssets::maybe_balance(id, 1), Some(5.into()));
+    assert!(!TopUpQueue::<Test>::contains_key(&id, &1));
+
+    assert_eq!(
+      FungibleAssets::do_unreserve(id, &1, 15.into()),
+      Ok(15.into())
+    );
+    assert_eq!(FungibleAssets::maybe_balance(id, 1), Some(20.into()));
+  })
+}
+
+#[test]
+fn migration_to_v1_works() {
+  use crate::migration::v1::{MigrateToV1, OldAssetAccount};
+  use codec::Encode;
+  use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(0).put::<FungibleAssets>();
+    let fa_id = create_fa("fa name", 2);
+    let old = OldAssetAccount {
+      balance: 10.into(),
+      reason: ExistenceReason::Sufficient,
+    };
+    frame_support::storage::unhashed::put_raw(
+      &Accounts::<Test>::hashed_key_for(1, fa_id),
+      &old.encode(),
+    );
+
+    MigrateToV1::<Test>::on_runtime_upgrade();
+
+    let account = Accounts::<Test>::get(1, fa_id).unwrap();
+    assert_eq!(account.balance, 10.into());
+    assert_eq!(account.reserved, 0.into());
+    assert_eq!(account.reason, ExistenceReason::Sufficient);
+    assert_eq!(FungibleAssets::on_chain_storage_version(), 1);
+  });
+}
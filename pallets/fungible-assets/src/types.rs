@@ -30,10 +30,20 @@ pub enum TopUpConsequence {
 pub struct AssetAccount {
   /// The balance
   pub(super) balance: FungibleAssetBalance,
+  /// The balance reserved by mechanics (e.g. bids of auctions).  \
+  /// It isn't included in `balance` and can't be spent until it's unreserved
+  pub(super) reserved: FungibleAssetBalance,
   /// The reason for the existence of the account.
   pub(super) reason: ExistenceReason,
 }
 
+impl AssetAccount {
+  /// The free and the reserved balance together, the top up is limited by it
+  pub fn total_balance(&self) -> FungibleAssetBalance {
+    self.balance.saturating_add(&self.reserved)
+  }
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub enum ExistenceReason {
   #[codec(index = 0)]
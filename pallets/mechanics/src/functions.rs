@@ -3,9 +3,21 @@ use sp_std::vec::Vec;
 
 use pallet_support::{
   bettor::{Bettor, BettorOutcome, BettorWinning, DrawOutcomeResult, OutcomeResult},
+  lootbox::LootboxReward,
   misc::cumsum_owned,
-  traits::{FungibleAssets, NonFungibleAssets},
-  DefaultListLengthLimit, DispatchResultAs, LockResultOf, LockedAccet, Locker,
+  purchased::{OfferUnavailable, PaymentRouting},
+  royalty::RoyaltyBeneficiary,
+  salvage::SalvageRefund,
+  traits::{FungibleAssets, NonFungibleAssets, OrganizationTreasury},
+  upgradable::UpgradeCost,
+  Attribute, AttributeList, AttributeValue, CommonError, DefaultListLengthLimit, DispatchResultAs,
+  LockResultOf, LockedAccet, Locker, NumberAttribute, OfferItemsLimit,
+};
+
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use sp_runtime::{
+  traits::{SaturatingSub, Zero},
+  SaturatedConversion,
 };
 
 use super::*;
@@ -30,22 +42,45 @@ impl<T: Config> Pallet<T> {
     let mechanic = Mechanics::<T>::take(&id.gamer_account, id.nonce);
     if let Some(mechanic) = mechanic {
       Timeouts::<T>::remove(mechanic.get_tiomeout_strorage_key(id.nonce));
+      // the bid escrowed by the auction always returns to the bidder
+      if let MechanicData::Auction(auction) = &mechanic.data {
+        if let Some(bid) = AuctionBids::<T>::take(&id.gamer_account, id.nonce) {
+          T::FungibleAssets::unreserve(auction.fungible_asset_id, &bid.bidder, bid.amount)?;
+        }
+      }
+      // the bet no longer holds its table
+      if let MechanicData::Bet(MechanicDataBet {
+        table_id: Some(table_id),
+        ..
+      }) = &mechanic.data
+      {
+        PendingBets::<T>::mutate(table_id, |count| *count = count.saturating_sub(1));
+      }
       // clear all locks for this mechanic
       for lock in mechanic.locked {
         let origin = Locker::Mechanic(id.clone());
         let who = id.gamer_account.account_id.clone();
         match lock {
           LockedAccet::Nfa(class_id, asset_id) => match asset_action {
-            AssetAction::Release => {
+            AssetAction::Release | AssetAction::Penalty(_) => {
               T::NonFungibleAssets::clear_lock(&who, &origin, &class_id, &asset_id)?
             },
             AssetAction::Burn => T::NonFungibleAssets::burn(class_id, asset_id, None)?,
           },
-          LockedAccet::Fa(asset_id, amount) => match asset_action {
-            AssetAction::Release => todo!("Create an clear_lock method for FA"),
-            AssetAction::Burn => {
-              let _ = T::FungibleAssets::burn_from(asset_id, &who, amount)?;
-            },
+          LockedAccet::Fa(asset_id, amount) => {
+            let amount = T::FungibleAssets::unreserve(asset_id, &who, amount)?;
+            match asset_action {
+              AssetAction::Release => (),
+              AssetAction::Burn => {
+                let _ = T::FungibleAssets::burn_from(asset_id, &who, amount)?;
+              },
+              AssetAction::Penalty(penalty) => {
+                let penalty: FungibleAssetBalance = penalty.mul_floor(*amount).into();
+                if !penalty.is_zero() {
+                  let _ = T::FungibleAssets::burn_from(asset_id, &who, penalty)?;
+                }
+              },
+            }
           },
         }
       }
@@ -116,6 +151,23 @@ impl<T: Config> Pallet<T> {
     Ok(lock_result)
   }
 
+  /// Try to lock FA for the given mechanic, both on the mechanic side and asset side.
+  ///
+  /// The locked amount is reserved on the balance of `who` until the mechanic is dropped.
+  pub(crate) fn try_lock_fa(
+    id: &MechanicIdOf<T>,
+    who: &AccountIdOf<T>,
+    asset_id: FungibleAssetId,
+    amount: FungibleAssetBalance,
+  ) -> DispatchResult {
+    T::FungibleAssets::reserve(asset_id, who, amount)?;
+    Self::try_lock(id, LockedAccet::Fa(asset_id, amount)).map_err(|e| {
+      // rollback if something goes wrong
+      let _ = T::FungibleAssets::unreserve(asset_id, who, amount);
+      e
+    })
+  }
+
   /// Clear lock for NFA
   ///
   /// Any error will be suppressed
@@ -133,33 +185,995 @@ impl<T: Config> Pallet<T> {
   }
 
   /// Execute Mechanic `exec_buy_nfa`
+  ///
+  /// `trade_in_ids` are ids of the assets traded in for the classes of the offer in the same order.
+  /// All payments are checked before the mint and burned after it.
   pub(crate) fn do_buy_nfa(
     who: &T::AccountId,
     class_id: &NonFungibleClassId,
     offer_id: &u32,
+    trade_in_ids: NonFungibleAssetIds<T>,
   ) -> DispatchResultAs<NonFungibleAssetId> {
     // checking availability of that mechanic for the nfa class
-    let (fa, price, attributes) = T::NonFungibleAssets::get_offer(class_id, offer_id)?;
+    let offer = T::NonFungibleAssets::get_offer(class_id, offer_id)?;
+    // check the sale window of the offer
+    let block_number: u32 = <frame_system::Pallet<T>>::block_number().saturated_into();
+    offer
+      .is_on_sale(block_number)
+      .map_err(|reason| match reason {
+        OfferUnavailable::NotStarted => Error::<T>::OfferNotStarted,
+        OfferUnavailable::Ended => Error::<T>::OfferEnded,
+      })?;
+    // check the stock and the purchases of the gamer
+    let sold = OfferSales::<T>::get(class_id, offer_id);
+    if let Some(stock) = offer.stock {
+      ensure!(sold < stock, Error::<T>::OfferSoldOut);
+    }
+    let bought = OfferPurchases::<T>::get((class_id, offer_id, who));
+    if let Some(max_per_gamer) = offer.max_per_gamer {
+      ensure!(bought < max_per_gamer, Error::<T>::OfferLimitReached);
+    }
     // check fa balances
-    T::FungibleAssets::can_withdraw(fa, who, price).into_result()?;
+    for (fa, amount) in offer.price.iter() {
+      T::FungibleAssets::can_withdraw(*fa, who, *amount).into_result()?;
+    }
+    // check the assets traded in, each of them must be owned by the gamer and unlocked
+    ensure!(
+      trade_in_ids.len() == offer.trade_ins.len(),
+      Error::<T>::IncompatibleData
+    );
+    for (i, (trade_in_class_id, trade_in_id)) in
+      offer.trade_ins.iter().zip(trade_in_ids.iter()).enumerate()
+    {
+      ensure!(
+        !trade_in_ids.iter().skip(i + 1).any(|id| id == trade_in_id),
+        Error::<T>::IncompatibleData
+      );
+      let details = T::NonFungibleAssets::get_asset(trade_in_class_id, trade_in_id)?;
+      ensure!(&details.owner == who, Error::<T>::NoPermission);
+      ensure!(details.locked == Locker::None, Error::<T>::AssetLocked);
+    }
     // mint nfa
     let asset_id = T::NonFungibleAssets::mint_into(class_id, who)?;
     // set attributes
-    T::NonFungibleAssets::set_attributes(&asset_id, attributes)?;
+    T::NonFungibleAssets::set_attributes(class_id, &asset_id, offer.attributes)?;
     // withdraw
-    T::FungibleAssets::burn_from(fa, who, price)?;
+    Self::pay_for_purchase(who, class_id, &offer.payment, offer.price)?;
+    for (trade_in_class_id, trade_in_id) in offer.trade_ins.into_iter().zip(trade_in_ids) {
+      T::NonFungibleAssets::burn(trade_in_class_id, trade_in_id, Some(who))?;
+    }
+    // purchases are tracked only for limited offers
+    if offer.stock.is_some() {
+      OfferSales::<T>::insert(class_id, offer_id, sold.saturating_add(1));
+    }
+    if offer.max_per_gamer.is_some() {
+      OfferPurchases::<T>::insert((class_id, offer_id, who), bought.saturating_add(1));
+    }
     Ok(asset_id)
   }
 
+  /// Withdraws the `price` of a purchase from `who` and routes it according to `payment`
+  fn pay_for_purchase(
+    who: &T::AccountId,
+    class_id: &NonFungibleClassId,
+    payment: &PaymentRouting,
+    price: BoundedVec<(FungibleAssetId, FungibleAssetBalance), OfferItemsLimit>,
+  ) -> DispatchResult {
+    // the treasury of the organization which owns the class
+    let treasury = match payment {
+      PaymentRouting::Burn => None,
+      _ => {
+        let class_details = T::NonFungibleAssets::get_class(class_id)?;
+        Some(T::OrganizationTreasury::treasury_of(&class_details.owner))
+      },
+    };
+    for (fa, amount) in price.into_iter() {
+      let (to_burn, to_treasury) = payment.split(amount);
+      if !to_burn.is_zero() {
+        T::FungibleAssets::burn_from(fa, who, to_burn)?;
+      }
+      let treasury = match &treasury {
+        Some(treasury) if !to_treasury.is_zero() => {
+          T::FungibleAssets::transfer(fa, who, treasury, to_treasury)?;
+          Some(treasury.clone())
+        },
+        _ => None,
+      };
+      Self::deposit_event(Event::PurchasePaid {
+        who: who.clone(),
+        class_id: *class_id,
+        fungible_asset_id: fa,
+        burned: to_burn,
+        transferred: to_treasury,
+        treasury,
+      });
+    }
+    Ok(())
+  }
+
+  /// Moves the `price` for the asset of the class from `buyer` to `seller`.  \
+  /// The royalty of the class is paid from the price to its beneficiary, so every mechanic which
+  /// transfers assets for value must pay through this method. Returns the paid royalty
+  pub fn pay_with_royalty(
+    buyer: &T::AccountId,
+    seller: &T::AccountId,
+    class_id: &NonFungibleClassId,
+    fungible_asset_id: FungibleAssetId,
+    price: FungibleAssetBalance,
+  ) -> DispatchResultAs<FungibleAssetBalance> {
+    let class_details = T::NonFungibleAssets::get_class(class_id)?;
+    let mut royalty = FungibleAssetBalance::zero();
+    if let Some(inner) = class_details.royalty {
+      royalty = inner.amount(price);
+      if !royalty.is_zero() {
+        let beneficiary = match inner.beneficiary {
+          RoyaltyBeneficiary::Treasury => {
+            T::OrganizationTreasury::treasury_of(&class_details.owner)
+          },
+          RoyaltyBeneficiary::Account(account) => account,
+        };
+        T::FungibleAssets::transfer(fungible_asset_id, buyer, &beneficiary, royalty)?;
+        Self::deposit_event(Event::RoyaltyPaid {
+          class_id: *class_id,
+          fungible_asset_id,
+          beneficiary,
+          amount: royalty,
+        });
+      }
+    }
+    T::FungibleAssets::transfer(
+      fungible_asset_id,
+      buyer,
+      seller,
+      price.saturating_sub(&royalty),
+    )?;
+    Ok(royalty)
+  }
+
+  /// Execute Mechanic `exec_upgrade_nfa`
+  ///
+  /// Returns the reached level
+  pub(crate) fn do_upgrade_nfa(
+    who: &T::AccountId,
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    payment_asset_id: Option<NonFungibleAssetId>,
+  ) -> DispatchResultAs<u32> {
+    // checking availability of that mechanic for the nfa class
+    let class_details = T::NonFungibleAssets::get_class(class_id)?;
+    Self::can_use_mechanic(&Mechanic::UpgradeNfa, &class_details)?;
+    let upgradable = class_details
+      .upgradable
+      .ok_or(Error::<T>::IncompatibleAsset)?;
+    // only an owned and unlocked asset can be upgraded
+    let asset_details = T::NonFungibleAssets::get_asset(class_id, asset_id)?;
+    ensure!(&asset_details.owner == who, Error::<T>::NoPermission);
+    ensure!(
+      asset_details.locked == Locker::None,
+      Error::<T>::AssetLocked
+    );
+
+    let current_level =
+      match T::NonFungibleAssets::get_attribute(class_id, asset_id, &upgradable.level_key) {
+        Some(AttributeValue::Number(level)) => level.number_value,
+        Some(_) => return Err(Error::<T>::IncompatibleData.into()),
+        None => 0,
+      };
+    let level = upgradable
+      .next_level(current_level)
+      .ok_or(Error::<T>::MaxLevelReached)?;
+    let next_level = current_level.saturating_add(1);
+
+    // calculate new values of the attributes
+    let mut attributes: AttributeList = Default::default();
+    for delta in level.deltas.iter() {
+      let mut value = match T::NonFungibleAssets::get_attribute(class_id, asset_id, &delta.key) {
+        Some(AttributeValue::Number(value)) => value,
+        _ => return Err(Error::<T>::IncompatibleData.into()),
+      };
+      value.saturating_increase(delta.delta);
+      attributes
+        .try_push(Attribute {
+          key: delta.key.clone(),
+          value: AttributeValue::Number(value),
+        })
+        .map_err(|_| Error::<T>::Internal)?;
+    }
+    attributes
+      .try_push(Attribute {
+        key: upgradable.level_key.clone(),
+        value: AttributeValue::Number(NumberAttribute {
+          number_value: next_level,
+          number_max: Some(upgradable.max_level()),
+        }),
+      })
+      .map_err(|_| Error::<T>::Internal)?;
+
+    // withdraw
+    match level.cost {
+      UpgradeCost::Fa(fa, price) => {
+        T::FungibleAssets::can_withdraw(fa, who, price).into_result()?;
+        T::FungibleAssets::burn_from(fa, who, price)?;
+      },
+      UpgradeCost::Nfa(payment_class_id) => {
+        let payment_asset_id = payment_asset_id.ok_or(Error::<T>::IncompatibleData)?;
+        ensure!(
+          (payment_class_id, payment_asset_id) != (*class_id, *asset_id),
+          Error::<T>::IncompatibleData
+        );
+        let payment_details =
+          T::NonFungibleAssets::get_asset(&payment_class_id, &payment_asset_id)?;
+        ensure!(&payment_details.owner == who, Error::<T>::NoPermission);
+        ensure!(
+          payment_details.locked == Locker::None,
+          Error::<T>::AssetLocked
+        );
+        T::NonFungibleAssets::burn(payment_class_id, payment_asset_id, Some(who))?;
+      },
+    }
+    // set attributes
+    T::NonFungibleAssets::set_attributes(class_id, asset_id, attributes)?;
+    Ok(next_level)
+  }
+
+  /// Execute Mechanic `exec_open_lootbox`
+  ///
+  /// Burns the lootbox and mints the dropped rewards. Returns the received drops
+  pub(crate) fn do_open_lootbox(
+    mechanic_id: &MechanicIdOf<T>,
+    who: &T::AccountId,
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+  ) -> DispatchResultAs<LootboxDrops> {
+    // checking availability of that mechanic for the nfa class
+    let class_details = T::NonFungibleAssets::get_class(class_id)?;
+    Self::can_use_mechanic(&Mechanic::Lootbox, &class_details)?;
+    let lootbox = class_details.lootbox.ok_or(Error::<T>::IncompatibleAsset)?;
+    // only an owned and unlocked asset can be opened
+    let asset_details = T::NonFungibleAssets::get_asset(class_id, asset_id)?;
+    ensure!(&asset_details.owner == who, Error::<T>::NoPermission);
+    ensure!(
+      asset_details.locked == Locker::None,
+      Error::<T>::AssetLocked
+    );
+    T::NonFungibleAssets::burn(*class_id, *asset_id, Some(who))?;
+
+    // a rare drop is guaranteed for the first roll when the pity threshold is reached
+    let pity_counter = LootboxPityCounters::<T>::get(&mechanic_id.gamer_account, class_id);
+    let pity_reached = lootbox.pity.as_ref().map_or(false, |pity| {
+      pity_counter.saturating_add(1) >= pity.threshold
+    });
+
+    let mut drops = LootboxDrops::default();
+    let mut rare_dropped = false;
+    for roll in 0..lootbox.rolls {
+      let weights = lootbox
+        .drops
+        .iter()
+        .map(|d| {
+          if roll == 0 && pity_reached && !d.rare {
+            0
+          } else {
+            d.weight
+          }
+        })
+        .collect();
+      let drop = &lootbox.drops[Self::choose_weighted(mechanic_id, weights, roll)];
+      rare_dropped |= drop.rare;
+      let result = match &drop.reward {
+        LootboxReward::Fa(fa, amount) => {
+          T::FungibleAssets::mint_into(*fa, who, *amount)?;
+          LootboxDropResult::Fa(*fa, *amount)
+        },
+        LootboxReward::Nfa(drop_class_id, attributes) => {
+          let drop_asset_id = T::NonFungibleAssets::mint_into(drop_class_id, who)?;
+          T::NonFungibleAssets::set_attributes(drop_class_id, &drop_asset_id, attributes.clone())?;
+          LootboxDropResult::Nfa(*drop_class_id, drop_asset_id)
+        },
+      };
+      drops.try_push(result).map_err(|_| Error::<T>::Internal)?;
+    }
+
+    if lootbox.pity.is_some() {
+      if rare_dropped {
+        LootboxPityCounters::<T>::remove(&mechanic_id.gamer_account, class_id);
+      } else {
+        LootboxPityCounters::<T>::insert(
+          &mechanic_id.gamer_account,
+          class_id,
+          pity_counter.saturating_add(1),
+        );
+      }
+    }
+    Ok(drops)
+  }
+
+  /// Execute Mechanic `exec_burn_nfa`
+  ///
+  /// Burns the asset of the gamer and mints the refunds of the Salvage characteristic if the
+  /// class has it. Returns the refunded assets
+  pub(crate) fn do_burn_nfa(
+    who: &T::AccountId,
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+  ) -> DispatchResultAs<SalvagedAssets> {
+    let class_details = T::NonFungibleAssets::get_class(class_id)?;
+    Self::can_use_mechanic(&Mechanic::BurnNfa, &class_details)?;
+    // only an owned and unlocked asset can be burned
+    let asset_details = T::NonFungibleAssets::get_asset(class_id, asset_id)?;
+    ensure!(&asset_details.owner == who, Error::<T>::NoPermission);
+    ensure!(
+      asset_details.locked == Locker::None,
+      Error::<T>::AssetLocked
+    );
+    T::NonFungibleAssets::burn(*class_id, *asset_id, Some(who))?;
+
+    let mut refunded = SalvagedAssets::default();
+    if let Some(salvage) = class_details.salvage {
+      for refund in salvage.refunds.iter() {
+        let result = match refund {
+          SalvageRefund::Fa(fa, amount) => {
+            T::FungibleAssets::mint_into(*fa, who, *amount)?;
+            LootboxDropResult::Fa(*fa, *amount)
+          },
+          SalvageRefund::Nfa(refund_class_id, attributes) => {
+            let refund_asset_id = T::NonFungibleAssets::mint_into(refund_class_id, who)?;
+            T::NonFungibleAssets::set_attributes(
+              refund_class_id,
+              &refund_asset_id,
+              attributes.clone(),
+            )?;
+            LootboxDropResult::Nfa(*refund_class_id, refund_asset_id)
+          },
+        };
+        refunded
+          .try_push(result)
+          .map_err(|_| Error::<T>::Internal)?;
+      }
+    }
+    Ok(refunded)
+  }
+
+  /// Creates a crafting recipe of the organization
+  pub(crate) fn do_create_recipe(
+    organization_id: &T::AccountId,
+    recipe: Recipe,
+  ) -> DispatchResultAs<RecipeId> {
+    ensure!(recipe.is_valid(), Error::<T>::WrongRecipe);
+    // the recipe burns and mints assets of the organization only
+    recipe
+      .ensure_references::<T::AccountId, Self>(organization_id)
+      .map_err(|err| match err {
+        CommonError::ForeignReferencedAsset => Error::<T>::NoPermission,
+        _ => Error::<T>::WrongRecipe,
+      })?;
+    // referenced assets can't be destroyed while the recipe uses them
+    for asset_id in recipe.fa_references() {
+      T::FungibleAssets::inc_references(&asset_id)?;
+    }
+    let recipe_id = NextRecipeId::<T>::try_mutate(|id| -> DispatchResultAs<RecipeId> {
+      let current_id = *id;
+      *id = id.checked_add(1).ok_or(Error::<T>::NoAvailableRecipeId)?;
+      Ok(current_id)
+    })?;
+    Recipes::<T>::insert(organization_id, recipe_id, recipe);
+    Self::deposit_event(Event::RecipeCreated {
+      organization_id: organization_id.clone(),
+      recipe_id,
+    });
+    Ok(recipe_id)
+  }
+
+  /// Removes a crafting recipe of the organization
+  pub(crate) fn do_remove_recipe(
+    organization_id: &T::AccountId,
+    recipe_id: RecipeId,
+  ) -> DispatchResult {
+    let recipe = Recipes::<T>::take(organization_id, recipe_id).ok_or(Error::<T>::UnknownRecipe)?;
+    for asset_id in recipe.fa_references() {
+      T::FungibleAssets::dec_references(&asset_id)?;
+    }
+    Self::deposit_event(Event::RecipeRemoved {
+      organization_id: organization_id.clone(),
+      recipe_id,
+    });
+    Ok(())
+  }
+
+  /// Execute Mechanic `exec_craft`
+  ///
+  /// Locks the given assets by the mechanic, checks them against the recipe,
+  /// burns all inputs and mints the outputs.
+  pub(crate) fn do_craft(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    recipe_id: RecipeId,
+    asset_ids: NonFungibleAssetIds<T>,
+  ) -> DispatchResult {
+    let recipe = Recipes::<T>::get(organization_id, recipe_id).ok_or(Error::<T>::UnknownRecipe)?;
+    // an asset must be given for each NFA input
+    ensure!(
+      asset_ids.len() == recipe.nfa_inputs.len(),
+      Error::<T>::IncompatibleData
+    );
+    let mechanic_id = Self::get_mechanic_id(who, organization_id);
+
+    // create the mechanic data
+    let mechanic =
+      MechanicDetailsBuilder::build::<T>(mechanic_id.gamer_account.clone(), MechanicData::Craft);
+    let timeout_key = mechanic.get_tiomeout_strorage_key(mechanic_id.nonce);
+    Mechanics::<T>::insert(&mechanic_id.gamer_account, mechanic_id.nonce, mechanic);
+    Timeouts::<T>::insert(timeout_key, ());
+
+    // lock and check the NFA inputs
+    for (input, asset_id) in recipe.nfa_inputs.iter().zip(asset_ids.into_iter()) {
+      Self::try_lock_nfa(&mechanic_id, who, input.class_id, asset_id)
+        .and_then(|lock_result| match lock_result {
+          // the same asset can't be used twice
+          LockResult::Already(_) => Err(Error::<T>::IncompatibleData.into()),
+          // the approved delegate can't craft with the asset of the owner
+          LockResult::Locked(details) if &details.owner != who => {
+            Err(Error::<T>::NoPermission.into())
+          },
+          LockResult::Locked(_) => Self::ensure_requirements(input, &asset_id),
+        })
+        .map_err(|err| {
+          let _ = Self::drop_mechanic(&mechanic_id, AssetAction::Release);
+          err
+        })?;
+    }
+    // check the FA inputs
+    for (fa, amount) in recipe.fa_inputs.iter() {
+      T::FungibleAssets::can_withdraw(*fa, who, *amount)
+        .into_result()
+        .map_err(|err| {
+          let _ = Self::drop_mechanic(&mechanic_id, AssetAction::Release);
+          err
+        })?;
+    }
+    // all inputs are checked, add FAs to the mechanic locks to burn them together with NFAs
+    for (fa, amount) in recipe.fa_inputs.iter() {
+      Self::try_lock_fa(&mechanic_id, who, *fa, *amount).map_err(|err| {
+        let _ = Self::drop_mechanic(&mechanic_id, AssetAction::Release);
+        err
+      })?;
+    }
+    // burn inputs
+    Self::drop_mechanic(&mechanic_id, AssetAction::Burn)?;
+
+    // mint outputs
+    let mut crafted = CraftedAssets::default();
+    for output in recipe.outputs.into_iter() {
+      match output {
+        RecipeOutput::Fa(asset_id, amount) => {
+          T::FungibleAssets::mint_into(asset_id, who, amount)?;
+        },
+        RecipeOutput::Nfa(class_id, attributes) => {
+          let asset_id = T::NonFungibleAssets::mint_into(&class_id, who)?;
+          T::NonFungibleAssets::set_attributes(&class_id, &asset_id, attributes)?;
+          crafted
+            .try_push(asset_id)
+            .map_err(|_| Error::<T>::Internal)?;
+        },
+      }
+    }
+
+    let result: EventMechanicResult = Some(EventMechanicResultData::Craft(crafted));
+    Self::deposit_event(Event::Finished {
+      id: mechanic_id.nonce,
+      owner: mechanic_id.gamer_account,
+      result,
+    });
+    Ok(())
+  }
+
+  /// Execute Mechanic `exec_list_nfa`
+  ///
+  /// Creates a listing of the asset for the `price` in the fungible asset of the organization.
+  /// The asset is locked by the listing until it's sold, cancelled or timed out
+  pub(crate) fn do_list_nfa(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    fungible_asset_id: FungibleAssetId,
+    price: FungibleAssetBalance,
+  ) -> DispatchResultAs<MechanicIdOf<T>> {
+    Self::ensure_can_sell(
+      who,
+      organization_id,
+      class_id,
+      asset_id,
+      &fungible_asset_id,
+      &price,
+    )?;
+
+    let mechanic_id = Self::get_mechanic_id(who, organization_id);
+    let data = MechanicData::Listing(MechanicDataListing {
+      fungible_asset_id,
+      price,
+    });
+    let mechanic = MechanicDetailsBuilder::build::<T>(mechanic_id.gamer_account.clone(), data);
+    let timeout_key = mechanic.get_tiomeout_strorage_key(mechanic_id.nonce);
+    Mechanics::<T>::insert(&mechanic_id.gamer_account, mechanic_id.nonce, mechanic);
+    Timeouts::<T>::insert(timeout_key, ());
+
+    Self::try_lock_nfa(&mechanic_id, who, *class_id, *asset_id).map_err(|err| {
+      let _ = Self::drop_mechanic(&mechanic_id, AssetAction::Release);
+      err
+    })?;
+    Ok(mechanic_id)
+  }
+
+  /// Checks that `who` can sell the asset for the `price` in the fungible asset of the organization
+  fn ensure_can_sell(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    fungible_asset_id: &FungibleAssetId,
+    price: &FungibleAssetBalance,
+  ) -> DispatchResult {
+    ensure!(!price.is_zero(), Error::<T>::IncompatibleData);
+    // the price is paid in a currency of the organization
+    ensure!(
+      T::FungibleAssets::owner_of(fungible_asset_id).as_ref() == Some(organization_id),
+      Error::<T>::IncompatibleAsset
+    );
+    T::NonFungibleAssets::ensure_transferable(class_id)?;
+    let asset_details = T::NonFungibleAssets::get_asset(class_id, asset_id)?;
+    ensure!(&asset_details.owner == who, Error::<T>::NoPermission);
+    Ok(())
+  }
+
+  /// Returns the listed asset and the price of the Marketplace mechanic
+  fn get_listing(
+    listing_id: &MechanicIdOf<T>,
+  ) -> DispatchResultAs<(NonFungibleClassId, NonFungibleAssetId, MechanicDataListing)> {
+    let mechanic = Mechanics::<T>::get(&listing_id.gamer_account, listing_id.nonce)
+      .ok_or(Error::<T>::MechanicsNotAvailable)?;
+    let listing = match mechanic.data {
+      MechanicData::Listing(listing) => listing,
+      _ => return Err(Error::<T>::IncompatibleData.into()),
+    };
+    match mechanic.locked.first() {
+      Some(LockedAccet::Nfa(class_id, asset_id)) => Ok((*class_id, *asset_id, listing)),
+      _ => {
+        debug_assert!(
+          false,
+          "a listing without the listed asset can't be present here"
+        );
+        Err(Error::<T>::Internal.into())
+      },
+    }
+  }
+
+  /// Execute Mechanic `exec_buy_listing`
+  ///
+  /// Pays the price of the listing to the seller and moves the listed asset to `who`.
+  /// The royalty of the class is paid from the price
+  pub(crate) fn do_buy_listing(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    listing_id: &MechanicIdOf<T>,
+  ) -> DispatchResultAs<(NonFungibleClassId, NonFungibleAssetId)> {
+    // only a gamer of the same organization can buy the asset
+    ensure!(
+      &listing_id.gamer_account.organization_id == organization_id,
+      Error::<T>::NoPermission
+    );
+    let seller = listing_id.gamer_account.account_id.clone();
+    ensure!(&seller != who, Error::<T>::NoPermission);
+    let (class_id, asset_id, listing) = Self::get_listing(listing_id)?;
+
+    Self::pay_with_royalty(
+      who,
+      &seller,
+      &class_id,
+      listing.fungible_asset_id,
+      listing.price,
+    )?;
+    Self::drop_mechanic(listing_id, AssetAction::Release)?;
+    T::NonFungibleAssets::transfer(&class_id, &asset_id, Some(&seller), who)?;
+    Ok((class_id, asset_id))
+  }
+
+  /// Cancels the listing of `who` and releases the listed asset
+  pub(crate) fn do_cancel_listing(listing_id: &MechanicIdOf<T>) -> DispatchResult {
+    Self::get_listing(listing_id)?;
+    Self::drop_mechanic(listing_id, AssetAction::Release)
+  }
+
+  /// Execute Mechanic `exec_create_auction`
+  ///
+  /// Locks the asset until the `end_block`, when it's sold to the highest bidder.
+  /// The asset returns to the seller if there are no bids
+  pub(crate) fn do_create_auction(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    fungible_asset_id: FungibleAssetId,
+    reserve_price: FungibleAssetBalance,
+    end_block: BlockNumberFor<T>,
+  ) -> DispatchResultAs<MechanicIdOf<T>> {
+    ensure!(
+      end_block > <frame_system::Pallet<T>>::block_number(),
+      Error::<T>::IncompatibleData
+    );
+    Self::ensure_can_sell(
+      who,
+      organization_id,
+      class_id,
+      asset_id,
+      &fungible_asset_id,
+      &reserve_price,
+    )?;
+
+    let mechanic_id = Self::get_mechanic_id(who, organization_id);
+    let data = MechanicData::Auction(MechanicDataAuction {
+      fungible_asset_id,
+      reserve_price,
+    });
+    let mut mechanic = MechanicDetailsBuilder::build::<T>(mechanic_id.gamer_account.clone(), data);
+    // the auction is settled by the timeout
+    mechanic.timeout_id = end_block;
+    let timeout_key = mechanic.get_tiomeout_strorage_key(mechanic_id.nonce);
+    Mechanics::<T>::insert(&mechanic_id.gamer_account, mechanic_id.nonce, mechanic);
+    Timeouts::<T>::insert(timeout_key, ());
+
+    Self::try_lock_nfa(&mechanic_id, who, *class_id, *asset_id).map_err(|err| {
+      let _ = Self::drop_mechanic(&mechanic_id, AssetAction::Release);
+      err
+    })?;
+    Ok(mechanic_id)
+  }
+
+  /// Returns the auctioned asset and the terms of the Auction mechanic
+  fn get_auction(
+    auction_id: &MechanicIdOf<T>,
+  ) -> DispatchResultAs<(NonFungibleClassId, NonFungibleAssetId, MechanicDataAuction)> {
+    let mechanic = Mechanics::<T>::get(&auction_id.gamer_account, auction_id.nonce)
+      .ok_or(Error::<T>::MechanicsNotAvailable)?;
+    let auction = match mechanic.data {
+      MechanicData::Auction(auction) => auction,
+      _ => return Err(Error::<T>::IncompatibleData.into()),
+    };
+    match mechanic.locked.first() {
+      Some(LockedAccet::Nfa(class_id, asset_id)) => Ok((*class_id, *asset_id, auction)),
+      _ => {
+        debug_assert!(
+          false,
+          "an auction without the auctioned asset can't be present here"
+        );
+        Err(Error::<T>::Internal.into())
+      },
+    }
+  }
+
+  /// Execute Mechanic `exec_bid`
+  ///
+  /// Reserves the bid on the balance of `who` and returns the outbid bid to its bidder
+  pub(crate) fn do_bid(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    auction_id: &MechanicIdOf<T>,
+    amount: FungibleAssetBalance,
+  ) -> DispatchResult {
+    // only a gamer of the same organization can bid
+    ensure!(
+      &auction_id.gamer_account.organization_id == organization_id,
+      Error::<T>::NoPermission
+    );
+    ensure!(
+      &auction_id.gamer_account.account_id != who,
+      Error::<T>::NoPermission
+    );
+    let (_, _, auction) = Self::get_auction(auction_id)?;
+    ensure!(amount >= auction.reserve_price, Error::<T>::BidTooLow);
+    let best_bid = AuctionBids::<T>::get(&auction_id.gamer_account, auction_id.nonce);
+    if let Some(best_bid) = &best_bid {
+      ensure!(amount > best_bid.amount, Error::<T>::BidTooLow);
+    }
+
+    T::FungibleAssets::reserve(auction.fungible_asset_id, who, amount)?;
+    if let Some(best_bid) = best_bid {
+      T::FungibleAssets::unreserve(auction.fungible_asset_id, &best_bid.bidder, best_bid.amount)?;
+    }
+    AuctionBids::<T>::insert(
+      &auction_id.gamer_account,
+      auction_id.nonce,
+      AuctionBid {
+        bidder: who.clone(),
+        amount,
+      },
+    );
+    Ok(())
+  }
+
+  /// Settles the auction at its end.
+  ///
+  /// The asset is sold to the highest bidder, otherwise it returns to the seller.  \
+  /// Returns the winning bid.
+  ///
+  /// The settlement isn't transactional, so it must be run by [Self::try_settle_auction]
+  pub(crate) fn settle_auction(
+    auction_id: &MechanicIdOf<T>,
+  ) -> DispatchResultAs<Option<FungibleAssetBalance>> {
+    let (class_id, asset_id, auction) = Self::get_auction(auction_id)?;
+    let seller = auction_id.gamer_account.account_id.clone();
+    let bid = match AuctionBids::<T>::take(&auction_id.gamer_account, auction_id.nonce) {
+      Some(bid) => bid,
+      None => {
+        Self::drop_mechanic(auction_id, AssetAction::Release)?;
+        return Ok(None);
+      },
+    };
+    // the asset could become non-transferable during the auction
+    T::NonFungibleAssets::ensure_transferable(&class_id)?;
+    T::FungibleAssets::unreserve(auction.fungible_asset_id, &bid.bidder, bid.amount)?;
+    Self::pay_with_royalty(
+      &bid.bidder,
+      &seller,
+      &class_id,
+      auction.fungible_asset_id,
+      bid.amount,
+    )?;
+    Self::drop_mechanic(auction_id, AssetAction::Release)?;
+    T::NonFungibleAssets::transfer(&class_id, &asset_id, Some(&seller), &bid.bidder)?;
+    Ok(Some(bid.amount))
+  }
+
+  /// Settles the auction in the storage transaction.
+  ///
+  /// If the settlement fails, all its changes are reverted, the bid returns to the bidder and
+  /// the asset returns to the seller
+  pub(crate) fn try_settle_auction(auction_id: &MechanicIdOf<T>) -> Option<FungibleAssetBalance> {
+    with_transaction(|| match Self::settle_auction(auction_id) {
+      Ok(result) => TransactionOutcome::Commit(Ok(result)),
+      Err(err) => TransactionOutcome::Rollback(Err(err)),
+    })
+    .unwrap_or_else(|_: DispatchError| {
+      let _ = Self::drop_mechanic(auction_id, AssetAction::Release);
+      None
+    })
+  }
+
+  /// Execute Mechanic `exec_propose_trade`
+  ///
+  /// Locks the `offered` assets of `who` until the `counterparty` accepts the trade giving the
+  /// `requested` assets in exchange, or the trade is cancelled or timed out
+  pub(crate) fn do_propose_trade(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    counterparty: &T::AccountId,
+    offered: TradeAssets,
+    requested: TradeAssets,
+  ) -> DispatchResultAs<MechanicIdOf<T>> {
+    ensure!(counterparty != who, Error::<T>::NoPermission);
+    ensure!(
+      !offered.is_empty() && !requested.is_empty(),
+      Error::<T>::IncompatibleData
+    );
+    Self::ensure_tradable(organization_id, &offered)?;
+    Self::ensure_tradable(organization_id, &requested)?;
+
+    let mechanic_id = Self::get_mechanic_id(who, organization_id);
+    let data = MechanicData::Trade(MechanicDataTrade {
+      counterparty: counterparty.clone(),
+      requested,
+    });
+    Self::lock_trade_assets(&mechanic_id, data, &offered)?;
+    Ok(mechanic_id)
+  }
+
+  /// Checks that the assets can be traded in the organization
+  fn ensure_tradable(organization_id: &T::AccountId, assets: &TradeAssets) -> DispatchResult {
+    for asset in assets.iter() {
+      match asset {
+        LockedAccet::Nfa(class_id, _) => T::NonFungibleAssets::ensure_transferable(class_id)?,
+        LockedAccet::Fa(asset_id, amount) => {
+          ensure!(!amount.is_zero(), Error::<T>::IncompatibleData);
+          // only a currency of the organization can be traded
+          ensure!(
+            T::FungibleAssets::owner_of(asset_id).as_ref() == Some(organization_id),
+            Error::<T>::IncompatibleAsset
+          );
+        },
+      }
+    }
+    Ok(())
+  }
+
+  /// Creates the mechanic of the trade side and locks the given assets by it
+  fn lock_trade_assets(
+    mechanic_id: &MechanicIdOf<T>,
+    data: MechanicData<T::AccountId>,
+    assets: &TradeAssets,
+  ) -> DispatchResult {
+    let who = &mechanic_id.gamer_account.account_id;
+    let mechanic = MechanicDetailsBuilder::build::<T>(mechanic_id.gamer_account.clone(), data);
+    let timeout_key = mechanic.get_tiomeout_strorage_key(mechanic_id.nonce);
+    Mechanics::<T>::insert(&mechanic_id.gamer_account, mechanic_id.nonce, mechanic);
+    Timeouts::<T>::insert(timeout_key, ());
+
+    for asset in assets.iter() {
+      match asset {
+        LockedAccet::Nfa(class_id, asset_id) => {
+          Self::try_lock_nfa(mechanic_id, who, *class_id, *asset_id).and_then(|lock_result| {
+            match lock_result {
+              // the same asset can't be traded twice
+              LockResult::Already(_) => Err(Error::<T>::IncompatibleData.into()),
+              // the approved delegate can't trade the asset of the owner
+              LockResult::Locked(details) if &details.owner != who => {
+                Err(Error::<T>::NoPermission.into())
+              },
+              LockResult::Locked(_) => Ok(()),
+            }
+          })
+        },
+        LockedAccet::Fa(asset_id, amount) => {
+          Self::try_lock_fa(mechanic_id, who, *asset_id, *amount)
+        },
+      }
+      .map_err(|err| {
+        let _ = Self::drop_mechanic(mechanic_id, AssetAction::Release);
+        err
+      })?;
+    }
+    Ok(())
+  }
+
+  /// Moves the traded assets from `source` to `dest`.  \
+  /// FAs given for NFAs pay the royalty of the `royalty_class` as the price of them.  \
+  /// The assets must be released before
+  fn transfer_trade_assets(
+    source: &T::AccountId,
+    dest: &T::AccountId,
+    assets: &TradeAssets,
+    royalty_class: Option<&NonFungibleClassId>,
+  ) -> DispatchResult {
+    for asset in assets.iter() {
+      match asset {
+        LockedAccet::Nfa(class_id, asset_id) => {
+          T::NonFungibleAssets::transfer(class_id, asset_id, Some(source), dest)?
+        },
+        LockedAccet::Fa(asset_id, amount) => match royalty_class {
+          Some(class_id) => {
+            let _ = Self::pay_with_royalty(source, dest, class_id, *asset_id, *amount)?;
+          },
+          None => {
+            let _ = T::FungibleAssets::transfer(*asset_id, source, dest, *amount)?;
+          },
+        },
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns the class of the traded NFAs with the highest royalty
+  fn trade_royalty_class(assets: &TradeAssets) -> DispatchResultAs<Option<NonFungibleClassId>> {
+    let mut royalty_class = None;
+    let mut max_percent = Percent::zero();
+    for asset in assets.iter() {
+      if let LockedAccet::Nfa(class_id, _) = asset {
+        if let Some(royalty) = T::NonFungibleAssets::get_class(class_id)?.royalty {
+          if royalty.percent > max_percent {
+            max_percent = royalty.percent;
+            royalty_class = Some(*class_id);
+          }
+        }
+      }
+    }
+    Ok(royalty_class)
+  }
+
+  /// Returns the offered assets and the terms of the Trade mechanic
+  fn get_trade(
+    trade_id: &MechanicIdOf<T>,
+  ) -> DispatchResultAs<(TradeAssets, MechanicDataTrade<T::AccountId>)> {
+    let mechanic = Mechanics::<T>::get(&trade_id.gamer_account, trade_id.nonce)
+      .ok_or(Error::<T>::MechanicsNotAvailable)?;
+    match mechanic.data {
+      MechanicData::Trade(trade) => Ok((mechanic.locked, trade)),
+      _ => Err(Error::<T>::IncompatibleData.into()),
+    }
+  }
+
+  /// Execute Mechanic `exec_accept_trade`
+  ///
+  /// Locks the requested assets of `who` and swaps them with the offered ones.
+  /// The swap is done entirely or not at all
+  pub(crate) fn do_accept_trade(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    trade_id: &MechanicIdOf<T>,
+  ) -> DispatchResult {
+    ensure!(
+      &trade_id.gamer_account.organization_id == organization_id,
+      Error::<T>::NoPermission
+    );
+    let (offered, trade) = Self::get_trade(trade_id)?;
+    ensure!(&trade.counterparty == who, Error::<T>::NoPermission);
+    let proposer = trade_id.gamer_account.account_id.clone();
+
+    // the counterparty commits to the trade by locking the requested assets
+    let counter_id = Self::get_mechanic_id(who, organization_id);
+    let data = MechanicData::Trade(MechanicDataTrade {
+      counterparty: proposer.clone(),
+      requested: Default::default(),
+    });
+    Self::lock_trade_assets(&counter_id, data, &trade.requested)?;
+
+    // both sides are committed, swap the assets
+    // the FAs given for NFAs pay the royalty as on the marketplace, the class with the highest
+    // royalty is charged when several classes are traded
+    let offered_royalty = Self::trade_royalty_class(&offered)?;
+    let requested_royalty = Self::trade_royalty_class(&trade.requested)?;
+    Self::drop_mechanic(trade_id, AssetAction::Release)?;
+    Self::drop_mechanic(&counter_id, AssetAction::Release)?;
+    Self::transfer_trade_assets(&proposer, who, &offered, requested_royalty.as_ref())?;
+    Self::transfer_trade_assets(who, &proposer, &trade.requested, offered_royalty.as_ref())?;
+    Ok(())
+  }
+
+  /// Cancels the trade by any of its sides and releases the offered assets
+  pub(crate) fn do_cancel_trade(who: &T::AccountId, trade_id: &MechanicIdOf<T>) -> DispatchResult {
+    let (_, trade) = Self::get_trade(trade_id)?;
+    ensure!(
+      &trade_id.gamer_account.account_id == who || &trade.counterparty == who,
+      Error::<T>::NoPermission
+    );
+    Self::drop_mechanic(trade_id, AssetAction::Release)
+  }
+
+  /// Cancel the active mechanic by its owner.  \
+  /// The locked assets are forfeited according to the policy of the mechanic.
+  /// The auction which has bids can't be cancelled, it's sold at its end
+  pub(crate) fn do_cancel(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    mechanic_id: &MechanicIdOf<T>,
+  ) -> DispatchResultAs<ForfeitPolicy> {
+    mechanic_id
+      .ensure_owner(who)
+      .map_err(|_| Error::<T>::NoPermission)?;
+    mechanic_id
+      .ensure_organization(organization_id)
+      .map_err(|_| Error::<T>::NoPermission)?;
+    let mechanic = Mechanics::<T>::try_get(&mechanic_id.gamer_account, mechanic_id.nonce)
+      .map_err(|_| Error::<T>::MechanicsNotAvailable)?;
+    ensure!(
+      !AuctionBids::<T>::contains_key(&mechanic_id.gamer_account, mechanic_id.nonce),
+      Error::<T>::CannotCancel
+    );
+    let policy = mechanic.data.forfeit_policy(T::CancelPenalty::get());
+    Self::drop_mechanic(mechanic_id, policy.into())?;
+    Ok(policy)
+  }
+
+  /// Checks that the asset meets the requirements of the recipe input
+  fn ensure_requirements(input: &RecipeNfaInput, asset_id: &NonFungibleAssetId) -> DispatchResult {
+    for requirement in input.requirements.iter() {
+      let value = T::NonFungibleAssets::get_attribute(&input.class_id, asset_id, &requirement.key)
+        .ok_or(Error::<T>::IncompatibleAsset)?;
+      ensure!(requirement.is_met(&value), Error::<T>::IncompatibleAsset);
+    }
+    Ok(())
+  }
+
   /// Process a bet mechanic the first time. \
-  /// Here check given asset to acceptance for the Bet mechanic,
+  /// Here check given assets to acceptance for the Bet mechanic,
   /// Create new mechanic and execute first round.
+  ///
+  /// All `asset_ids` are staked in one bet, the winnings are paid for each of them.
   pub(crate) fn do_bet(
     who: &T::AccountId,
     organization_id: &T::AccountId,
     class_id: &NonFungibleClassId,
-    asset_id: &NonFungibleAssetId,
+    asset_ids: NonFungibleAssetIds<T>,
   ) -> DispatchResult {
+    ensure!(!asset_ids.is_empty(), Error::<T>::IncompatibleData);
     let mechanic_id = Self::get_mechanic_id(who, organization_id);
 
     // create the mechanic data
@@ -169,10 +1183,22 @@ impl<T: Config> Pallet<T> {
     Mechanics::<T>::insert(&mechanic_id.gamer_account, mechanic_id.nonce, mechanic);
     Timeouts::<T>::insert(timeout_key, ());
 
-    let _ = Self::try_lock_nfa(&mechanic_id, who, *class_id, *asset_id).map_err(|err| {
-      let _ = Self::drop_mechanic(&mechanic_id, AssetAction::Release);
-      err
-    })?;
+    for asset_id in asset_ids.into_iter() {
+      Self::try_lock_nfa(&mechanic_id, who, *class_id, asset_id)
+        .and_then(|lock_result| match lock_result {
+          // the same asset can't be staked twice
+          LockResult::Already(_) => Err(Error::<T>::IncompatibleData.into()),
+          // the approved delegate can't stake the asset of the owner
+          LockResult::Locked(details) if &details.owner != who => {
+            Err(Error::<T>::NoPermission.into())
+          },
+          LockResult::Locked(_) => Ok(()),
+        })
+        .map_err(|err| {
+          let _ = Self::drop_mechanic(&mechanic_id, AssetAction::Release);
+          err
+        })?;
+    }
     let class_details = T::NonFungibleAssets::get_class(class_id)?;
     // we need to check can it acceptable to this mechanic
     Self::can_use_mechanic(&Mechanic::Bet, &class_details).map_err(|err| {
@@ -189,6 +1215,89 @@ impl<T: Config> Pallet<T> {
     Ok(())
   }
 
+  /// Execute Mechanic `exec_bet_fa`
+  ///
+  /// Locks the `stake` of the fungible asset of the bet table and plays the first round.
+  pub(crate) fn do_bet_fa(
+    who: &T::AccountId,
+    organization_id: &T::AccountId,
+    table_id: BetTableId,
+    stake: FungibleAssetBalance,
+  ) -> DispatchResult {
+    let table =
+      BetTables::<T>::get(organization_id, table_id).ok_or(Error::<T>::UnknownBetTable)?;
+    ensure!(
+      stake >= table.min_stake && stake <= table.max_stake,
+      Error::<T>::StakeOutOfRange
+    );
+    let mechanic_id = Self::get_mechanic_id(who, organization_id);
+
+    // create the mechanic data
+    let data = MechanicData::Bet(MechanicDataBet {
+      outcomes: Default::default(),
+      table_id: Some(table_id),
+    });
+    let mechanic = MechanicDetailsBuilder::build::<T>(mechanic_id.gamer_account.clone(), data);
+    let timeout_key = mechanic.get_tiomeout_strorage_key(mechanic_id.nonce);
+    Mechanics::<T>::insert(&mechanic_id.gamer_account, mechanic_id.nonce, mechanic);
+    Timeouts::<T>::insert(timeout_key, ());
+    // the table can't be removed until the bet is finished
+    PendingBets::<T>::mutate(table_id, |count| *count = count.saturating_add(1));
+
+    Self::try_lock_fa(&mechanic_id, who, table.fungible_asset_id, stake).map_err(|err| {
+      let _ = Self::drop_mechanic(&mechanic_id, AssetAction::Release);
+      err
+    })?;
+    // no results exist for the first round
+    Self::play_bet_round(who, mechanic_id, &table.to_bettor(stake), Vec::new())
+  }
+
+  /// Creates a bet table of the organization
+  pub(crate) fn do_create_bet_table(
+    organization_id: &T::AccountId,
+    table: BetTable,
+  ) -> DispatchResultAs<BetTableId> {
+    ensure!(table.is_valid(), Error::<T>::WrongBetTable);
+    // the stake is paid in a currency of the organization
+    ensure!(
+      T::FungibleAssets::owner_of(&table.fungible_asset_id).as_ref() == Some(organization_id),
+      Error::<T>::IncompatibleAsset
+    );
+    // the currency of the stakes can't be destroyed while the table uses it
+    T::FungibleAssets::inc_references(&table.fungible_asset_id)?;
+    let table_id = NextBetTableId::<T>::try_mutate(|id| -> DispatchResultAs<BetTableId> {
+      let current_id = *id;
+      *id = id.checked_add(1).ok_or(Error::<T>::NoAvailableBetTableId)?;
+      Ok(current_id)
+    })?;
+    BetTables::<T>::insert(organization_id, table_id, table);
+    Self::deposit_event(Event::BetTableCreated {
+      organization_id: organization_id.clone(),
+      table_id,
+    });
+    Ok(table_id)
+  }
+
+  /// Removes a bet table of the organization.  \
+  /// The table which has pending bets can't be removed
+  pub(crate) fn do_remove_bet_table(
+    organization_id: &T::AccountId,
+    table_id: BetTableId,
+  ) -> DispatchResult {
+    ensure!(
+      PendingBets::<T>::get(table_id).is_zero(),
+      Error::<T>::BetTableInUse
+    );
+    let table =
+      BetTables::<T>::take(organization_id, table_id).ok_or(Error::<T>::UnknownBetTable)?;
+    T::FungibleAssets::dec_references(&table.fungible_asset_id)?;
+    Self::deposit_event(Event::BetTableRemoved {
+      organization_id: organization_id.clone(),
+      table_id,
+    });
+    Ok(())
+  }
+
   /// Process existed Bet mechanic by it id. \
   /// Where is no checks about the bettor asset -
   /// any checks has been executed in [do_bet()]
@@ -203,33 +1312,35 @@ impl<T: Config> Pallet<T> {
     let mechanic = Mechanics::<T>::try_get(&mechanic_id.gamer_account, mechanic_id.nonce)
       .map_err(|_| Error::<T>::MechanicsNotAvailable)?;
     // get bet asset from mechanic lock
-    // for the Bet mechanic only one asset can be using
-    if mechanic.locked.len() != 1 {
-      debug_assert!(
-        false,
-        "for the Bet mechanic only one asset can be using (locked)"
-      );
-      return Err(Error::<T>::Internal.into());
-    }
-    let bet_asset = mechanic.locked[0];
-    let (class_id, _) = match bet_asset {
-      LockedAccet::Nfa(class_id, asset_id) => (class_id, asset_id),
-      // expect only NFA Bettor
-      LockedAccet::Fa(_, _) => return Err(Error::<T>::Internal.into()),
+    // all NFAs staked in the Bet mechanic are of the same class, the FA is staked alone
+    let bet_asset = match mechanic.locked.first() {
+      Some(bet_asset) => *bet_asset,
+      None => {
+        debug_assert!(false, "the Bet mechanic can't be played without a stake");
+        return Err(Error::<T>::Internal.into());
+      },
     };
-    let class_details = T::NonFungibleAssets::get_class(&class_id)?;
 
     // get played outcomes
-    let outcomes = if let MechanicData::Bet(data_bet) = mechanic.data {
-      data_bet.outcomes.into_inner()
+    let (outcomes, table_id) = if let MechanicData::Bet(data_bet) = mechanic.data {
+      (data_bet.outcomes.into_inner(), data_bet.table_id)
     } else {
       debug_assert!(
         false,
         "a mechanic data whitout the bettor can't be present here"
       );
-      Vec::new()
+      (Vec::new(), None)
     };
-    if let Some(bettor) = class_details.bettor {
+    let bettor = match (bet_asset, table_id) {
+      (LockedAccet::Nfa(class_id, _), None) => T::NonFungibleAssets::get_class(&class_id)?.bettor,
+      (LockedAccet::Fa(_, stake), Some(table_id)) => {
+        let table = BetTables::<T>::get(&mechanic_id.gamer_account.organization_id, table_id)
+          .ok_or(Error::<T>::UnknownBetTable)?;
+        Some(table.to_bettor(stake))
+      },
+      _ => None,
+    };
+    if let Some(bettor) = bettor {
       Self::play_bet_round(who, mechanic_id, &bettor, outcomes)?;
     } else {
       debug_assert!(false, "an asset whitout the bettor can't be present here");
@@ -281,7 +1392,7 @@ impl<T: Config> Pallet<T> {
   /// TODO: deal with randomness freshness
   /// https://github.com/paritytech/substrate/issues/8311
   /// Taken from https://github.com/paritytech/substrate/blob/d602397a0bbb24b5d627795b797259a44a5e29e9/frame/lottery/src/lib.rs#L506
-  fn generate_random_number(mechanic_id: &MechanicIdOf<T>, seed_suffix: u32) -> u32 {
+  fn generate_random_number<S: Encode>(mechanic_id: &MechanicIdOf<T>, seed_suffix: S) -> u32 {
     let (random_seed, _) = T::Randomness::random(&(mechanic_id, seed_suffix).encode());
     let random_number = <u32>::decode(&mut random_seed.as_ref())
       .expect("secure hashes should always be bigger than u32; qed");
@@ -301,6 +1412,20 @@ impl<T: Config> Pallet<T> {
     random_number % total
   }
 
+  /// Randomly choose a variant like [Self::choose_variant], but the random seed is mixed with
+  /// `salt`, so a several independent variants can be chosen by one mechanic
+  pub(crate) fn choose_salted_variant(mechanic_id: &MechanicIdOf<T>, total: u32, salt: u32) -> u32 {
+    let mut random_number = Self::generate_random_number(mechanic_id, (salt, 0u32));
+    // Best effort attempt to remove bias from modulus operator.
+    for i in 1..total {
+      if random_number < u32::MAX - u32::MAX % total {
+        break;
+      }
+      random_number = Self::generate_random_number(mechanic_id, (salt, i));
+    }
+    random_number % total
+  }
+
   /// Randomly choose an outcome index by given probability
   ///
   /// Returns an index of some selected outcomes.  \
@@ -311,16 +1436,34 @@ impl<T: Config> Pallet<T> {
   ) -> u32 {
     let probs = cumsum_owned(outcomes.iter().map(|o| o.probability).collect());
     let random_variant = Self::choose_variant(mechanic_id, probs[probs.len() - 1]);
-    let mut chosen_outcome_idx = probs.len() - 1;
+    Self::find_weighted_index(probs, random_variant)
+      .try_into()
+      .expect("BoundedVec index can't overfolow DefaultListLengthLimit")
+  }
+
+  /// Randomly choose an index by given weights using `salt` for the random seed
+  ///
+  /// **Warn**: `weights` must not be empty and its sum must be more than 0
+  pub(crate) fn choose_weighted(
+    mechanic_id: &MechanicIdOf<T>,
+    weights: Vec<u32>,
+    salt: u32,
+  ) -> usize {
+    let probs = cumsum_owned(weights);
+    let random_variant = Self::choose_salted_variant(mechanic_id, probs[probs.len() - 1], salt);
+    Self::find_weighted_index(probs, random_variant)
+  }
+
+  /// Returns an index of the first cumulative probability which is more than the variant
+  fn find_weighted_index(probs: Vec<u32>, random_variant: u32) -> usize {
+    let mut chosen_idx = probs.len() - 1;
     for (idx, prob) in probs.into_iter().enumerate() {
       if prob > random_variant {
-        chosen_outcome_idx = idx;
+        chosen_idx = idx;
         break;
       }
     }
-    chosen_outcome_idx
-      .try_into()
-      .expect("BoundedVec index can't overfolow DefaultListLengthLimit")
+    chosen_idx
   }
 
   /// Trying to determine the winner by completed rounds
@@ -382,14 +1525,27 @@ impl<T: Config> Pallet<T> {
 
     match result {
       BetResult::Won => {
+        // each staked NFA wins separately
+        let stakes =
+          Mechanics::<T>::get(&mechanic_id.gamer_account, mechanic_id.nonce).map_or(1, |mechanic| {
+            mechanic
+              .locked
+              .iter()
+              .filter(|lock| matches!(lock, LockedAccet::Nfa(..)))
+              .count()
+              .max(1)
+          }) as u32;
         // mint assets
         for wining in bettor.winnings.clone() {
           match wining {
             BettorWinning::Fa(asset_id, amount) => {
+              let amount = amount.saturating_mul(stakes.into()).into();
               T::FungibleAssets::mint_into(asset_id, who, amount)?;
             },
             BettorWinning::Nfa(class_id) => {
-              T::NonFungibleAssets::mint_into(&class_id, who)?;
+              for _ in 0..stakes {
+                T::NonFungibleAssets::mint_into(&class_id, who)?;
+              }
             },
           }
         }
@@ -437,13 +1593,21 @@ impl<T: Config> Pallet<T> {
           .to_vec()
           .try_into()
           .expect("the number of values cannot exceed the number of rounds");
-        let data = MechanicData::Bet(MechanicDataBet { outcomes });
         let mechanic = match maybe_mechanic {
           Some(ref mut mechanic) => {
-            mechanic.data = data;
+            // keep the bet table of the FA staked bet
+            let table_id = match &mechanic.data {
+              MechanicData::Bet(data_bet) => data_bet.table_id,
+              _ => None,
+            };
+            mechanic.data = MechanicData::Bet(MechanicDataBet { outcomes, table_id });
             (*mechanic).clone()
           },
           maybe_mechanic @ None => {
+            let data = MechanicData::Bet(MechanicDataBet {
+              outcomes,
+              table_id: None,
+            });
             let mechanic = MechanicDetailsBuilder::build::<T>(id.gamer_account.clone(), data);
             Timeouts::<T>::insert(mechanic.get_tiomeout_strorage_key(id.nonce), ());
             *maybe_mechanic = Some(mechanic.clone());
@@ -476,6 +1640,28 @@ impl<T: Config> Pallet<T> {
           Ok(())
         }
       },
+      Mechanic::UpgradeNfa => {
+        if class_details.upgradable.is_none() {
+          Err(Error::<T>::IncompatibleAsset.into())
+        } else {
+          Ok(())
+        }
+      },
+      // assets are checked by the recipe
+      Mechanic::Craft => Ok(()),
+      Mechanic::Lootbox => {
+        if class_details.lootbox.is_none() {
+          Err(Error::<T>::IncompatibleAsset.into())
+        } else {
+          Ok(())
+        }
+      },
+      // any asset can be burned by its owner, the Salvage characteristic is optional
+      Mechanic::BurnNfa => Ok(()),
+      // the transferability of assets is checked by the listing
+      Mechanic::Marketplace => Ok(()),
+      Mechanic::Auction => Ok(()),
+      Mechanic::Trade => Ok(()),
     }
   }
 
@@ -527,19 +1713,40 @@ impl<T: Config> Pallet<T> {
   /// Returns weight and count of dropped mechanics
   pub(crate) fn process_mechanic_timeouts() -> (Weight, u32) {
     let mut mechanics_count = 0;
+    let mut weight: Weight = 0;
     let curr_block = <frame_system::Pallet<T>>::block_number();
     for (mechanic_id, _) in Timeouts::<T>::drain_prefix((curr_block,)) {
       let id = MechanicId {
         gamer_account: mechanic_id.0,
         nonce: mechanic_id.1,
       };
-      let _ = Self::drop_mechanic(&id, AssetAction::Burn);
-      Self::deposit_event(Event::DroppedByTimeout {
-        owner: id.gamer_account,
-        id: id.nonce,
-      });
+      let mechanic = Mechanics::<T>::get(&id.gamer_account, id.nonce);
+      // the timeout, the mechanic and each of its locks
+      let locks = mechanic
+        .as_ref()
+        .map_or(0, |mechanic| mechanic.locked.len() as u64);
+      weight = weight.saturating_add(T::DbWeight::get().reads_writes(2 + locks, 2 + locks));
+      let data = mechanic.map(|mechanic| mechanic.data);
+      if let Some(MechanicData::Auction(_)) = data {
+        // auctions are settled instead of being dropped:
+        // the bid, the class, the reserve, the royalty and the price payments, the transfer
+        weight = weight.saturating_add(T::DbWeight::get().reads_writes(6, 6));
+        let result = Self::try_settle_auction(&id);
+        Self::deposit_event(Event::Finished {
+          id: id.nonce,
+          owner: id.gamer_account,
+          result: Some(EventMechanicResultData::Auction(result)),
+        });
+      } else {
+        let action = data.map_or(AssetAction::Burn, |data| data.timeout_action());
+        let _ = Self::drop_mechanic(&id, action);
+        Self::deposit_event(Event::DroppedByTimeout {
+          owner: id.gamer_account,
+          id: id.nonce,
+        });
+      }
       mechanics_count = mechanics_count.saturating_add(1);
     }
-    (0, mechanics_count)
+    (weight, mechanics_count)
   }
 }
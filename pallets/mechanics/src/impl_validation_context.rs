@@ -0,0 +1,18 @@
+use pallet_support::{
+  traits::{FungibleAssets, NonFungibleAssets},
+  FungibleAssetId, NonFungibleClassId, ValidationContext,
+};
+
+use super::*;
+
+impl<T: pallet::Config> ValidationContext<AccountIdOf<T>> for Pallet<T> {
+  fn fa_owner(asset_id: &FungibleAssetId) -> Option<AccountIdOf<T>> {
+    T::FungibleAssets::owner_of(asset_id)
+  }
+
+  fn nfa_class_owner(class_id: &NonFungibleClassId) -> Option<AccountIdOf<T>> {
+    T::NonFungibleAssets::get_class(class_id)
+      .ok()
+      .map(|details| details.owner)
+  }
+}
@@ -12,6 +12,8 @@ mod tests;
 mod benchmarking;
 
 mod functions;
+mod impl_validation_context;
+pub mod migration;
 mod types;
 
 pub use pallet_support::{
@@ -22,17 +24,24 @@ pub use types::*;
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
 
-use sp_runtime::traits::Saturating;
+use sp_runtime::{traits::Saturating, Percent};
 
-use frame_support::{log, traits::Randomness};
+use frame_support::{
+  log,
+  traits::{EnsureOriginWithArg, Randomness},
+};
 
 #[frame_support::pallet]
 pub mod pallet {
   use super::*;
-  use pallet_support::GameAccountOf;
+  use pallet_support::{DefaultListLengthLimit, GameAccountOf, GamerAccount};
+
+  /// The current storage version.
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
   #[pallet::pallet]
   #[pallet::generate_store(pub(super) trait Store)]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
   /// Configure the pallet by specifying the parameters and types on which it depends.
@@ -44,6 +53,8 @@ pub mod pallet {
     type FungibleAssets: pallet_support::traits::FungibleAssets<Self::AccountId>;
     /// Connector to non-fungible assets instances.
     type NonFungibleAssets: pallet_support::traits::NonFungibleAssets<Self::AccountId, Self::Index>;
+    /// Connector to treasuries of organizations.
+    type OrganizationTreasury: pallet_support::traits::OrganizationTreasury<Self::AccountId>;
     /// Something that provides randomness in the runtime.
     type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
     /// The origin which may execute mechanics.
@@ -51,6 +62,8 @@ pub mod pallet {
     /// Mechanics can only be executed by a regular user, neither the organization nor any of its
     /// members can execute mechanics
     type ExecuteOrigin: frame_support::traits::EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+    /// The origin which may manage the mechanics settings of an organization (e.g. recipes).
+    type CreateOrigin: EnsureOriginWithArg<Self::Origin, Self::AccountId>;
     /// The maximum list length to pass to mechanics.
     #[pallet::constant]
     type AssetsListLimit: Get<u32>;
@@ -58,6 +71,9 @@ pub mod pallet {
     /// occurs, mechanics will be destroyed.
     #[pallet::constant]
     type MechanicsLifeTime: Get<Self::BlockNumber>;
+    /// Share of the fungible stake burned when the gamer cancels a bet played on a bet table.
+    #[pallet::constant]
+    type CancelPenalty: Get<Percent>;
   }
 
   #[pallet::storage]
@@ -85,6 +101,93 @@ pub mod pallet {
     OptionQuery,
   >;
 
+  #[pallet::storage]
+  /// The highest bids of the active auctions.
+  pub(super) type AuctionBids<T: Config> = StorageDoubleMap<
+    _,
+    Twox64Concat,
+    GameAccountOf<T>,
+    Twox64Concat,
+    T::Index,
+    AuctionBidOf<T>,
+    OptionQuery,
+  >;
+
+  #[pallet::storage]
+  /// Crafting recipes of organizations.
+  pub(super) type Recipes<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Blake2_128Concat,
+    RecipeId,
+    Recipe,
+    OptionQuery,
+  >;
+
+  #[pallet::storage]
+  /// Number of lootbox openings of the gamer without a rare drop, for each lootbox class.
+  pub(super) type LootboxPityCounters<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    GameAccountOf<T>,
+    Blake2_128Concat,
+    NonFungibleClassId,
+    u32,
+    ValueQuery,
+  >;
+
+  #[pallet::storage]
+  /// Number of assets sold by the limited offers of the Purchased characteristic.
+  pub(super) type OfferSales<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    NonFungibleClassId,
+    Blake2_128Concat,
+    u32,
+    u32,
+    ValueQuery,
+  >;
+
+  #[pallet::storage]
+  /// Number of assets bought by gamers by the offers of the Purchased characteristic
+  /// which limit purchases per gamer.
+  pub(super) type OfferPurchases<T: Config> = StorageNMap<
+    _,
+    (
+      NMapKey<Blake2_128Concat, NonFungibleClassId>,
+      NMapKey<Blake2_128Concat, u32>,
+      NMapKey<Blake2_128Concat, T::AccountId>,
+    ),
+    u32,
+    ValueQuery,
+  >;
+
+  #[pallet::storage]
+  /// Storing the next recipe id
+  pub type NextRecipeId<T: Config> = StorageValue<_, RecipeId, ValueQuery>;
+
+  #[pallet::storage]
+  /// Bet tables of organizations where gamers stake fungible assets.
+  pub(super) type BetTables<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Blake2_128Concat,
+    BetTableId,
+    BetTable,
+    OptionQuery,
+  >;
+
+  #[pallet::storage]
+  /// Storing the next bet table id
+  pub type NextBetTableId<T: Config> = StorageValue<_, BetTableId, ValueQuery>;
+
+  #[pallet::storage]
+  /// The number of bets pending on a bet table.
+  pub(super) type PendingBets<T: Config> =
+    StorageMap<_, Blake2_128Concat, BetTableId, u32, ValueQuery>;
+
   #[pallet::event]
   #[pallet::generate_deposit(pub(super) fn deposit_event)]
   pub enum Event<T: Config> {
@@ -105,6 +208,102 @@ pub mod pallet {
       owner: GameAccountOf<T>,
       id: T::Index,
     },
+    /// A payment for a purchase has been made.
+    ///
+    /// `burned` amount of the fungible asset was burned, `transferred` amount was moved to the
+    /// `treasury` of the organization.
+    PurchasePaid {
+      who: T::AccountId,
+      class_id: NonFungibleClassId,
+      fungible_asset_id: FungibleAssetId,
+      burned: FungibleAssetBalance,
+      transferred: FungibleAssetBalance,
+      treasury: Option<T::AccountId>,
+    },
+    /// A royalty has been paid to the `beneficiary` on the sale of an asset of the class.
+    RoyaltyPaid {
+      class_id: NonFungibleClassId,
+      fungible_asset_id: FungibleAssetId,
+      beneficiary: T::AccountId,
+      amount: FungibleAssetBalance,
+    },
+    /// An asset has been listed on the marketplace.
+    Listed {
+      owner: GameAccountOf<T>,
+      id: T::Index,
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      fungible_asset_id: FungibleAssetId,
+      price: FungibleAssetBalance,
+    },
+    /// A listed asset has been sold to the `buyer`.
+    ListingSold {
+      owner: GameAccountOf<T>,
+      id: T::Index,
+      buyer: T::AccountId,
+    },
+    /// A listing has been cancelled by the seller and the asset is released.
+    ListingCancelled {
+      owner: GameAccountOf<T>,
+      id: T::Index,
+    },
+    /// An asset has been put up for the auction.
+    AuctionCreated {
+      owner: GameAccountOf<T>,
+      id: T::Index,
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      fungible_asset_id: FungibleAssetId,
+      reserve_price: FungibleAssetBalance,
+      end_block: T::BlockNumber,
+    },
+    /// A new highest bid has been placed on the auction.
+    BidPlaced {
+      owner: GameAccountOf<T>,
+      id: T::Index,
+      bidder: T::AccountId,
+      amount: FungibleAssetBalance,
+    },
+    /// A trade has been proposed to the `counterparty` and the offered assets are locked.
+    TradeProposed {
+      owner: GameAccountOf<T>,
+      id: T::Index,
+      counterparty: T::AccountId,
+      offered: TradeAssets,
+      requested: TradeAssets,
+    },
+    /// The trade has been accepted by the counterparty and the assets are swapped.
+    TradeAccepted {
+      owner: GameAccountOf<T>,
+      id: T::Index,
+      counterparty: T::AccountId,
+    },
+    /// The trade has been cancelled by one of its sides and the offered assets are released.
+    TradeCancelled {
+      owner: GameAccountOf<T>,
+      id: T::Index,
+      by: T::AccountId,
+    },
+    /// A crafting recipe has been created.
+    RecipeCreated {
+      organization_id: T::AccountId,
+      recipe_id: RecipeId,
+    },
+    /// A crafting recipe has been removed.
+    RecipeRemoved {
+      organization_id: T::AccountId,
+      recipe_id: RecipeId,
+    },
+    /// A bet table has been created.
+    BetTableCreated {
+      organization_id: T::AccountId,
+      table_id: BetTableId,
+    },
+    /// A bet table has been removed.
+    BetTableRemoved {
+      organization_id: T::AccountId,
+      table_id: BetTableId,
+    },
   }
 
   // Errors inform users that something went wrong.
@@ -122,6 +321,38 @@ pub mod pallet {
     IncompatibleData,
     /// The signing account has no permission to do the operation.
     NoPermission,
+    /// Asset is locked
+    AssetLocked,
+    /// The asset has reached the maximum level
+    MaxLevelReached,
+    /// The recipe is not found
+    UnknownRecipe,
+    /// The recipe is invalid
+    WrongRecipe,
+    /// No available recipe ID
+    NoAvailableRecipeId,
+    /// The sale of the offer hasn't started yet
+    OfferNotStarted,
+    /// The sale of the offer is over
+    OfferEnded,
+    /// All assets of the offer are sold
+    OfferSoldOut,
+    /// The gamer has bought the maximum number of assets of the offer
+    OfferLimitReached,
+    /// The bet table is not found
+    UnknownBetTable,
+    /// The bet table is invalid
+    WrongBetTable,
+    /// No available bet table ID
+    NoAvailableBetTableId,
+    /// The bet table can't be removed while bets on it are pending
+    BetTableInUse,
+    /// The stake is out of the limits of the bet table
+    StakeOutOfRange,
+    /// The bid is below the reserve price or doesn't exceed the highest bid of the auction
+    BidTooLow,
+    /// The mechanic can't be cancelled in its current state
+    CannotCancel,
   }
 
   // Implement the pallet hooks.
@@ -149,18 +380,24 @@ pub mod pallet {
   #[pallet::call]
   impl<T: Config> Pallet<T> {
     /// Execute mechanic `Buy NFA`
-    #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+    ///
+    /// `trade_in_ids` are ids of the assets traded in for the classes of the offer in the same order.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      3 + 2 * trade_in_ids.len() as u64,
+      3 + 2 * trade_in_ids.len() as u64
+    ))]
     pub fn exec_buy_nfa(
       origin: OriginFor<T>,
       organization_id: AccountIdOf<T>,
       class_id: NonFungibleClassId,
       offer_id: u32,
+      trade_in_ids: NonFungibleAssetIds<T>,
     ) -> DispatchResult {
       // Only a regular user can execute mechanic
       let who = T::ExecuteOrigin::ensure_origin(origin)?;
       // Generate mechanic id
       let mechanic_id = Self::get_mechanic_id(&who, &organization_id);
-      let asset_id = Self::do_buy_nfa(&who, &class_id, &offer_id)?;
+      let asset_id = Self::do_buy_nfa(&who, &class_id, &offer_id, trade_in_ids)?;
 
       let result: EventMechanicResult = Some(EventMechanicResultData::BuyNfa(asset_id));
       Self::deposit_event(Event::Finished {
@@ -172,16 +409,414 @@ pub mod pallet {
     }
 
     /// Execute mechanic `Bet`
-    #[pallet::weight(T::DbWeight::get().reads_writes(5, 5))]
+    ///
+    /// `asset_ids` are ids of the assets of the class staked in one bet.
+    /// The winnings are paid for each of them.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      4 + asset_ids.len() as u64,
+      4 + 2 * asset_ids.len() as u64
+    ))]
     pub fn exec_bet(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      class_id: NonFungibleClassId,
+      asset_ids: NonFungibleAssetIds<T>,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      Self::do_bet(&who, &organization_id, &class_id, asset_ids)?;
+      Ok(())
+    }
+
+    /// Execute mechanic `Bet` staking a fungible asset
+    ///
+    /// The `stake` is locked during the rounds of the bet table of the organization.  \
+    /// The won bet pays the stake multiplied by the payout of the table, the draw returns it.
+    #[pallet::weight(T::DbWeight::get().reads_writes(5, 5))]
+    pub fn exec_bet_fa(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      table_id: BetTableId,
+      stake: FungibleAssetBalance,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      Self::do_bet_fa(&who, &organization_id, table_id, stake)?;
+      Ok(())
+    }
+
+    /// Execute mechanic `Upgrade NFA`
+    ///
+    /// Upgrades the asset to the next level of the Upgradable characteristic.  \
+    /// `payment_asset_id` is the asset to burn if the level costs an NFA.
+    #[pallet::weight(T::DbWeight::get().reads_writes(6, 3))]
+    pub fn exec_upgrade_nfa(
       origin: OriginFor<T>,
       organization_id: AccountIdOf<T>,
       class_id: NonFungibleClassId,
       asset_id: NonFungibleAssetId,
+      payment_asset_id: Option<NonFungibleAssetId>,
     ) -> DispatchResult {
       // Only a regular user can execute mechanic
       let who = T::ExecuteOrigin::ensure_origin(origin)?;
-      Self::do_bet(&who, &organization_id, &class_id, &asset_id)?;
+      // Generate mechanic id
+      let mechanic_id = Self::get_mechanic_id(&who, &organization_id);
+      let level = Self::do_upgrade_nfa(&who, &class_id, &asset_id, payment_asset_id)?;
+
+      let result: EventMechanicResult = Some(EventMechanicResultData::UpgradeNfa(level));
+      Self::deposit_event(Event::Finished {
+        id: mechanic_id.nonce,
+        owner: mechanic_id.gamer_account,
+        result,
+      });
+      Ok(())
+    }
+
+    /// Execute mechanic `Open Lootbox`
+    ///
+    /// Burns the lootbox asset and mints the dropped rewards.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      4 + 2 * pallet_support::LOOTBOX_MAX_ROLLS as u64,
+      2 + 2 * pallet_support::LOOTBOX_MAX_ROLLS as u64
+    ))]
+    pub fn exec_open_lootbox(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      // Generate mechanic id
+      let mechanic_id = Self::get_mechanic_id(&who, &organization_id);
+      let drops = Self::do_open_lootbox(&mechanic_id, &who, &class_id, &asset_id)?;
+
+      let result: EventMechanicResult = Some(EventMechanicResultData::Lootbox(drops));
+      Self::deposit_event(Event::Finished {
+        id: mechanic_id.nonce,
+        owner: mechanic_id.gamer_account,
+        result,
+      });
+      Ok(())
+    }
+
+    /// Execute mechanic `Burn NFA`
+    ///
+    /// Burns the unlocked asset of the gamer.  \
+    /// If the class has the Salvage characteristic, its refunds are minted to the gamer.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      3 + 2 * pallet_support::SalvageRefundsLimit::get() as u64,
+      2 + 2 * pallet_support::SalvageRefundsLimit::get() as u64
+    ))]
+    pub fn exec_burn_nfa(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      // Generate mechanic id
+      let mechanic_id = Self::get_mechanic_id(&who, &organization_id);
+      let refunded = Self::do_burn_nfa(&who, &class_id, &asset_id)?;
+
+      let result: EventMechanicResult = Some(EventMechanicResultData::BurnNfa(refunded));
+      Self::deposit_event(Event::Finished {
+        id: mechanic_id.nonce,
+        owner: mechanic_id.gamer_account,
+        result,
+      });
+      Ok(())
+    }
+
+    /// Execute mechanic `Marketplace`
+    ///
+    /// Lists the asset for the `price` in the fungible asset of the organization.  \
+    /// The asset is locked until the listing is bought, cancelled or timed out.
+    #[pallet::weight(T::DbWeight::get().reads_writes(4, 3))]
+    pub fn exec_list_nfa(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      fungible_asset_id: FungibleAssetId,
+      price: FungibleAssetBalance,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      let mechanic_id = Self::do_list_nfa(
+        &who,
+        &organization_id,
+        &class_id,
+        &asset_id,
+        fungible_asset_id,
+        price,
+      )?;
+      Self::deposit_event(Event::Listed {
+        id: mechanic_id.nonce,
+        owner: mechanic_id.gamer_account,
+        class_id,
+        asset_id,
+        fungible_asset_id,
+        price,
+      });
+      Ok(())
+    }
+
+    /// Buy the asset listed by another gamer of the organization
+    ///
+    /// The price is paid to the seller, the royalty of the class is paid from it.
+    #[pallet::weight(T::DbWeight::get().reads_writes(6, 7))]
+    pub fn exec_buy_listing(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      listing_id: MechanicIdOf<T>,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      Self::do_buy_listing(&who, &organization_id, &listing_id)?;
+      Self::deposit_event(Event::ListingSold {
+        id: listing_id.nonce,
+        owner: listing_id.gamer_account,
+        buyer: who,
+      });
+      Ok(())
+    }
+
+    /// Cancel the listing of the gamer and release the listed asset
+    #[pallet::weight(T::DbWeight::get().reads_writes(2, 3))]
+    pub fn cancel_listing(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      id: T::Index,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      let listing_id = MechanicId {
+        gamer_account: GamerAccount {
+          account_id: who,
+          organization_id,
+        },
+        nonce: id,
+      };
+      Self::do_cancel_listing(&listing_id)?;
+      Self::deposit_event(Event::ListingCancelled {
+        id: listing_id.nonce,
+        owner: listing_id.gamer_account,
+      });
+      Ok(())
+    }
+
+    /// Execute mechanic `Auction`
+    ///
+    /// Puts the asset up for the auction with bids in the fungible asset of the organization.  \
+    /// At the `end_block` the asset is sold to the highest bidder if the bid reaches the
+    /// `reserve_price`, otherwise it returns to the seller.
+    #[pallet::weight(T::DbWeight::get().reads_writes(4, 3))]
+    pub fn exec_create_auction(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      fungible_asset_id: FungibleAssetId,
+      reserve_price: FungibleAssetBalance,
+      end_block: T::BlockNumber,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      let mechanic_id = Self::do_create_auction(
+        &who,
+        &organization_id,
+        &class_id,
+        &asset_id,
+        fungible_asset_id,
+        reserve_price,
+        end_block,
+      )?;
+      Self::deposit_event(Event::AuctionCreated {
+        id: mechanic_id.nonce,
+        owner: mechanic_id.gamer_account,
+        class_id,
+        asset_id,
+        fungible_asset_id,
+        reserve_price,
+        end_block,
+      });
+      Ok(())
+    }
+
+    /// Bid on the auction of another gamer of the organization
+    ///
+    /// The bid is reserved until it's outbid or the auction ends.
+    #[pallet::weight(T::DbWeight::get().reads_writes(4, 3))]
+    pub fn exec_bid(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      auction_id: MechanicIdOf<T>,
+      amount: FungibleAssetBalance,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      Self::do_bid(&who, &organization_id, &auction_id, amount)?;
+      Self::deposit_event(Event::BidPlaced {
+        id: auction_id.nonce,
+        owner: auction_id.gamer_account,
+        bidder: who,
+        amount,
+      });
+      Ok(())
+    }
+
+    /// Execute mechanic `Trade`
+    ///
+    /// Proposes to the `counterparty` to swap the `offered` assets for the `requested` ones.  \
+    /// The offered assets are locked until the trade is accepted, cancelled or timed out.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      2 + 2 * (offered.len() + requested.len()) as u64,
+      2 + offered.len() as u64
+    ))]
+    pub fn exec_propose_trade(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      counterparty: AccountIdOf<T>,
+      offered: TradeAssets,
+      requested: TradeAssets,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      let mechanic_id = Self::do_propose_trade(
+        &who,
+        &organization_id,
+        &counterparty,
+        offered.clone(),
+        requested.clone(),
+      )?;
+      Self::deposit_event(Event::TradeProposed {
+        id: mechanic_id.nonce,
+        owner: mechanic_id.gamer_account,
+        counterparty,
+        offered,
+        requested,
+      });
+      Ok(())
+    }
+
+    /// Accept the trade proposed to the gamer
+    ///
+    /// The requested assets of the gamer are swapped with the offered ones at once.  \
+    /// FAs given for NFAs pay the royalty of the traded class with the highest royalty.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      4 + 5 * DefaultListLengthLimit::get() as u64,
+      4 + 5 * DefaultListLengthLimit::get() as u64
+    ))]
+    pub fn exec_accept_trade(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      trade_id: MechanicIdOf<T>,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      Self::do_accept_trade(&who, &organization_id, &trade_id)?;
+      Self::deposit_event(Event::TradeAccepted {
+        id: trade_id.nonce,
+        owner: trade_id.gamer_account,
+        counterparty: who,
+      });
+      Ok(())
+    }
+
+    /// Cancel the trade by any of its sides and release the offered assets
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      2 + DefaultListLengthLimit::get() as u64,
+      2 + DefaultListLengthLimit::get() as u64
+    ))]
+    pub fn cancel_trade(origin: OriginFor<T>, trade_id: MechanicIdOf<T>) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      Self::do_cancel_trade(&who, &trade_id)?;
+      Self::deposit_event(Event::TradeCancelled {
+        id: trade_id.nonce,
+        owner: trade_id.gamer_account,
+        by: who,
+      });
+      Ok(())
+    }
+
+    /// Execute mechanic `Craft`
+    ///
+    /// `asset_ids` are ids of the assets for the NFA inputs of the recipe in the same order.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      3 + 2 * asset_ids.len() as u64,
+      3 + 2 * asset_ids.len() as u64
+    ))]
+    pub fn exec_craft(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      recipe_id: RecipeId,
+      asset_ids: NonFungibleAssetIds<T>,
+    ) -> DispatchResult {
+      // Only a regular user can execute mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      Self::do_craft(&who, &organization_id, recipe_id, asset_ids)?;
+      Ok(())
+    }
+
+    /// Create a crafting recipe of the organization
+    ///
+    /// The recipe can consume and produce assets of the organization only.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      1 + 2 * recipe.items_count(),
+      2 + recipe.items_count()
+    ))]
+    pub fn create_recipe(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      recipe: Recipe,
+    ) -> DispatchResult {
+      // Only organization can manage recipes
+      T::CreateOrigin::ensure_origin(origin, &organization_id)?;
+      Self::do_create_recipe(&organization_id, recipe)?;
+      Ok(())
+    }
+
+    /// Remove a crafting recipe of the organization
+    #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+    pub fn remove_recipe(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      recipe_id: RecipeId,
+    ) -> DispatchResult {
+      // Only organization can manage recipes
+      T::CreateOrigin::ensure_origin(origin, &organization_id)?;
+      Self::do_remove_recipe(&organization_id, recipe_id)?;
+      Ok(())
+    }
+
+    /// Create a bet table of the organization
+    #[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+    pub fn create_bet_table(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      table: BetTable,
+    ) -> DispatchResult {
+      // Only organization can manage bet tables
+      T::CreateOrigin::ensure_origin(origin, &organization_id)?;
+      Self::do_create_bet_table(&organization_id, table)?;
+      Ok(())
+    }
+
+    /// Remove a bet table of the organization
+    ///
+    /// The table can't be removed while bets on it are pending.
+    #[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+    pub fn remove_bet_table(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      table_id: BetTableId,
+    ) -> DispatchResult {
+      // Only organization can manage bet tables
+      T::CreateOrigin::ensure_origin(origin, &organization_id)?;
+      Self::do_remove_bet_table(&organization_id, table_id)?;
       Ok(())
     }
 
@@ -197,5 +832,29 @@ pub mod pallet {
       Self::do_upgrade(&who, &organization_id, upgrage_data)?;
       Ok(())
     }
+
+    /// Cancel the active mechanic
+    ///
+    /// The locked assets are forfeited by the policy of the mechanic: released, burned or
+    /// released less the penalty.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      3 + DefaultListLengthLimit::get() as u64,
+      2 + DefaultListLengthLimit::get() as u64
+    ))]
+    pub fn cancel(
+      origin: OriginFor<T>,
+      organization_id: AccountIdOf<T>,
+      mechanic_id: MechanicIdOf<T>,
+    ) -> DispatchResult {
+      // Only a regular user can cancel mechanic
+      let who = T::ExecuteOrigin::ensure_origin(origin)?;
+      let policy = Self::do_cancel(&who, &organization_id, &mechanic_id)?;
+      Self::deposit_event(Event::Stopped {
+        id: mechanic_id.nonce,
+        owner: mechanic_id.gamer_account,
+        reason: EventMechanicStopReason::Cancelled(policy),
+      });
+      Ok(())
+    }
   }
 }
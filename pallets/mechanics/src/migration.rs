@@ -0,0 +1,83 @@
+//! Storage migrations for the Mechanics pallet.
+use super::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use pallet_support::{DefaultListLengthLimit, GamerAccount, LockedAccet};
+use sp_std::marker::PhantomData;
+
+/// Migration of the active mechanics to the layout with the bet table of the Bet mechanic added
+/// after the version 0.
+pub mod v1 {
+  use super::*;
+
+  /// Data of the Bet mechanic stored by the version 0
+  #[derive(Encode, Decode)]
+  pub struct OldMechanicDataBet {
+    pub outcomes: MechanicDataBetOutcomes,
+  }
+
+  /// Mechanic data stored by the version 0
+  #[derive(Encode, Decode)]
+  pub enum OldMechanicData {
+    BuyNfa,
+    Bet(OldMechanicDataBet),
+  }
+
+  /// Mechanic details stored by the version 0
+  #[derive(Encode, Decode)]
+  pub struct OldMechanicDetails<AccountId, BlockNumber> {
+    pub owner: GamerAccount<AccountId>,
+    pub timeout_id: BlockNumber,
+    pub locked: BoundedVec<LockedAccet, DefaultListLengthLimit>,
+    pub data: OldMechanicData,
+  }
+
+  impl OldMechanicData {
+    /// Bets of the version 0 are made by NFAs with the Bettor characteristic
+    fn migrate<AccountId>(self) -> MechanicData<AccountId> {
+      match self {
+        OldMechanicData::BuyNfa => MechanicData::BuyNfa,
+        OldMechanicData::Bet(bet) => MechanicData::Bet(MechanicDataBet {
+          outcomes: bet.outcomes,
+          table_id: None,
+        }),
+      }
+    }
+  }
+
+  impl<AccountId, BlockNumber> OldMechanicDetails<AccountId, BlockNumber> {
+    fn migrate(self) -> MechanicDetails<AccountId, BlockNumber> {
+      MechanicDetails {
+        owner: self.owner,
+        timeout_id: self.timeout_id,
+        locked: self.locked,
+        data: self.data.migrate(),
+      }
+    }
+  }
+
+  pub struct MigrateToV1<T>(PhantomData<T>);
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let onchain_version = Pallet::<T>::on_chain_storage_version();
+      if onchain_version >= 1 {
+        log::info!(
+          "🧹 Mechanics migration to v1 skipped [on-chain version: {:?}]",
+          onchain_version
+        );
+        return T::DbWeight::get().reads(1);
+      }
+
+      let mut count = 0u64;
+      Mechanics::<T>::translate::<OldMechanicDetails<T::AccountId, T::BlockNumber>, _>(
+        |_, _, old| {
+          count += 1;
+          Some(old.migrate())
+        },
+      );
+      StorageVersion::new(1).put::<Pallet<T>>();
+      log::info!("🧹 Mechanics migrated to v1 [mechanics: {}]", count);
+
+      T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+  }
+}
@@ -1,16 +1,26 @@
 use crate as pallet_mechanics;
 use codec::Encode;
-use frame_support::traits::{ConstU16, ConstU32, ConstU64, Hooks};
+use frame_support::{
+  parameter_types,
+  traits::{AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64, Hooks},
+};
 use frame_system as system;
 use pallet_support::{
   bettor::{Bettor, BettorOutcome, BettorWinning, DrawOutcomeResult, OutcomeResult},
+  lootbox::{Lootbox, LootboxDrop, LootboxPity, LootboxReward},
+  purchased::{Offer, PaymentRouting},
+  royalty::{Royalty, RoyaltyBeneficiary},
+  salvage::{Salvage, SalvageRefund},
+  transferability::Transferability,
   types_nfa::{AssetDetails, ClassDetails},
+  upgradable::{AttributeDelta, Upgradable, UpgradeCost, UpgradeLevel},
   *,
 };
 use sp_core::H256;
 use sp_runtime::{
   testing::Header,
   traits::{BlakeTwo256, IdentityLookup},
+  Percent,
 };
 // use frame_support_test::TestRandomness;
 
@@ -83,7 +93,54 @@ impl system::Config for Test {
 
 /// Mock of fungible-assets-pallet impl
 pub struct FAPallet {}
+thread_local! {
+  /// Reserved balances of accounts counted by the FAPallet mock
+  static FA_RESERVED: std::cell::RefCell<std::collections::BTreeMap<u64, FungibleAssetBalance>> =
+    Default::default();
+  /// Balances of accounts burned by the FAPallet mock
+  static FA_BURNED: std::cell::RefCell<std::collections::BTreeMap<u64, FungibleAssetBalance>> =
+    Default::default();
+  /// References to fungible assets counted by the FAPallet mock
+  static FA_REFERENCES: std::cell::RefCell<std::collections::BTreeMap<FungibleAssetId, u32>> =
+    Default::default();
+  /// Number of assets minted by the NFAPallet mock, per class
+  static NFA_MINTED: std::cell::RefCell<std::collections::BTreeMap<u32, u32>> =
+    Default::default();
+}
+
+/// Returns the balance of the account reserved by the FAPallet mock
+pub fn fa_reserved(who: u64) -> FungibleAssetBalance {
+  FA_RESERVED.with(|r| r.borrow().get(&who).copied().unwrap_or_default())
+}
+
+/// Returns the balance of the account burned by the FAPallet mock
+pub fn fa_burned(who: u64) -> FungibleAssetBalance {
+  FA_BURNED.with(|b| b.borrow().get(&who).copied().unwrap_or_default())
+}
+
+/// Returns the number of references to the fungible asset counted by the FAPallet mock
+pub fn fa_references(asset: u32) -> u32 {
+  FA_REFERENCES.with(|r| r.borrow().get(&asset.into()).copied().unwrap_or_default())
+}
+
+/// Returns the number of assets of the class minted by the NFAPallet mock
+pub fn nfa_minted(class_id: u32) -> u32 {
+  NFA_MINTED.with(|m| m.borrow().get(&class_id).copied().unwrap_or_default())
+}
+
 impl pallet_support::traits::FungibleAssets<u64> for FAPallet {
+  fn owner_of(asset: &FungibleAssetId) -> Option<u64> {
+    // test do_list_nfa_*
+    // FA 5 is owned by the organization 2, FA 6 by the organization 3
+    if asset == &5.into() {
+      return Some(2);
+    }
+    if asset == &6.into() {
+      return Some(3);
+    }
+    todo!()
+  }
+
   fn can_withdraw(
     asset: FungibleAssetId,
     _who: &u64,
@@ -100,28 +157,91 @@ impl pallet_support::traits::FungibleAssets<u64> for FAPallet {
 
   fn burn_from(
     asset_id: FungibleAssetId,
-    _who: &u64,
-    _amount: FungibleAssetBalance,
+    who: &u64,
+    amount: FungibleAssetBalance,
   ) -> pallet_support::DispatchResultAs<FungibleAssetBalance> {
     if asset_id == 5u32.into() {
+      // test cancel_bet_fa_applies_penalty
+      FA_BURNED.with(|b| {
+        let mut burned = b.borrow_mut();
+        let balance = burned.entry(*who).or_default();
+        *balance = *balance + amount;
+      });
       return Ok(10000.into());
     }
     todo!()
   }
 
-  fn inc_references(_asset: &FungibleAssetId) -> sp_runtime::DispatchResult {
+  fn transfer(
+    asset_id: FungibleAssetId,
+    _source: &u64,
+    _dest: &u64,
+    amount: FungibleAssetBalance,
+  ) -> pallet_support::DispatchResultAs<FungibleAssetBalance> {
+    if asset_id == 5u32.into() {
+      // test do_buy_nfa_payment_routing, pay_with_royalty_works, do_buy_listing_works
+      return Ok(amount);
+    }
+    todo!()
+  }
+
+  fn reserve(
+    _asset: FungibleAssetId,
+    who: &u64,
+    amount: FungibleAssetBalance,
+  ) -> sp_runtime::DispatchResult {
+    // test do_bid_*
+    if amount > 9999.into() {
+      return Err(sp_runtime::TokenError::NoFunds.into());
+    }
+    FA_RESERVED.with(|r| {
+      let mut reserved = r.borrow_mut();
+      let balance = reserved.entry(*who).or_default();
+      *balance = *balance + amount;
+    });
+    Ok(())
+  }
+
+  fn unreserve(
+    _asset: FungibleAssetId,
+    who: &u64,
+    amount: FungibleAssetBalance,
+  ) -> pallet_support::DispatchResultAs<FungibleAssetBalance> {
+    // test do_bid_*, auction_settled_by_timeout
+    FA_RESERVED.with(|r| {
+      let mut reserved = r.borrow_mut();
+      let balance = reserved.entry(*who).or_default();
+      let actual = (*balance).min(amount);
+      *balance = *balance - actual;
+      Ok(actual)
+    })
+  }
+
+  fn inc_references(asset: &FungibleAssetId) -> sp_runtime::DispatchResult {
+    // test remove_recipe_works, create_bet_table_works
+    FA_REFERENCES.with(|r| *r.borrow_mut().entry(*asset).or_default() += 1);
     Ok(())
   }
 
-  fn dec_references(_asset: &FungibleAssetId) -> sp_runtime::DispatchResult {
+  fn dec_references(asset: &FungibleAssetId) -> sp_runtime::DispatchResult {
+    // test remove_recipe_works, create_bet_table_works
+    FA_REFERENCES.with(|r| {
+      let mut references = r.borrow_mut();
+      let count = references.entry(*asset).or_default();
+      *count = count.saturating_sub(1);
+    });
     Ok(())
   }
 
   fn mint_into(
-    _asset: FungibleAssetId,
+    asset: FungibleAssetId,
     _who: &u64,
     _amount: FungibleAssetBalance,
   ) -> sp_runtime::DispatchResult {
+    if asset == 5.into() {
+      // test do_craft_works
+      return Ok(());
+    }
     todo!()
   }
 }
@@ -154,20 +274,29 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       return Ok(11u32.into());
     }
     if class_id == &20u32.into() {
-      // test do_bet_asset_one_round_work
+      // test do_bet_asset_one_round_work, do_bet_several_assets_works
+      NFA_MINTED.with(|m| *m.borrow_mut().entry(20).or_default() += 1);
       return Ok(11u32.into());
     }
+    if class_id == &51u32.into() {
+      // test do_craft_works
+      return Ok(52u32.into());
+    }
+    if class_id == &61u32.into() {
+      // test do_open_lootbox_works
+      return Ok(62u32.into());
+    }
+    if class_id == &81u32.into() {
+      // test do_burn_nfa_works
+      return Ok(83u32.into());
+    }
     todo!()
   }
 
   fn get_offer(
     class_id: &pallet_support::NonFungibleClassId,
     offer_id: &u32,
-  ) -> pallet_support::DispatchResultAs<(
-    pallet_support::FungibleAssetId,
-    pallet_support::FungibleAssetBalance,
-    pallet_support::AttributeList,
-  )> {
+  ) -> pallet_support::DispatchResultAs<Offer> {
     let a1 = Attribute {
       key: br"a1".to_vec().try_into().unwrap(),
       value: AttributeValue::Number(NumberAttribute {
@@ -180,22 +309,107 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       value: AttributeValue::Text(br"v1".to_vec().try_into().unwrap()),
     };
     let attributes: AttributeList = vec![a1, a2].try_into().unwrap();
+    let offer = Offer {
+      price: vec![(5.into(), 100.into())].try_into().unwrap(),
+      trade_ins: Default::default(),
+      attributes,
+      stock: None,
+      start_block: None,
+      end_block: None,
+      max_per_gamer: None,
+      payment: Default::default(),
+    };
     if class_id == &1u32.into() && offer_id == &2u32 {
-      return Ok((333.into(), 500.into(), attributes));
+      return Ok(Offer {
+        price: vec![(333.into(), 500.into())].try_into().unwrap(),
+        ..offer
+      });
     }
     if class_id == &1u32.into() && offer_id == &3u32 {
-      return Ok((1.into(), 10000.into(), attributes));
+      return Ok(Offer {
+        price: vec![(1.into(), 10000.into())].try_into().unwrap(),
+        ..offer
+      });
     }
-    Ok((5.into(), 100.into(), attributes))
+    if class_id == &1u32.into() && offer_id == &4u32 {
+      // test do_buy_nfa_limited_offer
+      return Ok(Offer {
+        stock: Some(2),
+        max_per_gamer: Some(1),
+        ..offer
+      });
+    }
+    if class_id == &1u32.into() && offer_id == &5u32 {
+      // test do_buy_nfa_sale_window
+      return Ok(Offer {
+        start_block: Some(3),
+        end_block: Some(4),
+        ..offer
+      });
+    }
+    if class_id == &1u32.into() && offer_id == &6u32 {
+      // test do_buy_nfa_bundle_price
+      return Ok(Offer {
+        price: vec![(5.into(), 100.into()), (1.into(), 10000.into())]
+          .try_into()
+          .unwrap(),
+        ..offer
+      });
+    }
+    if class_id == &1u32.into() && offer_id == &7u32 {
+      // test do_buy_nfa_trade_in
+      return Ok(Offer {
+        trade_ins: vec![70.into(), 70.into()].try_into().unwrap(),
+        ..offer
+      });
+    }
+    if class_id == &1u32.into() && offer_id == &8u32 {
+      // test do_buy_nfa_payment_routing
+      return Ok(Offer {
+        payment: PaymentRouting::Treasury,
+        ..offer
+      });
+    }
+    if class_id == &1u32.into() && offer_id == &9u32 {
+      // test do_buy_nfa_payment_routing
+      return Ok(Offer {
+        payment: PaymentRouting::Split(Percent::from_percent(30)),
+        ..offer
+      });
+    }
+    Ok(offer)
   }
 
   fn set_attributes(
+    _class_id: &pallet_support::NonFungibleClassId,
     asset_id: &pallet_support::NonFungibleAssetId,
     _attributes: pallet_support::AttributeList,
   ) -> frame_support::dispatch::DispatchResult {
     if asset_id == &10u32.into() {
       return Ok(());
     }
+    if asset_id == &62u32.into() {
+      // test do_open_lootbox_works
+      return Ok(());
+    }
+    if asset_id == &83u32.into() {
+      // test do_burn_nfa_works
+      return Ok(());
+    }
+    if asset_id == &52u32.into() {
+      // test do_craft_works
+      return Ok(());
+    }
+    if asset_id == &40u32.into() || asset_id == &44u32.into() {
+      // test do_upgrade_nfa_*
+      return Ok(());
+    }
+    todo!()
+  }
+  fn validate_attributes(
+    _class_id: &pallet_support::NonFungibleClassId,
+    _attributes: &pallet_support::AttributeList,
+  ) -> frame_support::dispatch::DispatchResult {
     todo!()
   }
   fn try_lock(
@@ -204,6 +418,30 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
     class_id: &NonFungibleClassId,
     asset_id: &NonFungibleAssetId,
   ) -> DispatchResultAs<LockResult<u64, u32>> {
+    if class_id == &97u32.into() && (asset_id == &97u32.into() || asset_id == &98u32.into()) {
+      // test do_propose_trade_works, do_accept_trade_works, do_cancel_trade_works
+      let lr: LockResultOf<Test> = LockResult::Locked(AssetDetails {
+        locked: origin,
+        owner: *who,
+      });
+      return Ok(lr);
+    }
+    if class_id == &97u32.into() && asset_id == &99u32.into() {
+      // test do_propose_trade_errors, the asset of the account 4 locked by its approved delegate
+      let lr: LockResultOf<Test> = LockResult::Locked(AssetDetails {
+        locked: origin,
+        owner: 4,
+      });
+      return Ok(lr);
+    }
+    if class_id == &95u32.into() && (asset_id == &95u32.into() || asset_id == &99u32.into()) {
+      // test do_list_nfa_*, do_buy_listing_*, do_cancel_listing_works, auction_settlement_reverted
+      let lr: LockResultOf<Test> = LockResult::Locked(AssetDetails {
+        locked: origin,
+        owner: *who,
+      });
+      return Ok(lr);
+    }
     if class_id == &2u32.into() && asset_id == &3u32.into() {
       // test try_lock_nfa_works
       let lr: LockResultOf<Test> = LockResult::Locked(AssetDetails {
@@ -250,8 +488,8 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       });
       return Ok(lr);
     }
-    if class_id == &34u32.into() && asset_id == &34u32.into() {
-      // test do_bet_asset_one_round_work
+    if class_id == &34u32.into() && (asset_id == &34u32.into() || asset_id == &37u32.into()) {
+      // test do_bet_asset_one_round_work, do_bet_several_assets_works
       let lr: LockResultOf<Test> = LockResult::Locked(AssetDetails {
         locked: origin,
         owner: *who,
@@ -274,6 +512,22 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       });
       return Ok(lr);
     }
+    if class_id == &50u32.into() && (asset_id == &50u32.into() || asset_id == &51u32.into()) {
+      // test do_craft_*
+      let lr: LockResultOf<Test> = LockResult::Locked(AssetDetails {
+        locked: origin,
+        owner: *who,
+      });
+      return Ok(lr);
+    }
+    if class_id == &50u32.into() && asset_id == &53u32.into() {
+      // test do_craft_errors, the asset of the account 3 locked by its approved delegate
+      let lr: LockResultOf<Test> = LockResult::Locked(AssetDetails {
+        locked: origin,
+        owner: 3,
+      });
+      return Ok(lr);
+    }
     todo!()
   }
   fn get_class(class_id: &NonFungibleClassId) -> DispatchResultAs<ClassDetails<u64>> {
@@ -284,6 +538,11 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
         name: br"a".to_vec().try_into().unwrap(),
         bettor: None,
         purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
         instances: 0,
         owner: 1,
       });
@@ -295,6 +554,11 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
         name: br"a".to_vec().try_into().unwrap(),
         bettor: None,
         purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
         instances: 0,
         owner: 1,
       });
@@ -324,6 +588,11 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
           draw_outcome: DrawOutcomeResult::Keep,
         }),
         purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
         instances: 0,
         owner: 1,
       });
@@ -353,6 +622,11 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
           draw_outcome: DrawOutcomeResult::Lose,
         }),
         purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
         instances: 0,
         owner: 1,
       });
@@ -382,6 +656,206 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
           draw_outcome: DrawOutcomeResult::Lose,
         }),
         purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
+        instances: 0,
+        owner: 1,
+      });
+    }
+    if class_id == &40.into() {
+      // test do_upgrade_nfa_*
+      return Ok(ClassDetails {
+        attributes: 0,
+        name: br"a".to_vec().try_into().unwrap(),
+        bettor: None,
+        purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: Some(Upgradable {
+          level_key: bvec!(br"level"),
+          levels: bvec![
+            UpgradeLevel {
+              cost: UpgradeCost::Fa(5.into(), 100.into()),
+              deltas: bvec![AttributeDelta {
+                key: bvec!(br"power"),
+                delta: 3,
+              }],
+            },
+            UpgradeLevel {
+              cost: UpgradeCost::Nfa(41.into()),
+              deltas: bvec![AttributeDelta {
+                key: bvec!(br"power"),
+                delta: 3,
+              }],
+            },
+          ],
+        }),
+        lootbox: None,
+        salvage: None,
+        royalty: None,
+        instances: 0,
+        owner: 1,
+      });
+    }
+    if class_id == &70.into() {
+      // test do_buy_nfa_trade_in
+      // asset 72 is locked, asset 73 is owned by another account
+      let locked = if asset_id == &72.into() {
+        Locker::Nfa(40.into(), 40.into())
+      } else {
+        Locker::None
+      };
+      let owner = if asset_id == &73.into() { 2 } else { 1 };
+      return Ok(AssetDetails { owner, locked });
+    }
+    if class_id == &60.into() {
+      // test do_open_lootbox_*
+      // with odd block numbers the rare NFA is dropped, with even - FA
+      return Ok(ClassDetails {
+        attributes: 0,
+        name: br"a".to_vec().try_into().unwrap(),
+        bettor: None,
+        purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: Some(Lootbox {
+          drops: bvec![
+            LootboxDrop {
+              weight: 1,
+              rare: false,
+              reward: LootboxReward::Fa(5.into(), 10.into()),
+            },
+            LootboxDrop {
+              weight: 1,
+              rare: true,
+              reward: LootboxReward::Nfa(61.into(), Default::default()),
+            },
+          ],
+          rolls: 1,
+          pity: Some(LootboxPity { threshold: 2 }),
+        }),
+        salvage: None,
+        royalty: None,
+        instances: 0,
+        owner: 1,
+      });
+    }
+    if class_id == &80.into() || class_id == &82.into() {
+      // test do_burn_nfa_*
+      // class 80 refunds FA 5 and NFA of class 81 when burned, class 82 has no refunds
+      let salvage = (class_id == &80.into()).then(|| Salvage {
+        refunds: bvec![
+          SalvageRefund::Fa(5.into(), 10.into()),
+          SalvageRefund::Nfa(81.into(), Default::default()),
+        ],
+      });
+      return Ok(ClassDetails {
+        attributes: 0,
+        name: br"a".to_vec().try_into().unwrap(),
+        bettor: None,
+        purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage,
+        royalty: None,
+        instances: 0,
+        owner: 1,
+      });
+    }
+    if class_id == &95.into() {
+      // test do_buy_listing_works
+      return Ok(ClassDetails {
+        attributes: 0,
+        name: br"a".to_vec().try_into().unwrap(),
+        bettor: None,
+        purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
+        instances: 0,
+        owner: 2,
+      });
+    }
+    if class_id == &90.into() || class_id == &91.into() {
+      // test pay_with_royalty_works
+      // class 90 pays 10% to the treasury, class 91 pays 5% to the account 7
+      let royalty = if class_id == &90.into() {
+        Royalty {
+          percent: Percent::from_percent(10),
+          beneficiary: RoyaltyBeneficiary::Treasury,
+        }
+      } else {
+        Royalty {
+          percent: Percent::from_percent(5),
+          beneficiary: RoyaltyBeneficiary::Account(7),
+        }
+      };
+      return Ok(ClassDetails {
+        attributes: 0,
+        name: br"a".to_vec().try_into().unwrap(),
+        bettor: None,
+        purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: Some(royalty),
+        instances: 0,
+        owner: 1,
+      });
+    }
+    if class_id == &50.into() || class_id == &51.into() {
+      // test create_recipe_works, remove_recipe_works, do_craft_*
+      return Ok(ClassDetails {
+        attributes: 0,
+        name: br"a".to_vec().try_into().unwrap(),
+        bettor: None,
+        purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
+        instances: 0,
+        owner: 2,
+      });
+    }
+    if class_id == &97.into() {
+      // test do_accept_trade_*
+      return Ok(ClassDetails {
+        attributes: 0,
+        name: br"a".to_vec().try_into().unwrap(),
+        bettor: None,
+        purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: Some(Royalty {
+          percent: Percent::from_percent(10),
+          beneficiary: RoyaltyBeneficiary::Account(7),
+        }),
+        instances: 0,
+        owner: 2,
+      });
+    }
+    if class_id == &1.into() {
+      // test do_buy_nfa_payment_routing
+      return Ok(ClassDetails {
+        attributes: 0,
+        name: br"a".to_vec().try_into().unwrap(),
+        bettor: None,
+        purchased: None,
+        transferability: Transferability::Transferable,
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
         instances: 0,
         owner: 1,
       });
@@ -409,6 +883,10 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       // test do_bet_result_processing_draw_lose
       return Ok(());
     }
+    if class_id == 70u32.into() {
+      // test do_buy_nfa_trade_in
+      return Ok(());
+    }
     if class_id == 8u32.into() && asset_id == 8u32.into() {
       // test play_bet_round_single_round_win
       return Ok(());
@@ -429,8 +907,8 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       // test play_bet_round_single_round_draw_win
       return Ok(());
     }
-    if class_id == 34u32.into() && asset_id == 34u32.into() {
-      // test do_bet_asset_one_round_work
+    if class_id == 34u32.into() && (asset_id == 34u32.into() || asset_id == 37u32.into()) {
+      // test do_bet_asset_one_round_work, do_bet_several_assets_works
       return Ok(());
     }
     if class_id == 35u32.into() && asset_id == 35u32.into() {
@@ -441,6 +919,22 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       // test do_do_upgrade_bet_two_rounds_work
       return Ok(());
     }
+    if class_id == 41u32.into() && asset_id == 1u32.into() {
+      // test do_upgrade_nfa_nfa_cost
+      return Ok(());
+    }
+    if class_id == 50u32.into() && asset_id == 50u32.into() {
+      // test do_craft_works
+      return Ok(());
+    }
+    if class_id == 60u32.into() && asset_id == 60u32.into() {
+      // test do_open_lootbox_works
+      return Ok(());
+    }
+    if (class_id == 80u32.into() || class_id == 82u32.into()) && asset_id == 80u32.into() {
+      // test do_burn_nfa_works
+      return Ok(());
+    }
     todo!()
   }
   fn clear_lock(
@@ -460,8 +954,8 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       // test do_bet_asset_not_bettor
       return Ok(());
     }
-    if class_id == &34.into() && asset_id == &34.into() {
-      // test do_bet_asset_one_round_work
+    if class_id == &34.into() && (asset_id == &34.into() || asset_id == &37.into()) {
+      // test do_bet_asset_one_round_work, do_bet_several_assets_works
       return Ok(());
     }
     if class_id == &35.into() && asset_id == &35.into() {
@@ -472,18 +966,168 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
       // test do_do_upgrade_bet_two_rounds_work
       return Ok(());
     }
+    if class_id == &50.into() && (asset_id == &51.into() || asset_id == &53.into()) {
+      // test do_craft_requirements_not_met, do_craft_errors
+      return Ok(());
+    }
+    if class_id == &95.into() && (asset_id == &95.into() || asset_id == &99.into()) {
+      // test do_buy_listing_works, do_cancel_listing_works, listing_released_by_timeout,
+      // auction_settlement_reverted
+      return Ok(());
+    }
+    if class_id == &97.into()
+      && (asset_id == &97.into() || asset_id == &98.into() || asset_id == &99.into())
+    {
+      // test do_accept_trade_works, do_cancel_trade_works, trade_released_by_timeout,
+      // do_propose_trade_errors
+      return Ok(());
+    }
     todo!()
   }
+  fn transfer(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    _maybe_check_owner: Option<&u64>,
+    _dest: &u64,
+  ) -> sp_runtime::DispatchResult {
+    if class_id == &95.into() && asset_id == &95.into() {
+      // test do_buy_listing_works
+      return Ok(());
+    }
+    if class_id == &95.into() && asset_id == &99.into() {
+      // test auction_settlement_reverted, the asset was burned during the auction
+      return Err(sp_runtime::DispatchError::Other(
+        "mock_error_asset_doesnt_exist",
+      ));
+    }
+    if class_id == &97.into() {
+      // test do_accept_trade_works
+      return Ok(());
+    }
+    todo!()
+  }
+  fn ensure_transferable(class_id: &NonFungibleClassId) -> sp_runtime::DispatchResult {
+    // test do_list_nfa_*, do_propose_trade_*
+    // class 96 is soulbound
+    if class_id == &95.into() || class_id == &97.into() {
+      return Ok(());
+    }
+    if class_id == &96.into() {
+      return Err(sp_runtime::DispatchError::Other("mock_soulbound"));
+    }
+    todo!()
+  }
+  fn get_asset(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+  ) -> DispatchResultAs<AssetDetails<u64, u32>> {
+    // test do_upgrade_nfa_*
+    // asset 41 is locked, asset 42 is owned by another account
+    if class_id == &40.into() || class_id == &41.into() {
+      let locked = if asset_id == &41.into() {
+        Locker::Mechanic(MechanicId {
+          gamer_account: GamerAccount {
+            account_id: 1,
+            organization_id: 2,
+          },
+          nonce: 1,
+        })
+      } else {
+        Locker::None
+      };
+      let owner = if asset_id == &42.into() { 2 } else { 1 };
+      return Ok(AssetDetails { owner, locked });
+    }
+    if class_id == &60.into() {
+      // test do_open_lootbox_*
+      // asset 63 is owned by another account
+      let owner = if asset_id == &63.into() { 2 } else { 1 };
+      return Ok(AssetDetails {
+        owner,
+        locked: Locker::None,
+      });
+    }
+    if class_id == &95.into() {
+      // test do_list_nfa_*
+      // asset 96 is owned by another account
+      let owner = if asset_id == &96.into() { 2 } else { 1 };
+      return Ok(AssetDetails {
+        owner,
+        locked: Locker::None,
+      });
+    }
+    if class_id == &80.into() || class_id == &82.into() {
+      // test do_burn_nfa_*
+      // asset 81 is locked, asset 82 is owned by another account
+      let locked = if asset_id == &81.into() {
+        Locker::Nfa(40.into(), 40.into())
+      } else {
+        Locker::None
+      };
+      let owner = if asset_id == &82.into() { 2 } else { 1 };
+      return Ok(AssetDetails { owner, locked });
+    }
+    todo!()
+  }
+  fn get_attribute(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    key: &AttributeKey,
+  ) -> Option<AttributeValue> {
+    // test do_upgrade_nfa_*
+    // asset 43 has the max level, asset 44 has the level 1
+    if class_id == &40.into() {
+      if key.as_slice() == br"power" {
+        return Some(AttributeValue::Number(NumberAttribute {
+          number_value: 7,
+          number_max: Some(12),
+        }));
+      }
+      if key.as_slice() == br"level" && asset_id == &43.into() {
+        return Some((2u32, 2u32).try_into().unwrap());
+      }
+      if key.as_slice() == br"level" && asset_id == &44.into() {
+        return Some((1u32, 2u32).try_into().unwrap());
+      }
+      return None;
+    }
+    if class_id == &50.into() && key.as_slice() == br"power" {
+      // test do_craft_*
+      // asset 50 has the power 7, others - 3
+      let power: u32 = if asset_id == &50.into() { 7 } else { 3 };
+      return Some(power.try_into().unwrap());
+    }
+    todo!()
+  }
+}
+
+/// Mock of organization-identity-pallet impl
+pub struct OrganizationPallet {}
+impl pallet_support::traits::OrganizationTreasury<u64> for OrganizationPallet {
+  fn treasury_of(organization_id: &u64) -> u64 {
+    if organization_id == &1 {
+      // test do_buy_nfa_payment_routing
+      return 100;
+    }
+    *organization_id
+  }
+}
+
+parameter_types! {
+  pub const CancelPenalty: Percent = Percent::from_percent(10);
 }
 
 impl pallet_mechanics::Config for Test {
   type Event = Event;
   type FungibleAssets = FAPallet;
   type NonFungibleAssets = NFAPallet;
+  type OrganizationTreasury = OrganizationPallet;
   type Randomness = TestRandomness<Self>;
   type AssetsListLimit = ConstU32<16>;
   type MechanicsLifeTime = ConstU64<20>;
+  type CancelPenalty = CancelPenalty;
   type ExecuteOrigin = frame_system::EnsureSigned<u64>;
+  type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,15 +1,20 @@
 use crate::{
-  mock::*, AssetAction, BetResult, Error, Event as MechanicsEvent, EventMechanicResultData,
-  EventMechanicResultDataBet, EventMechanicStopReason, Mechanic, MechanicData,
+  mock::*, AssetAction, AttributeRequirement, AuctionBids, BetResult, BetTable, BetTables, Error,
+  Event as MechanicsEvent, EventMechanicResultData, EventMechanicResultDataBet,
+  EventMechanicStopReason, ForfeitPolicy, LootboxDropResult, LootboxPityCounters, Mechanic,
+  MechanicData, MechanicDataAuction, MechanicDataBet, MechanicDataListing, MechanicDataTrade,
   MechanicDetailsBuilder, MechanicId, MechanicUpgradeData, MechanicUpgradeDataOf,
-  MechanicUpgradePayload, Mechanics, Timeouts,
+  MechanicUpgradePayload, Mechanics, NonFungibleClassId, OfferPurchases, OfferSales, PendingBets,
+  Recipe, RecipeNfaInput, RecipeOutput, Recipes, Timeouts, TradeAssets,
 };
 use frame_support::{assert_noop, assert_ok, BoundedVec};
 use frame_system::{EventRecord, Phase};
+use sp_runtime::Percent;
 
 use pallet_support::{
   bettor::{Bettor, BettorOutcome, BettorWinning, DrawOutcomeResult, OutcomeResult},
   purchased::{Offer, Purchased},
+  transferability::Transferability,
   types_nfa::ClassDetails,
   AssetCharacteristic, ClassDetailsOf, DefaultListLengthLimit, GamerAccount, LockedAccet,
   MechanicIdOf,
@@ -98,7 +103,7 @@ fn do_buy_nfa_unknown_asset() {
     // get_offer
     // class_id == 1 && offer_id == 2 - FA=333, price=500
     assert_noop!(
-      MechanicsModule::do_buy_nfa(&1, &1.into(), &2),
+      MechanicsModule::do_buy_nfa(&1, &1.into(), &2, bvec![]),
       sp_runtime::TokenError::UnknownAsset
     );
   });
@@ -115,7 +120,7 @@ fn do_buy_nfa_no_funds() {
     // class_id == 1 && offer_id == 3 - FA=1, price=10000
     // else - FA=5, price=100
     assert_noop!(
-      MechanicsModule::do_buy_nfa(&1, &1.into(), &3),
+      MechanicsModule::do_buy_nfa(&1, &1.into(), &3, bvec![]),
       sp_runtime::TokenError::NoFunds
     );
   });
@@ -131,7 +136,138 @@ fn do_buy_nfa_worked() {
     // class_id == 1 && offer_id == 2 - FA=333, price=500
     // class_id == 1 && offer_id == 3 - FA=1, price=10000
     // else - FA=5, price=100
-    assert_ok!(MechanicsModule::do_buy_nfa(&1, &1.into(), &1));
+    assert_ok!(MechanicsModule::do_buy_nfa(&1, &1.into(), &1, bvec![]));
+  });
+}
+
+#[test]
+fn do_buy_nfa_bundle_price() {
+  new_test_ext().execute_with(|| {
+    // get_offer
+    // class_id == 1 && offer_id == 6 - FA=5, price=100 and FA=1, price=10000
+    // all prices are checked before the mint
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&1, &1.into(), &6, bvec![]),
+      sp_runtime::TokenError::NoFunds
+    );
+  });
+}
+
+#[test]
+fn do_buy_nfa_trade_in() {
+  new_test_ext().execute_with(|| {
+    // get_offer
+    // class_id == 1 && offer_id == 7 - FA=5, price=100, two assets of class 70 traded in
+    // get_asset
+    // class_id == 70 - asset 72 is locked, asset 73 is owned by another account
+    let class_id: NonFungibleClassId = 1.into();
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&1, &class_id, &7, bvec![70.into()]),
+      Error::<Test>::IncompatibleData
+    );
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&1, &class_id, &7, bvec![70.into(), 70.into()]),
+      Error::<Test>::IncompatibleData
+    );
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&1, &class_id, &7, bvec![70.into(), 72.into()]),
+      Error::<Test>::AssetLocked
+    );
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&1, &class_id, &7, bvec![70.into(), 73.into()]),
+      Error::<Test>::NoPermission
+    );
+    assert_ok!(MechanicsModule::do_buy_nfa(
+      &1,
+      &class_id,
+      &7,
+      bvec![70.into(), 71.into()]
+    ));
+  });
+}
+
+#[test]
+fn do_buy_nfa_payment_routing() {
+  new_test_ext().execute_with(|| {
+    // get_offer
+    // class_id == 1 && offer_id == 1 - FA=5, price=100, burned
+    // class_id == 1 && offer_id == 8 - FA=5, price=100, moved to the treasury
+    // class_id == 1 && offer_id == 9 - FA=5, price=100, 30% moved to the treasury
+    // get_class
+    // class_id == 1 - owned by organization 1, which treasury is account 100
+    let class_id: NonFungibleClassId = 1.into();
+    let paid = |burned: u128, transferred: u128, treasury: Option<u64>| EventRecord {
+      phase: Phase::Initialization,
+      event: MechanicsEvent::PurchasePaid {
+        who: 2,
+        class_id,
+        fungible_asset_id: 5.into(),
+        burned: burned.into(),
+        transferred: transferred.into(),
+        treasury,
+      }
+      .into(),
+      topics: vec![],
+    };
+
+    assert_ok!(MechanicsModule::do_buy_nfa(&2, &class_id, &1, bvec![]));
+    assert_eq!(System::events(), vec![paid(100, 0, None)]);
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::do_buy_nfa(&2, &class_id, &8, bvec![]));
+    assert_eq!(System::events(), vec![paid(0, 100, Some(100))]);
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::do_buy_nfa(&2, &class_id, &9, bvec![]));
+    assert_eq!(System::events(), vec![paid(70, 30, Some(100))]);
+  });
+}
+
+#[test]
+fn do_buy_nfa_limited_offer() {
+  new_test_ext().execute_with(|| {
+    // get_offer
+    // class_id == 1 && offer_id == 4 - stock 2, max per gamer 1
+    let class_id: NonFungibleClassId = 1.into();
+    // unlimited offers aren't tracked
+    assert_ok!(MechanicsModule::do_buy_nfa(&1, &class_id, &1, bvec![]));
+    assert_eq!(OfferSales::<Test>::get(class_id, 1u32), 0);
+
+    assert_ok!(MechanicsModule::do_buy_nfa(&1, &class_id, &4, bvec![]));
+    assert_eq!(OfferSales::<Test>::get(class_id, 4u32), 1);
+    assert_eq!(OfferPurchases::<Test>::get((class_id, 4u32, 1u64)), 1);
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&1, &class_id, &4, bvec![]),
+      Error::<Test>::OfferLimitReached
+    );
+    assert_ok!(MechanicsModule::do_buy_nfa(&2, &class_id, &4, bvec![]));
+    assert_eq!(OfferSales::<Test>::get(class_id, 4u32), 2);
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&3, &class_id, &4, bvec![]),
+      Error::<Test>::OfferSoldOut
+    );
+  });
+}
+
+#[test]
+fn do_buy_nfa_sale_window() {
+  new_test_ext().execute_with(|| {
+    // get_offer
+    // class_id == 1 && offer_id == 5 - on sale from block 3 to block 4
+    let class_id: NonFungibleClassId = 1.into();
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&1, &class_id, &5, bvec![]),
+      Error::<Test>::OfferNotStarted
+    );
+    System::set_block_number(3);
+    assert_ok!(MechanicsModule::do_buy_nfa(&1, &class_id, &5, bvec![]));
+    System::set_block_number(4);
+    assert_ok!(MechanicsModule::do_buy_nfa(&1, &class_id, &5, bvec![]));
+    System::set_block_number(5);
+    assert_noop!(
+      MechanicsModule::do_buy_nfa(&1, &class_id, &5, bvec![]),
+      Error::<Test>::OfferEnded
+    );
   });
 }
 
@@ -1052,8 +1188,13 @@ fn can_use_mechanic_none() {
     let purchased: Purchased = Purchased {
       offers: bvec![Offer {
         attributes: bvec![],
-        price: 100.into(),
-        fa: 0.into(),
+        price: vec![(0.into(), 100.into())].try_into().unwrap(),
+        trade_ins: bvec![],
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       }],
     };
     assert!(purchased.is_valid()); // purchased must be valid
@@ -1064,6 +1205,11 @@ fn can_use_mechanic_none() {
       name: bvec!(br"ss"),
       bettor: None,
       purchased: None,
+      transferability: Transferability::Transferable,
+      upgradable: None,
+      lootbox: None,
+      salvage: None,
+      royalty: None,
     };
 
     let mechanic = Mechanic::Bet;
@@ -1106,8 +1252,13 @@ fn can_use_mechanic_bet() {
     let purchased: Purchased = Purchased {
       offers: bvec![Offer {
         attributes: bvec![],
-        price: 100.into(),
-        fa: 0.into(),
+        price: vec![(0.into(), 100.into())].try_into().unwrap(),
+        trade_ins: bvec![],
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       }],
     };
     assert!(purchased.is_valid()); // purchased must be valid
@@ -1118,6 +1269,11 @@ fn can_use_mechanic_bet() {
       name: bvec!(br"ss"),
       bettor: Some(bettor),
       purchased: None,
+      transferability: Transferability::Transferable,
+      upgradable: None,
+      lootbox: None,
+      salvage: None,
+      royalty: None,
     };
 
     let mechanic = Mechanic::Bet;
@@ -1156,8 +1312,13 @@ fn can_use_mechanic_purchased() {
     let purchased: Purchased = Purchased {
       offers: bvec![Offer {
         attributes: bvec![],
-        price: 100.into(),
-        fa: 0.into(),
+        price: vec![(0.into(), 100.into())].try_into().unwrap(),
+        trade_ins: bvec![],
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       }],
     };
     assert!(purchased.is_valid()); // purchased must be valid
@@ -1168,6 +1329,11 @@ fn can_use_mechanic_purchased() {
       name: bvec!(br"ss"),
       bettor: None,
       purchased: Some(purchased),
+      transferability: Transferability::Transferable,
+      upgradable: None,
+      lootbox: None,
+      salvage: None,
+      royalty: None,
     };
 
     let mechanic = Mechanic::Bet;
@@ -1831,7 +1997,7 @@ fn do_bet_unexisted_bet_asset() {
     let asset_id = 6.into();
 
     assert_noop!(
-      MechanicsModule::do_bet(&who, &org, &class_id, &asset_id),
+      MechanicsModule::do_bet(&who, &org, &class_id, bvec![asset_id]),
       sp_runtime::DispatchError::Other("mock_error_asset_doesnt_exist")
     );
 
@@ -1855,7 +2021,7 @@ fn do_bet_asset_not_bettor() {
     let asset_id = 7.into();
 
     assert_noop!(
-      MechanicsModule::do_bet(&who, &org, &class_id, &asset_id),
+      MechanicsModule::do_bet(&who, &org, &class_id, bvec![asset_id]),
       Error::<Test>::IncompatibleAsset
     );
 
@@ -1880,7 +2046,12 @@ fn do_bet_asset_one_round_work() {
 
     System::set_block_number(2); // rnd(2) % total_outcomes(2) = 0; 0 = win
     System::reset_events();
-    assert_ok!(MechanicsModule::do_bet(&who, &org, &class_id, &asset_id));
+    assert_ok!(MechanicsModule::do_bet(
+      &who,
+      &org,
+      &class_id,
+      bvec![asset_id]
+    ));
 
     // should mint Nfa(20), drop mechanic, deposit event
 
@@ -1913,6 +2084,61 @@ fn do_bet_asset_one_round_work() {
   });
 }
 
+#[test]
+fn do_bet_several_assets_works() {
+  new_test_ext().execute_with(|| {
+    let who = 116;
+    let org = 227;
+    let _n = System::account_nonce(who);
+    System::inc_account_nonce(who);
+    let inner_id = MechanicsModule::get_mechanic_id(&who, &org);
+
+    let class_id = 34.into();
+
+    System::set_block_number(2); // rnd(2) % total_outcomes(2) = 0; 0 = win
+    System::reset_events();
+    assert_ok!(MechanicsModule::do_bet(
+      &who,
+      &org,
+      &class_id,
+      bvec![34.into(), 37.into()]
+    ));
+
+    // should mint Nfa(20) for each staked asset
+    assert_eq!(nfa_minted(20), 2);
+    assert!(!Mechanics::<Test>::contains_key(
+      &inner_id.gamer_account,
+      &inner_id.nonce
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          id: inner_id.nonce,
+          owner: inner_id.gamer_account,
+          result: Some(EventMechanicResultData::Bet(EventMechanicResultDataBet {
+            outcomes: bvec![0,],
+            result: BetResult::Won,
+          }))
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn do_bet_without_assets_fails() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      MechanicsModule::do_bet(&116, &227, &34.into(), bvec![]),
+      Error::<Test>::IncompatibleData
+    );
+  });
+}
+
 #[test]
 fn do_bet_next_round_two_rounds_work() {
   new_test_ext().execute_with(|| {
@@ -1928,7 +2154,12 @@ fn do_bet_next_round_two_rounds_work() {
     let inner_id = MechanicsModule::get_mechanic_id(&who, &org);
     let timeout_id: <Test as frame_system::Config>::BlockNumber = inner_id.nonce as u64 + 21;
     System::reset_events();
-    assert_ok!(MechanicsModule::do_bet(&who, &org, &class_id, &asset_id));
+    assert_ok!(MechanicsModule::do_bet(
+      &who,
+      &org,
+      &class_id,
+      bvec![asset_id]
+    ));
 
     let m = Mechanics::<Test>::get(&inner_id.gamer_account, &inner_id.nonce).unwrap();
     assert_eq!(
@@ -2004,7 +2235,7 @@ fn do_do_upgrade_bet_two_rounds_work() {
       Origin::signed(who),
       org,
       class_id,
-      asset_id
+      bvec![asset_id]
     ));
 
     let m = Mechanics::<Test>::get(&inner_id.gamer_account, &inner_id.nonce).unwrap();
@@ -2286,3 +2517,1735 @@ fn process_mechanic_timeouts_lifecycle() {
     assert!(!Mechanics::<Test>::contains_key(&mid33, &44));
   });
 }
+
+#[test]
+fn do_upgrade_nfa_works() {
+  new_test_ext().execute_with(|| {
+    // class 40 is upgradable with two levels: for FA 5 and for NFA of the class 41
+    let who = 1;
+    let organization_id = 2;
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+    assert_ok!(MechanicsModule::exec_upgrade_nfa(
+      Origin::signed(who),
+      organization_id,
+      40.into(),
+      40.into(),
+      None
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          id: mechanic_id.nonce,
+          owner: mechanic_id.gamer_account,
+          result: Some(EventMechanicResultData::UpgradeNfa(1)),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn do_upgrade_nfa_nfa_cost() {
+  new_test_ext().execute_with(|| {
+    // the asset 44 has the level 1, the next level costs an NFA of the class 41
+    assert_noop!(
+      MechanicsModule::do_upgrade_nfa(&1, &40.into(), &44.into(), None),
+      Error::<Test>::IncompatibleData
+    );
+    assert_noop!(
+      MechanicsModule::do_upgrade_nfa(&1, &40.into(), &44.into(), Some(41.into())),
+      Error::<Test>::AssetLocked
+    );
+    assert_noop!(
+      MechanicsModule::do_upgrade_nfa(&1, &40.into(), &44.into(), Some(42.into())),
+      Error::<Test>::NoPermission
+    );
+    assert_eq!(
+      MechanicsModule::do_upgrade_nfa(&1, &40.into(), &44.into(), Some(1.into())),
+      Ok(2)
+    );
+  });
+}
+
+#[test]
+fn do_upgrade_nfa_errors() {
+  new_test_ext().execute_with(|| {
+    // class 7 isn't upgradable
+    assert_noop!(
+      MechanicsModule::do_upgrade_nfa(&1, &7.into(), &7.into(), None),
+      Error::<Test>::IncompatibleAsset
+    );
+    // asset 41 is locked
+    assert_noop!(
+      MechanicsModule::do_upgrade_nfa(&1, &40.into(), &41.into(), None),
+      Error::<Test>::AssetLocked
+    );
+    // asset 42 is owned by another account
+    assert_noop!(
+      MechanicsModule::do_upgrade_nfa(&1, &40.into(), &42.into(), None),
+      Error::<Test>::NoPermission
+    );
+    // asset 43 has the max level
+    assert_noop!(
+      MechanicsModule::do_upgrade_nfa(&1, &40.into(), &43.into(), None),
+      Error::<Test>::MaxLevelReached
+    );
+  });
+}
+
+fn craft_recipe() -> Recipe {
+  Recipe {
+    fa_inputs: bvec![(5.into(), 100.into())],
+    nfa_inputs: bvec![RecipeNfaInput {
+      class_id: 50.into(),
+      requirements: bvec![AttributeRequirement {
+        key: bvec!(br"power"),
+        value: 5u32.try_into().unwrap(),
+      }],
+    }],
+    outputs: bvec![
+      RecipeOutput::Nfa(51.into(), Default::default()),
+      RecipeOutput::Fa(5.into(), 10.into())
+    ],
+  }
+}
+
+#[test]
+fn create_recipe_works() {
+  new_test_ext().execute_with(|| {
+    let organization_id = 2;
+    let mut wrong = craft_recipe();
+    wrong.outputs = Default::default();
+    assert_noop!(
+      MechanicsModule::create_recipe(Origin::signed(organization_id), organization_id, wrong),
+      Error::<Test>::WrongRecipe
+    );
+    let mut wrong = craft_recipe();
+    wrong.fa_inputs = bvec![(5.into(), 0.into())];
+    assert_noop!(
+      MechanicsModule::create_recipe(Origin::signed(organization_id), organization_id, wrong),
+      Error::<Test>::WrongRecipe
+    );
+    // FA 6 is owned by another organization
+    let mut wrong = craft_recipe();
+    wrong.fa_inputs = bvec![(6.into(), 100.into())];
+    assert_noop!(
+      MechanicsModule::create_recipe(Origin::signed(organization_id), organization_id, wrong),
+      Error::<Test>::NoPermission
+    );
+    let mut wrong = craft_recipe();
+    wrong.outputs = bvec![RecipeOutput::Fa(6.into(), 10.into())];
+    assert_noop!(
+      MechanicsModule::create_recipe(Origin::signed(organization_id), organization_id, wrong),
+      Error::<Test>::NoPermission
+    );
+    // class 6 is owned by another organization
+    let mut wrong = craft_recipe();
+    wrong.nfa_inputs[0].class_id = 6.into();
+    assert_noop!(
+      MechanicsModule::create_recipe(Origin::signed(organization_id), organization_id, wrong),
+      Error::<Test>::NoPermission
+    );
+    let mut wrong = craft_recipe();
+    wrong.outputs = bvec![RecipeOutput::Nfa(6.into(), Default::default())];
+    assert_noop!(
+      MechanicsModule::create_recipe(Origin::signed(organization_id), organization_id, wrong),
+      Error::<Test>::NoPermission
+    );
+
+    assert_ok!(MechanicsModule::create_recipe(
+      Origin::signed(organization_id),
+      organization_id,
+      craft_recipe()
+    ));
+    assert_eq!(
+      Recipes::<Test>::get(organization_id, 0),
+      Some(craft_recipe())
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::RecipeCreated {
+          organization_id,
+          recipe_id: 0,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn remove_recipe_works() {
+  new_test_ext().execute_with(|| {
+    let organization_id = 2;
+    assert_noop!(
+      MechanicsModule::remove_recipe(Origin::signed(organization_id), organization_id, 0),
+      Error::<Test>::UnknownRecipe
+    );
+    assert_ok!(MechanicsModule::do_create_recipe(
+      &organization_id,
+      craft_recipe()
+    ));
+    // the FA 5 is referenced by the input and the output
+    assert_eq!(fa_references(5), 2);
+    assert_ok!(MechanicsModule::remove_recipe(
+      Origin::signed(organization_id),
+      organization_id,
+      0
+    ));
+    assert!(!Recipes::<Test>::contains_key(organization_id, 0));
+    assert_eq!(fa_references(5), 0);
+  });
+}
+
+#[test]
+fn do_craft_works() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    assert_ok!(MechanicsModule::do_create_recipe(
+      &organization_id,
+      craft_recipe()
+    ));
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+    System::reset_events();
+
+    assert_ok!(MechanicsModule::exec_craft(
+      Origin::signed(who),
+      organization_id,
+      0,
+      bvec![50.into()]
+    ));
+    assert!(!Mechanics::<Test>::contains_key(
+      &mechanic_id.gamer_account,
+      mechanic_id.nonce
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          id: mechanic_id.nonce,
+          owner: mechanic_id.gamer_account,
+          result: Some(EventMechanicResultData::Craft(bvec![52.into()])),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn do_craft_errors() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    assert_noop!(
+      MechanicsModule::do_craft(&who, &organization_id, 0, bvec![50.into()]),
+      Error::<Test>::UnknownRecipe
+    );
+    assert_ok!(MechanicsModule::do_create_recipe(
+      &organization_id,
+      craft_recipe()
+    ));
+    // an asset must be given for each NFA input
+    assert_noop!(
+      MechanicsModule::do_craft(&who, &organization_id, 0, bvec![50.into(), 50.into()]),
+      Error::<Test>::IncompatibleData
+    );
+    // the asset 51 has not enough power
+    assert_noop!(
+      MechanicsModule::do_craft(&who, &organization_id, 0, bvec![51.into()]),
+      Error::<Test>::IncompatibleAsset
+    );
+    // the approved delegate can't craft with the asset of the owner
+    assert_noop!(
+      MechanicsModule::do_craft(&who, &organization_id, 0, bvec![53.into()]),
+      Error::<Test>::NoPermission
+    );
+  });
+}
+
+#[test]
+fn choose_weighted_skips_zero_weights() {
+  new_test_ext().execute_with(|| {
+    let mechanic_id = MechanicId::from_account_id::<Test>(&1, &2);
+    for i in 1..10 {
+      run_to_block(i);
+      assert_eq!(
+        MechanicsModule::choose_weighted(&mechanic_id, vec![0, 3, 0], 0),
+        1
+      );
+      assert_eq!(
+        MechanicsModule::choose_weighted(&mechanic_id, vec![2, 0, 1], 1) != 1,
+        true
+      );
+    }
+  });
+}
+
+#[test]
+fn do_open_lootbox_works() {
+  new_test_ext().execute_with(|| {
+    // class 60 is a lootbox with FA and rare NFA drops and the pity threshold 2
+    // with even block numbers FA is dropped
+    run_to_block(2);
+    let who = 1;
+    let organization_id = 2;
+    let class_id: NonFungibleClassId = 60.into();
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_open_lootbox(
+      Origin::signed(who),
+      organization_id,
+      class_id,
+      60.into()
+    ));
+    assert_eq!(
+      LootboxPityCounters::<Test>::get(&mechanic_id.gamer_account, class_id),
+      1
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          id: mechanic_id.nonce,
+          owner: mechanic_id.gamer_account.clone(),
+          result: Some(EventMechanicResultData::Lootbox(bvec![
+            LootboxDropResult::Fa(5.into(), 10.into())
+          ])),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // the pity threshold is reached, so the rare drop is guaranteed
+    assert_eq!(
+      MechanicsModule::do_open_lootbox(&mechanic_id, &who, &class_id, &60.into()),
+      Ok(bvec![LootboxDropResult::Nfa(61.into(), 62.into())])
+    );
+    assert!(!LootboxPityCounters::<Test>::contains_key(
+      &mechanic_id.gamer_account,
+      class_id
+    ));
+  });
+}
+
+#[test]
+fn do_open_lootbox_errors() {
+  new_test_ext().execute_with(|| {
+    let mechanic_id = MechanicId::from_account_id::<Test>(&1, &2);
+    // class 7 isn't a lootbox
+    assert_noop!(
+      MechanicsModule::do_open_lootbox(&mechanic_id, &1, &7.into(), &7.into()),
+      Error::<Test>::IncompatibleAsset
+    );
+    // asset 63 is owned by another account
+    assert_noop!(
+      MechanicsModule::do_open_lootbox(&mechanic_id, &1, &60.into(), &63.into()),
+      Error::<Test>::NoPermission
+    );
+  });
+}
+
+#[test]
+fn do_burn_nfa_works() {
+  new_test_ext().execute_with(|| {
+    // class 80 refunds FA 5 and NFA of class 81 when burned
+    let who = 1;
+    let organization_id = 2;
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_burn_nfa(
+      Origin::signed(who),
+      organization_id,
+      80.into(),
+      80.into()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          id: mechanic_id.nonce,
+          owner: mechanic_id.gamer_account,
+          result: Some(EventMechanicResultData::BurnNfa(bvec![
+            LootboxDropResult::Fa(5.into(), 10.into()),
+            LootboxDropResult::Nfa(81.into(), 83.into())
+          ])),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // class 82 has no Salvage characteristic, so nothing is refunded
+    assert_eq!(
+      MechanicsModule::do_burn_nfa(&who, &82.into(), &80.into()),
+      Ok(bvec![])
+    );
+  });
+}
+
+#[test]
+fn do_burn_nfa_errors() {
+  new_test_ext().execute_with(|| {
+    // asset 81 is locked
+    assert_noop!(
+      MechanicsModule::do_burn_nfa(&1, &80.into(), &81.into()),
+      Error::<Test>::AssetLocked
+    );
+    // asset 82 is owned by another account
+    assert_noop!(
+      MechanicsModule::do_burn_nfa(&1, &80.into(), &82.into()),
+      Error::<Test>::NoPermission
+    );
+  });
+}
+
+#[test]
+fn pay_with_royalty_works() {
+  new_test_ext().execute_with(|| {
+    // class 90 pays 10% to the treasury 100 of the organization 1
+    System::reset_events();
+    assert_eq!(
+      MechanicsModule::pay_with_royalty(&1, &2, &90.into(), 5.into(), 1005.into()),
+      Ok(100.into())
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::RoyaltyPaid {
+          class_id: 90.into(),
+          fungible_asset_id: 5.into(),
+          beneficiary: 100,
+          amount: 100.into(),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // class 91 pays 5% to the account 7
+    System::reset_events();
+    assert_eq!(
+      MechanicsModule::pay_with_royalty(&1, &2, &91.into(), 5.into(), 1000.into()),
+      Ok(50.into())
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::RoyaltyPaid {
+          class_id: 91.into(),
+          fungible_asset_id: 5.into(),
+          beneficiary: 7,
+          amount: 50.into(),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // no royalty from small prices
+    System::reset_events();
+    assert_eq!(
+      MechanicsModule::pay_with_royalty(&1, &2, &91.into(), 5.into(), 10.into()),
+      Ok(0.into())
+    );
+    assert_eq!(System::events(), vec![]);
+  });
+}
+
+#[test]
+fn do_list_nfa_works() {
+  new_test_ext().execute_with(|| {
+    // asset 95 of class 95 is listed for 100 of FA 5 of the organization 2
+    let who = 1;
+    let organization_id = 2;
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_list_nfa(
+      Origin::signed(who),
+      organization_id,
+      95.into(),
+      95.into(),
+      5.into(),
+      100.into()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Listed {
+          owner: mechanic_id.gamer_account.clone(),
+          id: mechanic_id.nonce,
+          class_id: 95.into(),
+          asset_id: 95.into(),
+          fungible_asset_id: 5.into(),
+          price: 100.into(),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    let mechanic = Mechanics::<Test>::get(&mechanic_id.gamer_account, mechanic_id.nonce).unwrap();
+    assert_eq!(
+      mechanic.data,
+      MechanicData::Listing(MechanicDataListing {
+        fungible_asset_id: 5.into(),
+        price: 100.into(),
+      })
+    );
+    assert_eq!(
+      mechanic.locked.to_vec(),
+      vec![LockedAccet::Nfa(95.into(), 95.into())]
+    );
+    assert!(Timeouts::<Test>::contains_key(
+      mechanic.get_tiomeout_strorage_key(mechanic_id.nonce)
+    ));
+  });
+}
+
+#[test]
+fn do_list_nfa_errors() {
+  new_test_ext().execute_with(|| {
+    // zero price
+    assert_noop!(
+      MechanicsModule::do_list_nfa(&1, &2, &95.into(), &95.into(), 5.into(), 0.into()),
+      Error::<Test>::IncompatibleData
+    );
+    // FA 6 is owned by another organization
+    assert_noop!(
+      MechanicsModule::do_list_nfa(&1, &2, &95.into(), &95.into(), 6.into(), 100.into()),
+      Error::<Test>::IncompatibleAsset
+    );
+    // the approved delegate can't offer the asset of the owner
+    assert_noop!(
+      MechanicsModule::do_propose_trade(
+        &1,
+        &2,
+        &3,
+        bvec![LockedAccet::Nfa(97.into(), 99.into())],
+        requested.clone()
+      ),
+      Error::<Test>::NoPermission
+    );
+    // class 96 is not transferable
+    assert_noop!(
+      MechanicsModule::do_list_nfa(&1, &2, &96.into(), &95.into(), 5.into(), 100.into()),
+      sp_runtime::DispatchError::Other("mock_soulbound")
+    );
+    // asset 96 is owned by another account
+    assert_noop!(
+      MechanicsModule::do_list_nfa(&1, &2, &95.into(), &96.into(), 5.into(), 100.into()),
+      Error::<Test>::NoPermission
+    );
+  });
+}
+
+#[test]
+fn do_buy_listing_works() {
+  new_test_ext().execute_with(|| {
+    let seller = 1;
+    let buyer = 3;
+    let organization_id = 2;
+    let listing_id = MechanicsModule::do_list_nfa(
+      &seller,
+      &organization_id,
+      &95.into(),
+      &95.into(),
+      5.into(),
+      100.into(),
+    )
+    .unwrap();
+    let mechanic = Mechanics::<Test>::get(&listing_id.gamer_account, listing_id.nonce).unwrap();
+    let timeout_key = mechanic.get_tiomeout_strorage_key(listing_id.nonce);
+    // the seller can't buy own asset
+    assert_noop!(
+      MechanicsModule::exec_buy_listing(
+        Origin::signed(seller),
+        organization_id,
+        listing_id.clone()
+      ),
+      Error::<Test>::NoPermission
+    );
+    // the listing is available only in the organization of the seller
+    assert_noop!(
+      MechanicsModule::exec_buy_listing(Origin::signed(buyer), 7, listing_id.clone()),
+      Error::<Test>::NoPermission
+    );
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_buy_listing(
+      Origin::signed(buyer),
+      organization_id,
+      listing_id.clone()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::ListingSold {
+          owner: listing_id.gamer_account.clone(),
+          id: listing_id.nonce,
+          buyer,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert!(!Mechanics::<Test>::contains_key(
+      &listing_id.gamer_account,
+      listing_id.nonce
+    ));
+    assert!(!Timeouts::<Test>::contains_key(timeout_key));
+    // the listing can't be bought twice
+    assert_noop!(
+      MechanicsModule::exec_buy_listing(Origin::signed(buyer), organization_id, listing_id),
+      Error::<Test>::MechanicsNotAvailable
+    );
+  });
+}
+
+#[test]
+fn do_cancel_listing_works() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    let listing_id = MechanicsModule::do_list_nfa(
+      &who,
+      &organization_id,
+      &95.into(),
+      &95.into(),
+      5.into(),
+      100.into(),
+    )
+    .unwrap();
+    // only the seller can cancel the listing
+    assert_noop!(
+      MechanicsModule::cancel_listing(Origin::signed(3), organization_id, listing_id.nonce),
+      Error::<Test>::MechanicsNotAvailable
+    );
+    System::reset_events();
+    assert_ok!(MechanicsModule::cancel_listing(
+      Origin::signed(who),
+      organization_id,
+      listing_id.nonce
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::ListingCancelled {
+          owner: listing_id.gamer_account.clone(),
+          id: listing_id.nonce,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert!(!Mechanics::<Test>::contains_key(
+      &listing_id.gamer_account,
+      listing_id.nonce
+    ));
+  });
+}
+
+#[test]
+fn listing_released_by_timeout() {
+  new_test_ext().execute_with(|| {
+    let listing_id =
+      MechanicsModule::do_list_nfa(&1, &2, &95.into(), &95.into(), 5.into(), 100.into()).unwrap();
+    let mechanic = Mechanics::<Test>::get(&listing_id.gamer_account, listing_id.nonce).unwrap();
+    // the listed asset is released, not burned
+    run_to_block(mechanic.timeout_id);
+    assert!(!Mechanics::<Test>::contains_key(
+      &listing_id.gamer_account,
+      listing_id.nonce
+    ));
+    assert!(!Timeouts::<Test>::contains_key(
+      mechanic.get_tiomeout_strorage_key(listing_id.nonce)
+    ));
+  });
+}
+
+#[test]
+fn do_create_auction_works() {
+  new_test_ext().execute_with(|| {
+    // asset 95 of class 95 is auctioned for FA 5 of the organization 2 until the block 10
+    let who = 1;
+    let organization_id = 2;
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+    // the auction must end in the future
+    assert_noop!(
+      MechanicsModule::exec_create_auction(
+        Origin::signed(who),
+        organization_id,
+        95.into(),
+        95.into(),
+        5.into(),
+        100.into(),
+        1
+      ),
+      Error::<Test>::IncompatibleData
+    );
+    // the reserve price must be set
+    assert_noop!(
+      MechanicsModule::exec_create_auction(
+        Origin::signed(who),
+        organization_id,
+        95.into(),
+        95.into(),
+        5.into(),
+        0.into(),
+        10
+      ),
+      Error::<Test>::IncompatibleData
+    );
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_create_auction(
+      Origin::signed(who),
+      organization_id,
+      95.into(),
+      95.into(),
+      5.into(),
+      100.into(),
+      10
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::AuctionCreated {
+          owner: mechanic_id.gamer_account.clone(),
+          id: mechanic_id.nonce,
+          class_id: 95.into(),
+          asset_id: 95.into(),
+          fungible_asset_id: 5.into(),
+          reserve_price: 100.into(),
+          end_block: 10,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    let mechanic = Mechanics::<Test>::get(&mechanic_id.gamer_account, mechanic_id.nonce).unwrap();
+    assert_eq!(
+      mechanic.data,
+      MechanicData::Auction(MechanicDataAuction {
+        fungible_asset_id: 5.into(),
+        reserve_price: 100.into(),
+      })
+    );
+    assert_eq!(mechanic.timeout_id, 10);
+    assert!(Timeouts::<Test>::contains_key((
+      10,
+      &mechanic_id.gamer_account,
+      mechanic_id.nonce
+    )));
+  });
+}
+
+#[test]
+fn do_bid_works() {
+  new_test_ext().execute_with(|| {
+    let seller = 1;
+    let organization_id = 2;
+    let auction_id = MechanicsModule::do_create_auction(
+      &seller,
+      &organization_id,
+      &95.into(),
+      &95.into(),
+      5.into(),
+      100.into(),
+      10,
+    )
+    .unwrap();
+    // the seller can't bid on own auction
+    assert_noop!(
+      MechanicsModule::exec_bid(
+        Origin::signed(seller),
+        organization_id,
+        auction_id.clone(),
+        100.into()
+      ),
+      Error::<Test>::NoPermission
+    );
+    // the bid must reach the reserve price
+    assert_noop!(
+      MechanicsModule::exec_bid(
+        Origin::signed(3),
+        organization_id,
+        auction_id.clone(),
+        99.into()
+      ),
+      Error::<Test>::BidTooLow
+    );
+    // the bid is reserved on the balance of the bidder
+    assert_noop!(
+      MechanicsModule::exec_bid(
+        Origin::signed(3),
+        organization_id,
+        auction_id.clone(),
+        10000.into()
+      ),
+      sp_runtime::TokenError::NoFunds
+    );
+    assert_ok!(MechanicsModule::exec_bid(
+      Origin::signed(3),
+      organization_id,
+      auction_id.clone(),
+      100.into()
+    ));
+    assert_eq!(fa_reserved(3), 100.into());
+    // the bid must exceed the highest bid
+    assert_noop!(
+      MechanicsModule::exec_bid(
+        Origin::signed(4),
+        organization_id,
+        auction_id.clone(),
+        100.into()
+      ),
+      Error::<Test>::BidTooLow
+    );
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_bid(
+      Origin::signed(4),
+      organization_id,
+      auction_id.clone(),
+      150.into()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::BidPlaced {
+          owner: auction_id.gamer_account.clone(),
+          id: auction_id.nonce,
+          bidder: 4,
+          amount: 150.into(),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // the outbid bid is refunded
+    assert_eq!(fa_reserved(3), 0.into());
+    assert_eq!(fa_reserved(4), 150.into());
+    assert_eq!(
+      AuctionBids::<Test>::get(&auction_id.gamer_account, auction_id.nonce).map(|b| b.bidder),
+      Some(4)
+    );
+  });
+}
+
+#[test]
+fn auction_settled_by_timeout() {
+  new_test_ext().execute_with(|| {
+    let auction_id =
+      MechanicsModule::do_create_auction(&1, &2, &95.into(), &95.into(), 5.into(), 100.into(), 10)
+        .unwrap();
+    assert_ok!(MechanicsModule::do_bid(&3, &2, &auction_id, 120.into()));
+
+    System::reset_events();
+    run_to_block(10);
+    // the asset is sold to the highest bidder
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          owner: auction_id.gamer_account.clone(),
+          id: auction_id.nonce,
+          result: Some(EventMechanicResultData::Auction(Some(120.into()))),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert_eq!(fa_reserved(3), 0.into());
+    assert!(!Mechanics::<Test>::contains_key(
+      &auction_id.gamer_account,
+      auction_id.nonce
+    ));
+    assert!(!AuctionBids::<Test>::contains_key(
+      &auction_id.gamer_account,
+      auction_id.nonce
+    ));
+    // the auction is over
+    assert_noop!(
+      MechanicsModule::do_bid(&4, &2, &auction_id, 200.into()),
+      Error::<Test>::MechanicsNotAvailable
+    );
+  });
+}
+
+#[test]
+fn auction_settlement_reverted() {
+  new_test_ext().execute_with(|| {
+    let auction_id =
+      MechanicsModule::do_create_auction(&1, &2, &95.into(), &99.into(), 5.into(), 100.into(), 10)
+        .unwrap();
+    assert_ok!(MechanicsModule::do_bid(&3, &2, &auction_id, 120.into()));
+
+    System::reset_events();
+    run_to_block(10);
+    // the asset can't be transferred, so the payment is reverted and the bid is released
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          owner: auction_id.gamer_account.clone(),
+          id: auction_id.nonce,
+          result: Some(EventMechanicResultData::Auction(None)),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert_eq!(fa_reserved(3), 0.into());
+    assert!(!Mechanics::<Test>::contains_key(
+      &auction_id.gamer_account,
+      auction_id.nonce
+    ));
+    assert!(!AuctionBids::<Test>::contains_key(
+      &auction_id.gamer_account,
+      auction_id.nonce
+    ));
+  });
+}
+
+#[test]
+fn auction_without_bids_returns_asset() {
+  new_test_ext().execute_with(|| {
+    let auction_id =
+      MechanicsModule::do_create_auction(&1, &2, &95.into(), &95.into(), 5.into(), 100.into(), 10)
+        .unwrap();
+
+    System::reset_events();
+    run_to_block(10);
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          owner: auction_id.gamer_account.clone(),
+          id: auction_id.nonce,
+          result: Some(EventMechanicResultData::Auction(None)),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert!(!Mechanics::<Test>::contains_key(
+      &auction_id.gamer_account,
+      auction_id.nonce
+    ));
+  });
+}
+
+#[test]
+fn do_propose_trade_works() {
+  new_test_ext().execute_with(|| {
+    // the gamer 1 offers the asset 97 and 50 of FA 5 for the asset 98 of the gamer 3
+    let who = 1;
+    let organization_id = 2;
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+    let offered: TradeAssets = bvec![
+      LockedAccet::Nfa(97.into(), 97.into()),
+      LockedAccet::Fa(5.into(), 50.into())
+    ];
+    let requested: TradeAssets = bvec![LockedAccet::Nfa(97.into(), 98.into())];
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_propose_trade(
+      Origin::signed(who),
+      organization_id,
+      3,
+      offered.clone(),
+      requested.clone()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::TradeProposed {
+          owner: mechanic_id.gamer_account.clone(),
+          id: mechanic_id.nonce,
+          counterparty: 3,
+          offered: offered.clone(),
+          requested: requested.clone(),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    let mechanic = Mechanics::<Test>::get(&mechanic_id.gamer_account, mechanic_id.nonce).unwrap();
+    assert_eq!(
+      mechanic.data,
+      MechanicData::Trade(MechanicDataTrade {
+        counterparty: 3,
+        requested
+      })
+    );
+    // the offered assets are locked
+    assert_eq!(mechanic.locked, offered);
+    assert_eq!(fa_reserved(who), 50.into());
+  });
+}
+
+#[test]
+fn do_propose_trade_errors() {
+  new_test_ext().execute_with(|| {
+    let offered: TradeAssets = bvec![LockedAccet::Nfa(97.into(), 97.into())];
+    let requested: TradeAssets = bvec![LockedAccet::Nfa(97.into(), 98.into())];
+    // the trade with oneself
+    assert_noop!(
+      MechanicsModule::do_propose_trade(&1, &2, &1, offered.clone(), requested.clone()),
+      Error::<Test>::NoPermission
+    );
+    // nothing is requested
+    assert_noop!(
+      MechanicsModule::do_propose_trade(&1, &2, &3, offered.clone(), bvec![]),
+      Error::<Test>::IncompatibleData
+    );
+    // FA 6 is owned by another organization
+    assert_noop!(
+      MechanicsModule::do_propose_trade(
+        &1,
+        &2,
+        &3,
+        offered.clone(),
+        bvec![LockedAccet::Fa(6.into(), 10.into())]
+      ),
+      Error::<Test>::IncompatibleAsset
+    );
+    // zero amount of FA
+    assert_noop!(
+      MechanicsModule::do_propose_trade(
+        &1,
+        &2,
+        &3,
+        offered.clone(),
+        bvec![LockedAccet::Fa(5.into(), 0.into())]
+      ),
+      Error::<Test>::IncompatibleData
+    );
+    // class 96 is not transferable
+    assert_noop!(
+      MechanicsModule::do_propose_trade(
+        &1,
+        &2,
+        &3,
+        bvec![LockedAccet::Nfa(96.into(), 96.into())],
+        requested
+      ),
+      sp_runtime::DispatchError::Other("mock_soulbound")
+    );
+  });
+}
+
+#[test]
+fn do_accept_trade_works() {
+  new_test_ext().execute_with(|| {
+    let organization_id = 2;
+    let trade_id = MechanicsModule::do_propose_trade(
+      &1,
+      &organization_id,
+      &3,
+      bvec![
+        LockedAccet::Nfa(97.into(), 97.into()),
+        LockedAccet::Fa(5.into(), 50.into())
+      ],
+      bvec![LockedAccet::Nfa(97.into(), 98.into())],
+    )
+    .unwrap();
+    // only the counterparty can accept the trade
+    assert_noop!(
+      MechanicsModule::exec_accept_trade(Origin::signed(4), organization_id, trade_id.clone()),
+      Error::<Test>::NoPermission
+    );
+    assert_noop!(
+      MechanicsModule::exec_accept_trade(Origin::signed(3), 7, trade_id.clone()),
+      Error::<Test>::NoPermission
+    );
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_accept_trade(
+      Origin::signed(3),
+      organization_id,
+      trade_id.clone()
+    ));
+    // the offered FA pays 10% royalty of the class 97 for the requested asset
+    assert_eq!(
+      System::events(),
+      vec![
+        EventRecord {
+          phase: Phase::Initialization,
+          event: MechanicsEvent::RoyaltyPaid {
+            class_id: 97.into(),
+            fungible_asset_id: 5.into(),
+            beneficiary: 7,
+            amount: 5.into(),
+          }
+          .into(),
+          topics: vec![],
+        },
+        EventRecord {
+          phase: Phase::Initialization,
+          event: MechanicsEvent::TradeAccepted {
+            owner: trade_id.gamer_account.clone(),
+            id: trade_id.nonce,
+            counterparty: 3,
+          }
+          .into(),
+          topics: vec![],
+        },
+      ]
+    );
+    // mechanics of both sides are dropped and the FA is unreserved to be transferred
+    let counter_id = MechanicId::from_account_id::<Test>(&3, &organization_id);
+    assert!(!Mechanics::<Test>::contains_key(
+      &trade_id.gamer_account,
+      trade_id.nonce
+    ));
+    assert!(!Mechanics::<Test>::contains_key(
+      &counter_id.gamer_account,
+      counter_id.nonce
+    ));
+    assert_eq!(fa_reserved(1), 0.into());
+    assert_noop!(
+      MechanicsModule::exec_accept_trade(Origin::signed(3), organization_id, trade_id),
+      Error::<Test>::MechanicsNotAvailable
+    );
+  });
+}
+
+#[test]
+fn do_accept_trade_pays_royalty() {
+  new_test_ext().execute_with(|| {
+    // the gamer 1 sells the asset 97 for 100 of FA 5 to the gamer 3
+    let organization_id = 2;
+    let trade_id = MechanicsModule::do_propose_trade(
+      &1,
+      &organization_id,
+      &3,
+      bvec![LockedAccet::Nfa(97.into(), 97.into())],
+      bvec![LockedAccet::Fa(5.into(), 100.into())],
+    )
+    .unwrap();
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::do_accept_trade(
+      &3,
+      &organization_id,
+      &trade_id
+    ));
+    // the counterparty pays the royalty of the class 97 from the price
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::RoyaltyPaid {
+          class_id: 97.into(),
+          fungible_asset_id: 5.into(),
+          beneficiary: 7,
+          amount: 10.into(),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert_eq!(fa_reserved(3), 0.into());
+  });
+}
+
+#[test]
+fn do_cancel_trade_works() {
+  new_test_ext().execute_with(|| {
+    let trade_id = MechanicsModule::do_propose_trade(
+      &1,
+      &2,
+      &3,
+      bvec![LockedAccet::Fa(5.into(), 50.into())],
+      bvec![LockedAccet::Nfa(97.into(), 98.into())],
+    )
+    .unwrap();
+    // only sides of the trade can cancel it
+    assert_noop!(
+      MechanicsModule::cancel_trade(Origin::signed(4), trade_id.clone()),
+      Error::<Test>::NoPermission
+    );
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::cancel_trade(
+      Origin::signed(3),
+      trade_id.clone()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::TradeCancelled {
+          owner: trade_id.gamer_account.clone(),
+          id: trade_id.nonce,
+          by: 3,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert!(!Mechanics::<Test>::contains_key(
+      &trade_id.gamer_account,
+      trade_id.nonce
+    ));
+    assert_eq!(fa_reserved(1), 0.into());
+  });
+}
+
+#[test]
+fn trade_released_by_timeout() {
+  new_test_ext().execute_with(|| {
+    let trade_id = MechanicsModule::do_propose_trade(
+      &1,
+      &2,
+      &3,
+      bvec![
+        LockedAccet::Nfa(97.into(), 97.into()),
+        LockedAccet::Fa(5.into(), 50.into())
+      ],
+      bvec![LockedAccet::Nfa(97.into(), 98.into())],
+    )
+    .unwrap();
+    let mechanic = Mechanics::<Test>::get(&trade_id.gamer_account, trade_id.nonce).unwrap();
+    // the offered assets are released, not burned
+    run_to_block(mechanic.timeout_id);
+    assert!(!Mechanics::<Test>::contains_key(
+      &trade_id.gamer_account,
+      trade_id.nonce
+    ));
+    assert_eq!(fa_reserved(1), 0.into());
+  });
+}
+
+fn bet_table() -> BetTable {
+  BetTable {
+    fungible_asset_id: 5.into(),
+    min_stake: 10.into(),
+    max_stake: 100.into(),
+    payout: 2,
+    outcomes: bvec![
+      BettorOutcome {
+        name: bvec!(br"o1"),
+        probability: 1,
+        result: OutcomeResult::Win,
+      },
+      BettorOutcome {
+        name: bvec!(br"o2"),
+        probability: 1,
+        result: OutcomeResult::Lose,
+      }
+    ],
+    rounds: 1,
+    draw_outcome: DrawOutcomeResult::Keep,
+  }
+}
+
+#[test]
+fn create_bet_table_works() {
+  new_test_ext().execute_with(|| {
+    let organization_id = 2;
+    let mut wrong = bet_table();
+    wrong.min_stake = 200.into();
+    assert_noop!(
+      MechanicsModule::create_bet_table(Origin::signed(organization_id), organization_id, wrong),
+      Error::<Test>::WrongBetTable
+    );
+    // FA 6 is owned by another organization
+    let mut wrong = bet_table();
+    wrong.fungible_asset_id = 6.into();
+    assert_noop!(
+      MechanicsModule::create_bet_table(Origin::signed(organization_id), organization_id, wrong),
+      Error::<Test>::IncompatibleAsset
+    );
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::create_bet_table(
+      Origin::signed(organization_id),
+      organization_id,
+      bet_table()
+    ));
+    assert_eq!(
+      BetTables::<Test>::get(organization_id, 0),
+      Some(bet_table())
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::BetTableCreated {
+          organization_id,
+          table_id: 0,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // the won bet pays the stake multiplied by the payout
+    assert_eq!(
+      bet_table().to_bettor(30.into()).winnings.into_inner(),
+      vec![BettorWinning::Fa(5.into(), 60.into())]
+    );
+    assert_eq!(fa_references(5), 1);
+
+    assert_ok!(MechanicsModule::remove_bet_table(
+      Origin::signed(organization_id),
+      organization_id,
+      0
+    ));
+    assert!(BetTables::<Test>::get(organization_id, 0).is_none());
+    assert_eq!(fa_references(5), 0);
+    assert_noop!(
+      MechanicsModule::remove_bet_table(Origin::signed(organization_id), organization_id, 0),
+      Error::<Test>::UnknownBetTable
+    );
+  });
+}
+
+#[test]
+fn do_bet_fa_works() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    let table_id = MechanicsModule::do_create_bet_table(&organization_id, bet_table()).unwrap();
+    assert_noop!(
+      MechanicsModule::exec_bet_fa(Origin::signed(who), organization_id, table_id, 5.into()),
+      Error::<Test>::StakeOutOfRange
+    );
+    assert_noop!(
+      MechanicsModule::exec_bet_fa(Origin::signed(who), organization_id, 9, 50.into()),
+      Error::<Test>::UnknownBetTable
+    );
+
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+    System::set_block_number(2); // rnd(2) % total_outcomes(2) = 0; 0 = win
+    System::reset_events();
+    assert_ok!(MechanicsModule::exec_bet_fa(
+      Origin::signed(who),
+      organization_id,
+      table_id,
+      50.into()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          id: mechanic_id.nonce,
+          owner: mechanic_id.gamer_account.clone(),
+          result: Some(EventMechanicResultData::Bet(EventMechanicResultDataBet {
+            outcomes: bvec![0,],
+            result: BetResult::Won,
+          }))
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // the stake is consumed
+    assert_eq!(fa_reserved(who), 0.into());
+    assert!(!Mechanics::<Test>::contains_key(
+      &mechanic_id.gamer_account,
+      mechanic_id.nonce
+    ));
+  });
+}
+
+#[test]
+fn do_bet_fa_next_round_works() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    let mut table = bet_table();
+    table.rounds = 2;
+    let table_id = MechanicsModule::do_create_bet_table(&organization_id, table).unwrap();
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+
+    System::set_block_number(2); // rnd(2) % total_outcomes(2) = 0; 0 = win
+    assert_ok!(MechanicsModule::do_bet_fa(
+      &who,
+      &organization_id,
+      table_id,
+      50.into()
+    ));
+    // the first round is played, the stake is locked until the next one
+    let mechanic = Mechanics::<Test>::get(&mechanic_id.gamer_account, mechanic_id.nonce).unwrap();
+    assert_eq!(
+      mechanic.data,
+      MechanicData::Bet(MechanicDataBet {
+        outcomes: bvec![0,],
+        table_id: Some(table_id),
+      })
+    );
+    assert_eq!(
+      mechanic.locked.into_inner(),
+      vec![LockedAccet::Fa(5.into(), 50.into())]
+    );
+    assert_eq!(fa_reserved(who), 50.into());
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::do_bet_next_round(
+      &who,
+      mechanic_id.clone()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          id: mechanic_id.nonce,
+          owner: mechanic_id.gamer_account,
+          result: Some(EventMechanicResultData::Bet(EventMechanicResultDataBet {
+            outcomes: bvec![0, 0],
+            result: BetResult::Won,
+          }))
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert_eq!(fa_reserved(who), 0.into());
+  });
+}
+
+#[test]
+fn remove_bet_table_with_pending_bets() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    let mut table = bet_table();
+    table.rounds = 2;
+    let table_id = MechanicsModule::do_create_bet_table(&organization_id, table).unwrap();
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+
+    System::set_block_number(2); // rnd(2) % total_outcomes(2) = 0; 0 = win
+    assert_ok!(MechanicsModule::do_bet_fa(
+      &who,
+      &organization_id,
+      table_id,
+      50.into()
+    ));
+    assert_eq!(PendingBets::<Test>::get(table_id), 1);
+    assert_noop!(
+      MechanicsModule::remove_bet_table(Origin::signed(organization_id), organization_id, table_id),
+      Error::<Test>::BetTableInUse
+    );
+    assert_eq!(fa_reserved(who), 50.into());
+
+    // the finished bet releases the table
+    assert_ok!(MechanicsModule::do_bet_next_round(&who, mechanic_id));
+    assert_eq!(PendingBets::<Test>::get(table_id), 0);
+    assert_ok!(MechanicsModule::remove_bet_table(
+      Origin::signed(organization_id),
+      organization_id,
+      table_id
+    ));
+    assert!(BetTables::<Test>::get(organization_id, table_id).is_none());
+  });
+}
+
+#[test]
+fn do_bet_fa_draw_returns_stake() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    let mut table = bet_table();
+    table.outcomes = bvec![
+      BettorOutcome {
+        name: bvec!(br"o0"),
+        probability: 1,
+        result: OutcomeResult::Draw,
+      },
+      BettorOutcome {
+        name: bvec!(br"o1"),
+        probability: 1,
+        result: OutcomeResult::Win,
+      },
+      BettorOutcome {
+        name: bvec!(br"o2"),
+        probability: 1,
+        result: OutcomeResult::Lose,
+      }
+    ];
+    let table_id = MechanicsModule::do_create_bet_table(&organization_id, table).unwrap();
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+
+    System::set_block_number(3); // rnd(3) % total_outcomes(3) = 0; 0 = draw
+    System::reset_events();
+    assert_ok!(MechanicsModule::do_bet_fa(
+      &who,
+      &organization_id,
+      table_id,
+      50.into()
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Finished {
+          id: mechanic_id.nonce,
+          owner: mechanic_id.gamer_account,
+          result: Some(EventMechanicResultData::Bet(EventMechanicResultDataBet {
+            outcomes: bvec![0,],
+            result: BetResult::Draw,
+          }))
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // the stake is released
+    assert_eq!(fa_reserved(who), 0.into());
+  });
+}
+
+#[test]
+fn cancel_bet_burns_stake() {
+  new_test_ext().execute_with(|| {
+    let who = 117;
+    let org = 228;
+    let _n = System::account_nonce(who);
+    System::inc_account_nonce(who);
+
+    System::set_block_number(2); // rnd(2) % total_outcomes(2) = 0; 0 = win
+    let inner_id = MechanicsModule::get_mechanic_id(&who, &org);
+    let timeout_id: <Test as frame_system::Config>::BlockNumber = inner_id.nonce as u64 + 21;
+    // the bet stops after the first round and waits for the upgrade
+    assert_ok!(MechanicsModule::do_bet(
+      &who,
+      &org,
+      &35.into(),
+      bvec![35.into()]
+    ));
+    assert!(Mechanics::<Test>::contains_key(
+      &inner_id.gamer_account,
+      &inner_id.nonce
+    ));
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::cancel(
+      Origin::signed(who),
+      org,
+      inner_id.clone()
+    ));
+    assert!(!Mechanics::<Test>::contains_key(
+      &inner_id.gamer_account,
+      &inner_id.nonce
+    ));
+    assert!(!Timeouts::<Test>::contains_key((
+      timeout_id,
+      &inner_id.gamer_account,
+      &inner_id.nonce
+    )));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Stopped {
+          id: inner_id.nonce,
+          owner: inner_id.gamer_account,
+          reason: EventMechanicStopReason::Cancelled(ForfeitPolicy::Burn),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn cancel_bet_fa_applies_penalty() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    let mut table = bet_table();
+    table.rounds = 2;
+    let table_id = MechanicsModule::do_create_bet_table(&organization_id, table).unwrap();
+    let mechanic_id = MechanicId::from_account_id::<Test>(&who, &organization_id);
+
+    System::set_block_number(2); // rnd(2) % total_outcomes(2) = 0; 0 = win
+    assert_ok!(MechanicsModule::do_bet_fa(
+      &who,
+      &organization_id,
+      table_id,
+      50.into()
+    ));
+    assert_eq!(fa_reserved(who), 50.into());
+    assert_eq!(fa_burned(who), 0.into());
+
+    System::reset_events();
+    assert_ok!(MechanicsModule::cancel(
+      Origin::signed(who),
+      organization_id,
+      mechanic_id.clone()
+    ));
+    assert!(!Mechanics::<Test>::contains_key(
+      &mechanic_id.gamer_account,
+      mechanic_id.nonce
+    ));
+    // the stake is released, 10% of it is burned and the gamer keeps 45
+    assert_eq!(fa_reserved(who), 0.into());
+    assert_eq!(fa_burned(who), 5.into());
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Stopped {
+          id: mechanic_id.nonce,
+          owner: mechanic_id.gamer_account,
+          reason: EventMechanicStopReason::Cancelled(ForfeitPolicy::Penalty(
+            Percent::from_percent(10)
+          )),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn cancel_listing_releases_asset() {
+  new_test_ext().execute_with(|| {
+    let who = 1;
+    let organization_id = 2;
+    let listing_id = MechanicsModule::do_list_nfa(
+      &who,
+      &organization_id,
+      &95.into(),
+      &95.into(),
+      5.into(),
+      100.into(),
+    )
+    .unwrap();
+    System::reset_events();
+    assert_ok!(MechanicsModule::cancel(
+      Origin::signed(who),
+      organization_id,
+      listing_id.clone()
+    ));
+    assert!(!Mechanics::<Test>::contains_key(
+      &listing_id.gamer_account,
+      listing_id.nonce
+    ));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: MechanicsEvent::Stopped {
+          id: listing_id.nonce,
+          owner: listing_id.gamer_account,
+          reason: EventMechanicStopReason::Cancelled(ForfeitPolicy::Release),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn cancel_auction_works() {
+  new_test_ext().execute_with(|| {
+    let seller = 1;
+    let organization_id = 2;
+    let auction_id = MechanicsModule::do_create_auction(
+      &seller,
+      &organization_id,
+      &95.into(),
+      &95.into(),
+      5.into(),
+      100.into(),
+      10,
+    )
+    .unwrap();
+    // only the owner of the mechanic can cancel it
+    assert_noop!(
+      MechanicsModule::cancel(Origin::signed(3), organization_id, auction_id.clone()),
+      Error::<Test>::NoPermission
+    );
+    assert_noop!(
+      MechanicsModule::cancel(Origin::signed(seller), 3, auction_id.clone()),
+      Error::<Test>::NoPermission
+    );
+    let mut unknown_id = auction_id.clone();
+    unknown_id.nonce += 1;
+    assert_noop!(
+      MechanicsModule::cancel(Origin::signed(seller), organization_id, unknown_id),
+      Error::<Test>::MechanicsNotAvailable
+    );
+    // the auction with bids can't be cancelled
+    assert_ok!(MechanicsModule::exec_bid(
+      Origin::signed(3),
+      organization_id,
+      auction_id.clone(),
+      100.into()
+    ));
+    assert_noop!(
+      MechanicsModule::cancel(Origin::signed(seller), organization_id, auction_id.clone()),
+      Error::<Test>::CannotCancel
+    );
+  });
+}
+
+#[test]
+fn migration_to_v1_works() {
+  use crate::migration::v1::{
+    MigrateToV1, OldMechanicData, OldMechanicDataBet, OldMechanicDetails,
+  };
+  use codec::Encode;
+  use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(0).put::<MechanicsModule>();
+    let mechanic_id = MechanicId::from_account_id::<Test>(&1, &2);
+    let old = OldMechanicDetails::<u64, u64> {
+      owner: mechanic_id.gamer_account.clone(),
+      timeout_id: 10,
+      locked: bvec![LockedAccet::Nfa(34.into(), 34.into())],
+      data: OldMechanicData::Bet(OldMechanicDataBet { outcomes: bvec![0] }),
+    };
+    frame_support::storage::unhashed::put_raw(
+      &Mechanics::<Test>::hashed_key_for(&mechanic_id.gamer_account, mechanic_id.nonce),
+      &old.encode(),
+    );
+
+    MigrateToV1::<Test>::on_runtime_upgrade();
+
+    let mechanic = Mechanics::<Test>::get(&mechanic_id.gamer_account, mechanic_id.nonce).unwrap();
+    assert_eq!(mechanic.timeout_id, 10);
+    assert_eq!(
+      mechanic.locked.into_inner(),
+      vec![LockedAccet::Nfa(34.into(), 34.into())]
+    );
+    assert_eq!(
+      mechanic.data,
+      MechanicData::Bet(MechanicDataBet {
+        outcomes: bvec![0],
+        table_id: None,
+      })
+    );
+    assert_eq!(MechanicsModule::on_chain_storage_version(), 1);
+  });
+}
@@ -1,8 +1,13 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use pallet_support::{
-  DefaultListLengthLimit, GamerAccount, IndexOf, LockedAccet, BETTOR_MAX_NUMBER_OF_ROUNDS,
+  bettor::{Bettor, BettorWinning, DrawOutcomeResult, Outcomes, RoundsCount},
+  AssetCharacteristic, AttributeKey, AttributeList, AttributeValue, CommonError,
+  DefaultListLengthLimit, GamerAccount, IndexOf, LockedAccet, RecipeItemsLimit,
+  SalvageRefundsLimit, ValidationContext, BETTOR_MAX_NUMBER_OF_ROUNDS, LOOTBOX_MAX_ROLLS,
 };
 use scale_info::TypeInfo;
+use sp_runtime::{traits::Zero, Percent};
+use sp_std::vec::Vec;
 
 use super::*;
 
@@ -25,6 +30,20 @@ pub enum Mechanic {
   BuyNfa,
   /// NFA bet
   Bet,
+  /// NFA upgrade to the next level
+  UpgradeNfa,
+  /// Crafting by the recipe
+  Craft,
+  /// Lootbox opening
+  Lootbox,
+  /// NFA burning by the owner
+  BurnNfa,
+  /// NFA sale for a fixed price
+  Marketplace,
+  /// NFA sale to the highest bidder
+  Auction,
+  /// Swap of assets between two gamers
+  Trade,
 }
 
 #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
@@ -37,7 +56,7 @@ pub struct MechanicDetails<AccountId, BlockNumber> {
   /// List of assets locked by mechanic
   pub locked: BoundedVec<LockedAccet, DefaultListLengthLimit>,
   // store a type of mechanic with data
-  pub data: MechanicData,
+  pub data: MechanicData<AccountId>,
 }
 impl<AccountId, BlockNumber> MechanicDetails<AccountId, BlockNumber>
 where
@@ -59,7 +78,7 @@ pub(crate) struct MechanicDetailsBuilder {}
 impl MechanicDetailsBuilder {
   pub fn build<T: pallet::Config>(
     owner: GamerAccount<T::AccountId>,
-    data: MechanicData,
+    data: MechanicData<T::AccountId>,
   ) -> MechanicDetailsOf<T> {
     let timeout_id = Pallet::<T>::calc_timeout_block();
     MechanicDetails {
@@ -72,20 +91,101 @@ impl MechanicDetailsBuilder {
 }
 
 #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, PartialEq, Eq)]
-pub enum MechanicData {
+pub enum MechanicData<AccountId> {
   /// Data of the BuyNfa mechanic. Stub - no data needed
   BuyNfa,
   /// Data of the Bet mechanic
   Bet(MechanicDataBet),
+  /// Data of the Craft mechanic. Stub - no data needed
+  Craft,
+  /// Data of the Marketplace mechanic
+  Listing(MechanicDataListing),
+  /// Data of the Auction mechanic
+  Auction(MechanicDataAuction),
+  /// Data of the Trade mechanic
+  Trade(MechanicDataTrade<AccountId>),
 }
-impl From<&MechanicData> for Mechanic {
-  fn from(value: &MechanicData) -> Self {
+impl<AccountId> From<&MechanicData<AccountId>> for Mechanic {
+  fn from(value: &MechanicData<AccountId>) -> Self {
     match value {
       MechanicData::Bet(_) => Mechanic::Bet,
       MechanicData::BuyNfa => Mechanic::BuyNfa,
+      MechanicData::Craft => Mechanic::Craft,
+      MechanicData::Listing(_) => Mechanic::Marketplace,
+      MechanicData::Auction(_) => Mechanic::Auction,
+      MechanicData::Trade(_) => Mechanic::Trade,
     }
   }
 }
+impl<AccountId> MechanicData<AccountId> {
+  /// Returns the action performed with the locked assets when the mechanic times out
+  pub(crate) fn timeout_action(&self) -> AssetAction {
+    match self {
+      // the listed or traded assets return to their owners
+      MechanicData::Listing(_) | MechanicData::Auction(_) | MechanicData::Trade(_) => {
+        AssetAction::Release
+      },
+      _ => AssetAction::Burn,
+    }
+  }
+
+  /// Returns the policy applied to the locked assets when the gamer cancels the mechanic.  \
+  /// `penalty` is the share of the stake burned when a bet played on a bet table is cancelled
+  pub(crate) fn forfeit_policy(&self, penalty: Percent) -> ForfeitPolicy {
+    match self {
+      // the listed or traded assets return to their owners
+      MechanicData::Listing(_) | MechanicData::Auction(_) | MechanicData::Trade(_) => {
+        ForfeitPolicy::Release
+      },
+      // the fungible stake returns to the gamer less the penalty
+      MechanicData::Bet(MechanicDataBet {
+        table_id: Some(_), ..
+      }) => ForfeitPolicy::Penalty(penalty),
+      // the staked assets are forfeited as on the timeout
+      _ => ForfeitPolicy::Burn,
+    }
+  }
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// Data for the Marketplace mechanic hold the price of the listed asset.  \
+/// The listed asset is the only asset locked by the mechanic
+pub struct MechanicDataListing {
+  /// The fungible asset in which the price is paid
+  pub fungible_asset_id: FungibleAssetId,
+  pub price: FungibleAssetBalance,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// Data for the Auction mechanic hold the terms of the auction.  \
+/// The auctioned asset is the only asset locked by the mechanic, the auction ends at the timeout
+/// of the mechanic
+pub struct MechanicDataAuction {
+  /// The fungible asset in which bids are made
+  pub fungible_asset_id: FungibleAssetId,
+  /// The minimal bid at which the asset can be sold
+  pub reserve_price: FungibleAssetBalance,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// The highest bid of the auction.  \
+/// The amount of the bid is reserved on the balance of the bidder until the bid is outbid
+/// or the auction ends
+pub struct AuctionBid<AccountId> {
+  pub bidder: AccountId,
+  pub amount: FungibleAssetBalance,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// Data for the Trade mechanic hold the terms of the swap.  \
+/// The assets offered by the owner of the mechanic are locked by it, the requested assets are
+/// locked by the mechanic of the `counterparty` when the trade is accepted
+pub struct MechanicDataTrade<AccountId> {
+  /// The gamer who is offered the trade
+  pub counterparty: AccountId,
+  /// Assets which the `counterparty` gives in exchange
+  pub requested: TradeAssets,
+}
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
 /// Data for the Bet machanic hold results of the outcomes of rounds played.
@@ -93,6 +193,9 @@ pub struct MechanicDataBet {
   /// Each index of `outcomes` represent the played round and a value - index of the dropped
   /// variant in the bettor respectively
   pub outcomes: MechanicDataBetOutcomes,
+  /// The bet table of the organization if the bet is staked by FA, otherwise the bet is made
+  /// by NFA with the Bettor characteristic
+  pub table_id: Option<BetTableId>,
 }
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -130,6 +233,19 @@ impl From<&MechanicUpgradePayload> for Mechanic {
 pub enum EventMechanicStopReason<AccountId, BlockNumber> {
   /// Needs a mechanics upgrade
   UpgradeNeeded(MechanicDetails<AccountId, BlockNumber>),
+  /// Cancelled by the gamer, the locked assets are forfeited by the policy
+  Cancelled(ForfeitPolicy),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+/// Policy of the forfeit of the locked assets when the gamer cancels the mechanic
+pub enum ForfeitPolicy {
+  /// All assets are released and available to the gamer
+  Release,
+  /// All assets are burned
+  Burn,
+  /// The share of the fungible assets is burned, the rest of the assets are released
+  Penalty(Percent),
 }
 
 #[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
@@ -138,6 +254,27 @@ pub enum EventMechanicResultData {
   BuyNfa(NonFungibleAssetId),
   /// Hold a final outcoms of Bet mechanic
   Bet(EventMechanicResultDataBet),
+  /// Hold a reached level of the upgraded asset
+  UpgradeNfa(u32),
+  /// Hold ids of the crafted assets
+  Craft(CraftedAssets),
+  /// Hold drops of the opened lootbox
+  Lootbox(LootboxDrops),
+  /// Hold assets refunded for the burned asset
+  BurnNfa(SalvagedAssets),
+  /// Hold the winning bid of the auction. `None` if the asset wasn't sold
+  Auction(Option<FungibleAssetBalance>),
+}
+
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+/// A received drop of the lootbox or a refund of the burned asset
+pub enum LootboxDropResult {
+  /// Minted fungible asset \
+  /// Represented as (FA id, amount)
+  Fa(FungibleAssetId, FungibleAssetBalance),
+  /// Minted non-fungible asset \
+  /// Represented as (NFA class id, NFA id)
+  Nfa(NonFungibleClassId, NonFungibleAssetId),
 }
 
 #[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
@@ -148,15 +285,182 @@ pub struct EventMechanicResultDataBet {
   pub result: BetResult,
 }
 
+/// Type of the bet table id
+pub type BetTableId = u32;
+
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, PartialEq, Eq)]
+/// A bet table of the organization where gamers stake a fungible asset.  \
+/// The stake is consumed by the won or lost bet and returned by the draw
+pub struct BetTable {
+  /// The fungible asset staked by gamers
+  pub fungible_asset_id: FungibleAssetId,
+  /// The minimal stake of the bet
+  pub min_stake: FungibleAssetBalance,
+  /// The maximal stake of the bet
+  pub max_stake: FungibleAssetBalance,
+  /// The won bet pays the stake multiplied by `payout`
+  pub payout: u32,
+  /// Options of outcomes
+  pub outcomes: Outcomes,
+  pub rounds: RoundsCount,
+  pub draw_outcome: DrawOutcomeResult,
+}
+impl BetTable {
+  /// Returns the Bettor which plays the bet of the given stake
+  pub fn to_bettor(&self, stake: FungibleAssetBalance) -> Bettor {
+    let winning = stake.saturating_mul(u128::from(self.payout)).into();
+    Bettor {
+      outcomes: self.outcomes.clone(),
+      winnings: sp_std::vec![BettorWinning::Fa(self.fungible_asset_id, winning)]
+        .try_into()
+        .expect("one winning can't exceed DefaultListLengthLimit"),
+      rounds: self.rounds,
+      draw_outcome: self.draw_outcome.clone(),
+    }
+  }
+
+  /// Checks that the table can be used
+  pub fn is_valid(&self) -> bool {
+    if self.min_stake.is_zero() || self.min_stake > self.max_stake || self.payout == 0 {
+      return false;
+    }
+    self.to_bettor(self.min_stake).is_valid()
+  }
+}
+
+/// Type of the crafting recipe id
+pub type RecipeId = u32;
+
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, PartialEq, Eq)]
+/// A crafting recipe of the organization
+pub struct Recipe {
+  /// Fungible assets which will be burned.  \
+  /// Represented as (FA id, amount)
+  pub fa_inputs: BoundedVec<(FungibleAssetId, FungibleAssetBalance), RecipeItemsLimit>,
+  /// Non-fungible assets which will be burned. The gamer provides ids of assets in the same order
+  pub nfa_inputs: BoundedVec<RecipeNfaInput, RecipeItemsLimit>,
+  /// Assets which will be minted
+  pub outputs: BoundedVec<RecipeOutput, RecipeItemsLimit>,
+}
+impl Recipe {
+  /// Checks that the recipe can be used
+  pub fn is_valid(&self) -> bool {
+    // recipe must consume something and produce something
+    if self.outputs.is_empty() || (self.fa_inputs.is_empty() && self.nfa_inputs.is_empty()) {
+      return false;
+    }
+    // FAs should have amount more than 0
+    if self.fa_inputs.iter().any(|(_, amount)| amount.is_zero()) {
+      return false;
+    }
+    // attributes of minted NFAs must be valid
+    self.outputs.iter().all(|o| match o {
+      RecipeOutput::Fa(_, amount) => !amount.is_zero(),
+      RecipeOutput::Nfa(_, attributes) => attributes.iter().all(|a| a.validate().is_ok()),
+    })
+  }
+
+  /// Checks that the assets consumed and produced by the recipe exist and belong to the
+  /// `organization_id`
+  pub fn ensure_references<AccountId: PartialEq, C: ValidationContext<AccountId>>(
+    &self,
+    organization_id: &AccountId,
+  ) -> Result<(), CommonError> {
+    for (asset_id, _) in self.fa_inputs.iter() {
+      C::ensure_fa(asset_id, organization_id)?;
+    }
+    for input in self.nfa_inputs.iter() {
+      C::ensure_nfa_class(&input.class_id, organization_id)?;
+    }
+    for output in self.outputs.iter() {
+      match output {
+        RecipeOutput::Fa(asset_id, _) => C::ensure_fa(asset_id, organization_id)?,
+        RecipeOutput::Nfa(class_id, _) => C::ensure_nfa_class(class_id, organization_id)?,
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns the number of inputs and outputs of the recipe
+  pub fn items_count(&self) -> u64 {
+    (self.fa_inputs.len() + self.nfa_inputs.len() + self.outputs.len()) as u64
+  }
+
+  /// Returns fungible assets referenced by the recipe, one item per reference
+  pub fn fa_references(&self) -> Vec<FungibleAssetId> {
+    self
+      .fa_inputs
+      .iter()
+      .map(|(asset_id, _)| *asset_id)
+      .chain(self.outputs.iter().filter_map(|output| match output {
+        RecipeOutput::Fa(asset_id, _) => Some(*asset_id),
+        RecipeOutput::Nfa(_, _) => None,
+      }))
+      .collect()
+  }
+}
+
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, PartialEq, Eq)]
+/// A non-fungible input of the recipe
+pub struct RecipeNfaInput {
+  /// Class of the asset
+  pub class_id: NonFungibleClassId,
+  /// Attributes which the asset must have
+  pub requirements: BoundedVec<AttributeRequirement, RecipeItemsLimit>,
+}
+
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, PartialEq, Eq)]
+/// A requirement to the attribute of the asset.  \
+/// A number attribute must be greater than or equal to the given value, a text one must be equal
+pub struct AttributeRequirement {
+  pub key: AttributeKey,
+  pub value: AttributeValue,
+}
+impl AttributeRequirement {
+  /// Checks the given attribute value against the requirement
+  pub fn is_met(&self, value: &AttributeValue) -> bool {
+    match (&self.value, value) {
+      (AttributeValue::Number(required), AttributeValue::Number(value)) => {
+        value.number_value >= required.number_value
+      },
+      (AttributeValue::Text(required), AttributeValue::Text(value)) => required == value,
+      _ => false,
+    }
+  }
+}
+
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, PartialEq, Eq)]
+/// An output of the recipe
+pub enum RecipeOutput {
+  /// Fungible asset \
+  /// Represented as (FA id, amount)
+  Fa(FungibleAssetId, FungibleAssetBalance),
+  /// Non-fungible asset \
+  /// Represented as (NFA class id, attributes of the minted asset)
+  Nfa(NonFungibleClassId, AttributeList),
+}
+
 /// Actions that are performed with the assets at the time of the destruction of the mechanics
 pub(crate) enum AssetAction {
   /// All assets will be released and available to the user
   Release,
   /// All assets will be burned
   Burn,
+  /// The share of the fungible assets will be burned, the rest of the assets will be released
+  Penalty(Percent),
+}
+impl From<ForfeitPolicy> for AssetAction {
+  fn from(policy: ForfeitPolicy) -> Self {
+    match policy {
+      ForfeitPolicy::Release => AssetAction::Release,
+      ForfeitPolicy::Burn => AssetAction::Burn,
+      ForfeitPolicy::Penalty(penalty) => AssetAction::Penalty(penalty),
+    }
+  }
 }
 
 pub(crate) type MechanicDetailsOf<T> = MechanicDetails<AccountIdOf<T>, BlockNumberFor<T>>;
+pub(crate) type AuctionBidOf<T> = AuctionBid<AccountIdOf<T>>;
 /// Each index of `outcomes` represent the played round and a value - index of the dropped variant
 /// in the bettor respectively
 pub(crate) type MechanicDataBetOutcomes = BoundedVec<u32, ConstU32<BETTOR_MAX_NUMBER_OF_ROUNDS>>;
@@ -165,6 +469,19 @@ pub(crate) type MechanicUpgradeDataOf<T> = MechanicUpgradeData<AccountIdOf<T>, I
 pub(crate) type EventMechanicStopReasonOf<T> =
   EventMechanicStopReason<AccountIdOf<T>, BlockNumberFor<T>>;
 
+/// Ids of the assets minted by the Craft mechanic
+pub type CraftedAssets = BoundedVec<NonFungibleAssetId, RecipeItemsLimit>;
+
+/// Drops received by the lootbox opening
+pub type LootboxDrops = BoundedVec<LootboxDropResult, ConstU32<LOOTBOX_MAX_ROLLS>>;
+
+/// Assets given by one side of the trade.  \
+/// Bounded by the same limit as the locks of the mechanic, so each side fits into its mechanic
+pub type TradeAssets = BoundedVec<LockedAccet, DefaultListLengthLimit>;
+
+/// Assets refunded by the Salvage characteristic of the burned asset
+pub type SalvagedAssets = BoundedVec<LootboxDropResult, SalvageRefundsLimit>;
+
 /// Type of data that hold a result of finished mechanic.
 ///
 /// This is necessary because the mechanic resets all internal data when it finished.
@@ -0,0 +1,57 @@
+//! Runtime API definition for the Non-Fungible Assets pallet.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_support::{
+  royalty::RoyaltyBeneficiary,
+  types_nfa::{AssetDetails, ClassDetails},
+  Attribute, FungibleAssetBalance, NonFungibleAssetId, NonFungibleClassId,
+};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+  /// The API to query data of the non-fungible assets.
+  pub trait NonFungibleAssetsApi<AccountId, Index>
+  where
+    AccountId: Codec,
+    Index: Codec,
+  {
+    /// Returns the metadata of the asset class.
+    fn class_metadata(class_id: NonFungibleClassId) -> Option<Vec<u8>>;
+    /// Returns the metadata of the asset instance.
+    ///
+    /// If the instance has no own metadata, the metadata of its class is returned.
+    fn asset_metadata(class_id: NonFungibleClassId, asset_id: NonFungibleAssetId) -> Option<Vec<u8>>;
+    /// Returns the assets held by the account, optionally only the assets of the given class.
+    ///
+    /// Skips `offset` assets and returns at most `limit` of them.
+    fn account_assets(
+      account: AccountId,
+      class_id: Option<NonFungibleClassId>,
+      offset: u32,
+      limit: u32,
+    ) -> Vec<(NonFungibleClassId, NonFungibleAssetId)>;
+    /// Returns details of the asset instance, including the owner and the lock status.
+    fn asset_details(
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+    ) -> Option<AssetDetails<AccountId, Index>>;
+    /// Returns attributes of the asset instance, including not overridden attributes of its class.
+    ///
+    /// Skips `offset` attributes and returns at most `limit` of them.
+    fn asset_attributes(
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      offset: u32,
+      limit: u32,
+    ) -> Vec<Attribute>;
+    /// Returns details of the asset class including its characteristics.
+    fn class_details(class_id: NonFungibleClassId) -> Option<ClassDetails<AccountId>>;
+    /// Returns the beneficiary and the amount of the royalty paid when an asset of the class
+    /// is sold for the `price`.
+    fn royalty(
+      class_id: NonFungibleClassId,
+      price: FungibleAssetBalance,
+    ) -> Option<(RoyaltyBeneficiary<AccountId>, FungibleAssetBalance)>;
+  }
+}
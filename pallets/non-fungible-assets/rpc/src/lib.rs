@@ -0,0 +1,204 @@
+//! RPC interface for the Non-Fungible Assets pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+  core::RpcResult,
+  proc_macros::rpc,
+  types::error::{CallError, ErrorObject},
+};
+use pallet_support::{
+  royalty::RoyaltyBeneficiary,
+  types_nfa::{AssetDetails, ClassDetails},
+  Attribute, FungibleAssetBalance, NonFungibleAssetId, NonFungibleClassId,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+  generic::BlockId,
+  traits::{Block as BlockT, MaybeSerializeDeserialize},
+};
+
+pub use pallet_non_fungible_assets_rpc_runtime_api::NonFungibleAssetsApi as NonFungibleAssetsRuntimeApi;
+
+/// The default number of items returned by the paginated queries.
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait NonFungibleAssetsApi<BlockHash, AccountId, Index> {
+  /// Returns the assets held by the account, optionally only the assets of the given class.
+  #[method(name = "nfa_accountAssets")]
+  fn account_assets(
+    &self,
+    account: AccountId,
+    class_id: Option<NonFungibleClassId>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<(NonFungibleClassId, NonFungibleAssetId)>>;
+
+  /// Returns details of the asset instance, including the owner and the lock status.
+  #[method(name = "nfa_assetDetails")]
+  fn asset_details(
+    &self,
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Option<AssetDetails<AccountId, Index>>>;
+
+  /// Returns attributes of the asset instance, including not overridden attributes of its class.
+  #[method(name = "nfa_assetAttributes")]
+  fn asset_attributes(
+    &self,
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<Attribute>>;
+
+  /// Returns details of the asset class including its characteristics.
+  #[method(name = "nfa_classDetails")]
+  fn class_details(
+    &self,
+    class_id: NonFungibleClassId,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Option<ClassDetails<AccountId>>>;
+
+  /// Returns the beneficiary and the amount of the royalty paid when an asset of the class
+  /// is sold for the `price`.
+  #[method(name = "nfa_royalty")]
+  fn royalty(
+    &self,
+    class_id: NonFungibleClassId,
+    price: FungibleAssetBalance,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Option<(RoyaltyBeneficiary<AccountId>, FungibleAssetBalance)>>;
+}
+
+/// Provides RPC methods to query the non-fungible assets.
+pub struct NonFungibleAssets<C, B> {
+  client: Arc<C>,
+  _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> NonFungibleAssets<C, B> {
+  /// Creates a new instance of the NonFungibleAssets RPC handler.
+  pub fn new(client: Arc<C>) -> Self {
+    Self {
+      client,
+      _marker: Default::default(),
+    }
+  }
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+  /// The call to runtime failed.
+  RuntimeError,
+}
+
+impl From<Error> for i32 {
+  fn from(e: Error) -> i32 {
+    match e {
+      Error::RuntimeError => 1,
+    }
+  }
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+  CallError::Custom(ErrorObject::owned(
+    Error::RuntimeError.into(),
+    "Unable to query the non-fungible assets.",
+    Some(format!("{:?}", e)),
+  ))
+  .into()
+}
+
+impl<C, Block, AccountId, Index>
+  NonFungibleAssetsApiServer<<Block as BlockT>::Hash, AccountId, Index>
+  for NonFungibleAssets<C, Block>
+where
+  Block: BlockT,
+  C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+  C::Api: NonFungibleAssetsRuntimeApi<Block, AccountId, Index>,
+  AccountId: Codec + MaybeSerializeDeserialize + Send + Sync + 'static,
+  Index: Codec + MaybeSerializeDeserialize + Send + Sync + 'static,
+{
+  fn account_assets(
+    &self,
+    account: AccountId,
+    class_id: Option<NonFungibleClassId>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<(NonFungibleClassId, NonFungibleAssetId)>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    api
+      .account_assets(
+        &at,
+        account,
+        class_id,
+        offset.unwrap_or_default(),
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+      )
+      .map_err(runtime_error)
+  }
+
+  fn asset_details(
+    &self,
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Option<AssetDetails<AccountId, Index>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    api
+      .asset_details(&at, class_id, asset_id)
+      .map_err(runtime_error)
+  }
+
+  fn asset_attributes(
+    &self,
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    offset: Option<u32>,
+    limit: Option<u32>,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<Attribute>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    api
+      .asset_attributes(
+        &at,
+        class_id,
+        asset_id,
+        offset.unwrap_or_default(),
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+      )
+      .map_err(runtime_error)
+  }
+
+  fn class_details(
+    &self,
+    class_id: NonFungibleClassId,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Option<ClassDetails<AccountId>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    api.class_details(&at, class_id).map_err(runtime_error)
+  }
+
+  fn royalty(
+    &self,
+    class_id: NonFungibleClassId,
+    price: FungibleAssetBalance,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Option<(RoyaltyBeneficiary<AccountId>, FungibleAssetBalance)>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+    api.royalty(&at, class_id, price).map_err(runtime_error)
+  }
+}
@@ -1,6 +1,8 @@
 //! Functions for the Non-Fungible-Assets pallet.
 
-use pallet_support::{AssetCharacteristic, Locker};
+use pallet_support::{
+  royalty::RoyaltyBeneficiary, AssetCharacteristic, Locker, QUERY_PAGE_SIZE_LIMIT,
+};
 
 use super::*;
 
@@ -32,14 +34,86 @@ impl<T: Config> Pallet<T> {
       if let Some(check_owner) = maybe_check_owner {
         ensure!(class_details.owner == check_owner, Error::<T>::NoPermission);
       }
+      // Release fungible assets referenced by the characteristics
+      Self::update_fa_references(class_details.bettor.as_ref(), None)?;
+      Self::update_fa_references(class_details.purchased.as_ref(), None)?;
+      Self::update_fa_references(class_details.salvage.as_ref(), None)?;
+      Self::update_fa_references(class_details.upgradable.as_ref(), None)?;
+      Self::update_fa_references(class_details.lootbox.as_ref(), None)?;
       ClassAccounts::<T>::remove(&class_details.owner, class_id);
+      OwnershipOffers::<T>::remove(class_id);
       // Remove attributes for class and for all instances
       _ = ClassAttributes::<T>::clear_prefix(class_id, u32::MAX, None);
+      _ = AttributeTemplates::<T>::clear_prefix(class_id, u32::MAX, None);
+      _ = AttributeSchemas::<T>::clear_prefix(class_id, u32::MAX, None);
+      // Remove metadata for class and for all instances
+      ClassMetadataOf::<T>::remove(class_id);
+      _ = AssetMetadataOf::<T>::clear_prefix(class_id, u32::MAX, None);
       Self::deposit_event(Event::Destroyed { class_id });
       Ok(())
     })
   }
 
+  /// Offers to transfer the ownership of the asset class to the `new_owner` organization
+  pub fn do_offer_ownership(
+    class_id: NonFungibleClassId,
+    maybe_check_owner: Option<T::AccountId>,
+    new_owner: T::AccountId,
+  ) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    OwnershipOffers::<T>::insert(class_id, &new_owner);
+    Self::deposit_event(Event::OwnershipOffered {
+      class_id,
+      owner: details.owner,
+      new_owner,
+    });
+    Ok(())
+  }
+
+  /// Cancels the offer to transfer the ownership of the asset class
+  pub fn do_cancel_ownership_offer(
+    class_id: NonFungibleClassId,
+    maybe_check_owner: Option<T::AccountId>,
+  ) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    OwnershipOffers::<T>::take(class_id).ok_or(Error::<T>::NoOwnershipOffer)?;
+    Self::deposit_event(Event::OwnershipOfferCancelled {
+      class_id,
+      owner: details.owner,
+    });
+    Ok(())
+  }
+
+  /// Transfers the ownership of the asset class to the organization which the ownership was offered to
+  pub fn do_accept_ownership(
+    class_id: NonFungibleClassId,
+    new_owner: T::AccountId,
+  ) -> DispatchResult {
+    ensure!(
+      OwnershipOffers::<T>::get(class_id).as_ref() == Some(&new_owner),
+      Error::<T>::NoOwnershipOffer
+    );
+    Classes::<T>::try_mutate(class_id, |maybe_details| -> DispatchResult {
+      let details = maybe_details.as_mut().ok_or(Error::<T>::UnknownClass)?;
+      let old_owner = sp_std::mem::replace(&mut details.owner, new_owner.clone());
+      OwnershipOffers::<T>::remove(class_id);
+      ClassAccounts::<T>::remove(&old_owner, class_id);
+      ClassAccounts::<T>::insert(&new_owner, class_id, ());
+      Self::deposit_event(Event::OwnerChanged {
+        class_id,
+        old_owner,
+        new_owner,
+      });
+      Ok(())
+    })
+  }
+
   pub fn do_mint(
     class_id: NonFungibleClassId,
     owner: T::AccountId,
@@ -65,6 +139,8 @@ impl<T: Config> Pallet<T> {
       let asset_details = AssetDetailsBuilder::<T>::new(owner.clone())?.build()?;
       Assets::<T>::insert(class_id, asset_id, asset_details);
 
+      Self::roll_attributes(&class_id, &asset_id);
+
       Self::deposit_event(Event::Issued {
         class_id,
         asset_id,
@@ -90,8 +166,17 @@ impl<T: Config> Pallet<T> {
         );
       };
       Accounts::<T>::remove((&asset_details.owner, &class_id, &asset_id));
+      // Detach an instance from the parent and release all equipped to it instances to the owner
+      if let Locker::Nfa(parent_class_id, parent_asset_id) = asset_details.locked {
+        Children::<T>::remove((parent_class_id, parent_asset_id, class_id, asset_id));
+      }
+      Self::release_children(class_id, asset_id);
       // Remove attributes for an instance
       _ = Attributes::<T>::clear_prefix(asset_id, u32::MAX, None);
+      // Remove metadata for an instance
+      AssetMetadataOf::<T>::remove(class_id, asset_id);
+      // Remove the approval of an instance
+      Approvals::<T>::remove(class_id, asset_id);
       // decrease class intances counter
       Classes::<T>::try_mutate(class_id, |maybe_class_details| -> DispatchResult {
         let class_details = maybe_class_details
@@ -113,6 +198,74 @@ impl<T: Config> Pallet<T> {
     })
   }
 
+  /// Transfers an asset to the `dest` account together with all equipped to it assets.  \
+  /// The owner check passes for the approved delegate too, the approval is consumed by the transfer.  \
+  /// Locked assets and assets of soulbound classes can't be transferred, neither can the assets
+  /// with equipped soulbound assets.  \
+  /// Returns the number of moved equipped assets
+  pub(crate) fn do_transfer(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    maybe_check_owner: Option<&T::AccountId>,
+    dest: &T::AccountId,
+  ) -> DispatchResultAs<u32> {
+    Self::ensure_transferable(&class_id)?;
+    let descendants = Self::descendants_of(class_id, asset_id);
+    let moved = descendants.len() as u32;
+    for (child_class_id, _) in descendants.iter() {
+      Self::ensure_transferable(child_class_id)?;
+    }
+    Assets::<T>::try_mutate(class_id, asset_id, |maybe_details| -> DispatchResult {
+      let asset_details = maybe_details.as_mut().ok_or(Error::<T>::UnknownAsset)?;
+      if let Some(check_owner) = maybe_check_owner {
+        Self::ensure_owner_or_delegate(&class_id, &asset_id, asset_details, check_owner)?;
+      };
+      ensure!(asset_details.locked == Locker::None, Error::<T>::Locked);
+
+      Approvals::<T>::remove(class_id, asset_id);
+      Accounts::<T>::remove((&asset_details.owner, &class_id, &asset_id));
+      Accounts::<T>::insert((dest, &class_id, &asset_id), ());
+      let from = sp_std::mem::replace(&mut asset_details.owner, dest.clone());
+      // equipped instances follow the parent
+      for (child_class_id, child_asset_id) in descendants {
+        Assets::<T>::mutate(child_class_id, child_asset_id, |maybe_child| {
+          if let Some(child) = maybe_child {
+            Approvals::<T>::remove(child_class_id, child_asset_id);
+            Accounts::<T>::remove((&child.owner, &child_class_id, &child_asset_id));
+            Accounts::<T>::insert((dest, &child_class_id, &child_asset_id), ());
+            child.owner = dest.clone();
+          }
+        });
+      }
+
+      Self::deposit_event(Event::Transferred {
+        class_id,
+        asset_id,
+        from,
+        to: dest.clone(),
+      });
+      Ok(())
+    })?;
+    Ok(moved)
+  }
+
+  /// Weight of the transfer of an asset with `descendants` equipped assets.  \
+  /// Each equipped asset is checked and re-owned as the asset itself
+  pub(crate) fn transfer_weight(descendants: u32) -> Weight {
+    let descendants = u64::from(descendants);
+    T::DbWeight::get().reads_writes(3 + 3 * descendants, 4 + 4 * descendants)
+  }
+
+  /// Checks that assets of the class can change hands
+  pub fn ensure_transferable(class_id: &NonFungibleClassId) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    ensure!(
+      details.transferability.is_transferable(),
+      Error::<T>::Soulbound
+    );
+    Ok(())
+  }
+
   /// Creates attribute for the asset class.  \
   /// Attributes can be created only for classes
   pub fn do_create_attribute(
@@ -127,6 +280,7 @@ impl<T: Config> Pallet<T> {
     if let Some(check_owner) = maybe_check_owner {
       ensure!(details.owner == check_owner, Error::<T>::NoPermission);
     }
+    Self::ensure_meets_schema(&class_id, &attribute.key, &attribute.value)?;
     // Attribute must not exits
     if ClassAttributes::<T>::contains_key(class_id, &attribute.key) {
       return Err(Error::<T>::AttributeAlreadyExists.into());
@@ -164,6 +318,184 @@ impl<T: Config> Pallet<T> {
     Ok(())
   }
 
+  /// Sets the attribute template for the asset class.  \
+  /// The existing template with the same key is replaced
+  pub fn do_set_attribute_template(
+    class_id: NonFungibleClassId,
+    maybe_check_owner: Option<T::AccountId>,
+    key: AttributeKey,
+    template: AttributeTemplate,
+  ) -> DispatchResult {
+    template
+      .validate()
+      .map_err(|_| Error::<T>::WrongAttributeTemplate)?;
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    for value in template.extremes().iter() {
+      Self::ensure_meets_schema(&class_id, &key, value)?;
+    }
+    if !AttributeTemplates::<T>::contains_key(class_id, &key) {
+      ensure!(
+        AttributeTemplates::<T>::iter_key_prefix(class_id).count()
+          < pallet_support::AttributeListLengthLimit::get() as usize,
+        Error::<T>::TooManyAttributeTemplates
+      );
+    }
+
+    AttributeTemplates::<T>::insert(class_id, &key, &template);
+    Self::deposit_event(Event::AttributeTemplateSet {
+      class_id,
+      key,
+      template,
+    });
+    Ok(())
+  }
+
+  /// Removes the attribute template from the asset class
+  pub fn do_remove_attribute_template(
+    class_id: NonFungibleClassId,
+    maybe_check_owner: Option<T::AccountId>,
+    key: AttributeKey,
+  ) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    AttributeTemplates::<T>::take(class_id, &key).ok_or(Error::<T>::UnknownAttributeTemplate)?;
+    Self::deposit_event(Event::AttributeTemplateRemoved { class_id, key });
+    Ok(())
+  }
+
+  /// Sets the attribute schema for the asset class.  \
+  /// The existing schema with the same key is replaced.  \
+  /// Already assigned attributes aren't checked against the new schema
+  pub fn do_set_attribute_schema(
+    class_id: NonFungibleClassId,
+    maybe_check_owner: Option<T::AccountId>,
+    key: AttributeKey,
+    schema: AttributeSchema,
+  ) -> DispatchResult {
+    schema
+      .validate()
+      .map_err(|_| Error::<T>::WrongAttributeSchema)?;
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    if !AttributeSchemas::<T>::contains_key(class_id, &key) {
+      ensure!(
+        AttributeSchemas::<T>::iter_key_prefix(class_id).count()
+          < pallet_support::AttributeListLengthLimit::get() as usize,
+        Error::<T>::TooManyAttributeSchemas
+      );
+    }
+
+    AttributeSchemas::<T>::insert(class_id, &key, &schema);
+    Self::deposit_event(Event::AttributeSchemaSet {
+      class_id,
+      key,
+      schema,
+    });
+    Ok(())
+  }
+
+  /// Removes the attribute schema from the asset class
+  pub fn do_remove_attribute_schema(
+    class_id: NonFungibleClassId,
+    maybe_check_owner: Option<T::AccountId>,
+    key: AttributeKey,
+  ) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    AttributeSchemas::<T>::take(class_id, &key).ok_or(Error::<T>::UnknownAttributeSchema)?;
+    Self::deposit_event(Event::AttributeSchemaRemoved { class_id, key });
+    Ok(())
+  }
+
+  /// Checks the attribute value against the attribute schema of the class.  \
+  /// Any attribute is allowed if the class has no schemas
+  pub fn ensure_meets_schema(
+    class_id: &NonFungibleClassId,
+    key: &AttributeKey,
+    value: &AttributeValue,
+  ) -> DispatchResult {
+    match AttributeSchemas::<T>::get(class_id, key) {
+      Some(schema) => ensure!(schema.is_met_by(value), Error::<T>::AttributeSchemaMismatch),
+      None => ensure!(
+        AttributeSchemas::<T>::iter_key_prefix(class_id)
+          .next()
+          .is_none(),
+        Error::<T>::AttributeNotInSchema
+      ),
+    }
+    Ok(())
+  }
+
+  /// Checks that every attribute required by the schema of the class is assigned by `attributes`,
+  /// by the asset itself, by the class or by the attribute templates of the class
+  fn ensure_required_attributes(
+    class_id: &NonFungibleClassId,
+    maybe_asset_id: Option<&NonFungibleAssetId>,
+    attributes: &AttributeList,
+  ) -> DispatchResult {
+    for (key, schema) in AttributeSchemas::<T>::iter_prefix(class_id) {
+      if !schema.required {
+        continue;
+      }
+      let assigned = attributes.iter().any(|attr| attr.key == key)
+        || maybe_asset_id.map_or(false, |asset_id| {
+          Attributes::<T>::contains_key(asset_id, &key)
+        })
+        || ClassAttributes::<T>::contains_key(class_id, &key)
+        || AttributeTemplates::<T>::contains_key(class_id, &key);
+      ensure!(assigned, Error::<T>::RequiredAttributeMissing);
+    }
+    Ok(())
+  }
+
+  /// Checks that the attributes of a new asset of the class meet the attribute schema of the class
+  pub fn validate_attributes(
+    class_id: &NonFungibleClassId,
+    attributes: &AttributeList,
+  ) -> DispatchResult {
+    for attr in attributes.iter() {
+      attr.validate()?;
+      Self::ensure_meets_schema(class_id, &attr.key, &attr.value)?;
+    }
+    Self::ensure_required_attributes(class_id, None, attributes)
+  }
+
+  /// Assigns an attributes to the asset after checking them against the attribute schema of
+  /// the class
+  pub fn set_attributes(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    attributes: AttributeList,
+  ) -> DispatchResult {
+    for attr in attributes.iter() {
+      attr.validate()?;
+      Self::ensure_meets_schema(class_id, &attr.key, &attr.value)?;
+    }
+    Self::ensure_required_attributes(class_id, Some(asset_id), &attributes)?;
+    Self::assign_attributes(asset_id, attributes)
+  }
+
+  /// Assigns to the asset attributes rolled by the attribute templates of its class.  \
+  /// Attributes assigned to the asset later (e.g. attributes of the offer) override rolled ones
+  fn roll_attributes(class_id: &NonFungibleClassId, asset_id: &NonFungibleAssetId) {
+    for (key, template) in AttributeTemplates::<T>::iter_prefix(class_id) {
+      let (random_seed, _) =
+        T::Randomness::random(&(b"nfa_attributes", class_id, asset_id, &key).encode());
+      let random_number = <u32>::decode(&mut random_seed.as_ref())
+        .expect("secure hashes should always be bigger than u32; qed");
+      Attributes::<T>::insert(asset_id, &key, template.roll(random_number));
+    }
+  }
+
   /// Returns class details by class id
   /// Can return UnknownClass Error
   pub fn get_class_details(class_id: &NonFungibleClassId) -> DispatchResultAs<ClassDetailsOf<T>> {
@@ -182,13 +514,20 @@ impl<T: Config> Pallet<T> {
     Ok(())
   }
 
+  /// Returns an attribute of the asset or the attribute of the class if the asset doesn't have it
+  pub fn attribute_of(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    key: &AttributeKey,
+  ) -> Option<AttributeValue> {
+    Attributes::<T>::get(asset_id, key).or_else(|| ClassAttributes::<T>::get(class_id, key))
+  }
+
   pub(crate) fn do_set_characteristic(
     class_id: NonFungibleClassId,
     maybe_check_owner: Option<T::AccountId>,
-    characteristic: Characteristic,
+    characteristic: Characteristic<T::AccountId>,
   ) -> DispatchResult {
-    // TODO: add references management.
-    // If references to FA or NFA are changed then references in assets must be changed
     let mut details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
 
     if let Some(check_owner) = maybe_check_owner {
@@ -201,7 +540,11 @@ impl<T: Config> Pallet<T> {
           AssetCharacteristic::ensure(inner)
             .map_err::<Error<T>, _>(Into::into)
             .map_err::<DispatchError, _>(Into::into)?;
+          inner
+            .ensure_references::<T::AccountId, Self>(&details.owner)
+            .map_err::<Error<T>, _>(Into::into)?;
         };
+        Self::update_fa_references(details.bettor.as_ref(), bettor.as_ref())?;
         details.bettor = bettor;
       },
       Characteristic::Purchased(purchased) => {
@@ -209,9 +552,64 @@ impl<T: Config> Pallet<T> {
           AssetCharacteristic::ensure(inner)
             .map_err::<Error<T>, _>(Into::into)
             .map_err::<DispatchError, _>(Into::into)?;
+          inner
+            .ensure_references::<T::AccountId, Self>(&details.owner)
+            .map_err::<Error<T>, _>(Into::into)?;
+          // assets bought by the offers must meet the attribute schema
+          for offer in inner.offers.iter() {
+            Self::validate_attributes(&class_id, &offer.attributes)?;
+          }
         };
+        Self::update_fa_references(details.purchased.as_ref(), purchased.as_ref())?;
         details.purchased = purchased;
       },
+      Characteristic::Transferability(transferability) => {
+        details.transferability = transferability;
+      },
+      Characteristic::Upgradable(upgradable) => {
+        if let Some(inner) = &upgradable {
+          AssetCharacteristic::ensure(inner)
+            .map_err::<Error<T>, _>(Into::into)
+            .map_err::<DispatchError, _>(Into::into)?;
+          inner
+            .ensure_references::<T::AccountId, Self>(&details.owner)
+            .map_err::<Error<T>, _>(Into::into)?;
+        };
+        Self::update_fa_references(details.upgradable.as_ref(), upgradable.as_ref())?;
+        details.upgradable = upgradable;
+      },
+      Characteristic::Lootbox(lootbox) => {
+        if let Some(inner) = &lootbox {
+          AssetCharacteristic::ensure(inner)
+            .map_err::<Error<T>, _>(Into::into)
+            .map_err::<DispatchError, _>(Into::into)?;
+          inner
+            .ensure_references::<T::AccountId, Self>(&details.owner)
+            .map_err::<Error<T>, _>(Into::into)?;
+        };
+        Self::update_fa_references(details.lootbox.as_ref(), lootbox.as_ref())?;
+        details.lootbox = lootbox;
+      },
+      Characteristic::Salvage(salvage) => {
+        if let Some(inner) = &salvage {
+          AssetCharacteristic::ensure(inner)
+            .map_err::<Error<T>, _>(Into::into)
+            .map_err::<DispatchError, _>(Into::into)?;
+          inner
+            .ensure_references::<T::AccountId, Self>(&details.owner)
+            .map_err::<Error<T>, _>(Into::into)?;
+        };
+        Self::update_fa_references(details.salvage.as_ref(), salvage.as_ref())?;
+        details.salvage = salvage;
+      },
+      Characteristic::Royalty(royalty) => {
+        if let Some(inner) = &royalty {
+          AssetCharacteristic::ensure(inner)
+            .map_err::<Error<T>, _>(Into::into)
+            .map_err::<DispatchError, _>(Into::into)?;
+        };
+        details.royalty = royalty;
+      },
     };
 
     Classes::<T>::insert(class_id, &details);
@@ -219,7 +617,95 @@ impl<T: Config> Pallet<T> {
     Ok(())
   }
 
-  /// Set an asset lock
+  /// Moves references to fungible assets from the `old` characteristic to the `new` one.  \
+  /// Referenced assets can't be destroyed while a class uses them
+  fn update_fa_references<C: AssetCharacteristic>(
+    old: Option<&C>,
+    new: Option<&C>,
+  ) -> DispatchResult {
+    for asset_id in old.map(|c| c.fa_references()).unwrap_or_default() {
+      T::FungibleAssets::dec_references(&asset_id)?;
+    }
+    for asset_id in new.map(|c| c.fa_references()).unwrap_or_default() {
+      T::FungibleAssets::inc_references(&asset_id)?;
+    }
+    Ok(())
+  }
+
+  /// Approves the asset to be used by the `delegate`.  \
+  /// Replaces the previous approval if any
+  pub(crate) fn do_approve(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    owner: &T::AccountId,
+    delegate: T::AccountId,
+    maybe_deadline: Option<T::BlockNumber>,
+  ) -> DispatchResult {
+    let details = Assets::<T>::get(class_id, asset_id).ok_or(Error::<T>::UnknownAsset)?;
+    ensure!(&details.owner == owner, Error::<T>::NoPermission);
+
+    Approvals::<T>::insert(
+      class_id,
+      asset_id,
+      Approval {
+        delegate: delegate.clone(),
+        deadline: maybe_deadline,
+      },
+    );
+    Self::deposit_event(Event::ApprovedTransfer {
+      class_id,
+      asset_id,
+      owner: details.owner,
+      delegate,
+      deadline: maybe_deadline,
+    });
+    Ok(())
+  }
+
+  /// Cancels the approval of the asset
+  pub(crate) fn do_cancel_approval(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    owner: &T::AccountId,
+  ) -> DispatchResult {
+    let details = Assets::<T>::get(class_id, asset_id).ok_or(Error::<T>::UnknownAsset)?;
+    ensure!(&details.owner == owner, Error::<T>::NoPermission);
+    let approval = Approvals::<T>::take(class_id, asset_id).ok_or(Error::<T>::NotApproved)?;
+
+    Self::deposit_event(Event::ApprovalCancelled {
+      class_id,
+      asset_id,
+      owner: details.owner,
+      delegate: approval.delegate,
+    });
+    Ok(())
+  }
+
+  /// Checks that the `who` is the owner of the asset or its approved delegate.  \
+  /// Expired approvals are not taken into account
+  pub(crate) fn ensure_owner_or_delegate(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    details: &AssetDetails<T::AccountId, T::Index>,
+    who: &T::AccountId,
+  ) -> DispatchResult {
+    if &details.owner == who {
+      return Ok(());
+    }
+    let approval = Approvals::<T>::get(class_id, asset_id).ok_or(Error::<T>::NoPermission)?;
+    ensure!(&approval.delegate == who, Error::<T>::NoPermission);
+    if let Some(deadline) = approval.deadline {
+      ensure!(
+        frame_system::Pallet::<T>::block_number() <= deadline,
+        Error::<T>::ApprovalExpired
+      );
+    }
+    Ok(())
+  }
+
+  /// Set an asset lock.  \
+  /// The approved delegate can lock the asset too. Mechanics which burn the asset or pay out to the
+  /// gamer accept only the locks placed by the owner (see `LockResult`)
   pub(crate) fn set_lock(
     who: &AccountIdOf<T>,
     origin: Locker<AccountIdOf<T>, IndexOf<T>>,
@@ -231,7 +717,7 @@ impl<T: Config> Pallet<T> {
 
     let mut details = Assets::<T>::get(class_id, asset_id).ok_or(Error::<T>::UnknownAsset)?;
     // ownership check
-    ensure!(&details.owner == who, Error::<T>::NoPermission);
+    Self::ensure_owner_or_delegate(class_id, asset_id, &details, who)?;
 
     match details.locked {
       Locker::None => {
@@ -251,12 +737,355 @@ impl<T: Config> Pallet<T> {
     asset_id: &NonFungibleAssetId,
   ) -> DispatchResult {
     if let Some(mut details) = Assets::<T>::get(class_id, asset_id) {
-      // ownership check
-      ensure!(&details.owner == who, Error::<T>::NoPermission);
+      // ownership check. The account which has placed the lock is able to remove it
+      let is_locker = matches!(origin, Locker::Mechanic(id) if &id.gamer_account.account_id == who);
+      if !is_locker {
+        Self::ensure_owner_or_delegate(class_id, asset_id, &details, who)?;
+      }
       ensure!(&details.locked == origin, Error::<T>::NoPermission);
       details.locked = Locker::None;
       Assets::<T>::insert(class_id, asset_id, details);
     }
     Ok(())
   }
+
+  /// Sets metadata of the asset class.
+  pub fn do_set_class_metadata(
+    class_id: NonFungibleClassId,
+    maybe_check_owner: Option<T::AccountId>,
+    data: Vec<u8>,
+  ) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    let data: Metadata = data.try_into().map_err(|_| Error::<T>::MetadataTooLong)?;
+
+    ClassMetadataOf::<T>::insert(class_id, &data);
+    Self::deposit_event(Event::ClassMetadataSet { class_id, data });
+    Ok(())
+  }
+
+  /// Clears metadata of the asset class.
+  pub fn do_clear_class_metadata(
+    class_id: NonFungibleClassId,
+    maybe_check_owner: Option<T::AccountId>,
+  ) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    if ClassMetadataOf::<T>::take(class_id).is_some() {
+      Self::deposit_event(Event::ClassMetadataCleared { class_id });
+    }
+    Ok(())
+  }
+
+  /// Sets metadata of the asset instance.
+  pub fn do_set_asset_metadata(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    maybe_check_owner: Option<T::AccountId>,
+    data: Vec<u8>,
+  ) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    ensure!(
+      Assets::<T>::contains_key(class_id, asset_id),
+      Error::<T>::UnknownAsset
+    );
+    let data: Metadata = data.try_into().map_err(|_| Error::<T>::MetadataTooLong)?;
+
+    AssetMetadataOf::<T>::insert(class_id, asset_id, &data);
+    Self::deposit_event(Event::AssetMetadataSet {
+      class_id,
+      asset_id,
+      data,
+    });
+    Ok(())
+  }
+
+  /// Clears metadata of the asset instance.
+  pub fn do_clear_asset_metadata(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    maybe_check_owner: Option<T::AccountId>,
+  ) -> DispatchResult {
+    let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
+    if let Some(check_owner) = maybe_check_owner {
+      ensure!(details.owner == check_owner, Error::<T>::NoPermission);
+    }
+    if AssetMetadataOf::<T>::take(class_id, asset_id).is_some() {
+      Self::deposit_event(Event::AssetMetadataCleared { class_id, asset_id });
+    }
+    Ok(())
+  }
+
+  /// Returns metadata of the asset class
+  pub fn class_metadata(class_id: &NonFungibleClassId) -> Option<Vec<u8>> {
+    ClassMetadataOf::<T>::get(class_id).map(|data| data.into_inner())
+  }
+
+  /// Returns metadata of the asset instance.  \
+  /// If the instance has no own metadata, the metadata of its class is returned.
+  /// Returns `None` if the instance doesn't exist
+  pub fn asset_metadata(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+  ) -> Option<Vec<u8>> {
+    if !Assets::<T>::contains_key(class_id, asset_id) {
+      return None;
+    }
+    AssetMetadataOf::<T>::get(class_id, asset_id)
+      .map(|data| data.into_inner())
+      .or_else(|| Self::class_metadata(class_id))
+  }
+
+  /// Returns the assets held by the account, optionally only the assets of the given class.  \
+  /// Skips `offset` assets and returns at most `limit` of them (capped by `QUERY_PAGE_SIZE_LIMIT`)
+  pub fn account_assets(
+    account: &T::AccountId,
+    maybe_class_id: Option<NonFungibleClassId>,
+    offset: u32,
+    limit: u32,
+  ) -> Vec<(NonFungibleClassId, NonFungibleAssetId)> {
+    let limit = limit.min(QUERY_PAGE_SIZE_LIMIT) as usize;
+    match maybe_class_id {
+      Some(class_id) => Accounts::<T>::iter_key_prefix((account.clone(), class_id))
+        .skip(offset as usize)
+        .take(limit)
+        .map(|asset_id| (class_id, asset_id))
+        .collect(),
+      None => Accounts::<T>::iter_key_prefix((account.clone(),))
+        .skip(offset as usize)
+        .take(limit)
+        .collect(),
+    }
+  }
+
+  /// Returns details of the asset instance, including its lock status
+  pub fn asset_details(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+  ) -> Option<AssetDetails<T::AccountId, T::Index>> {
+    Assets::<T>::get(class_id, asset_id)
+  }
+
+  /// Returns attributes of the asset instance together with the attributes of its class
+  /// which the instance doesn't override.  \
+  /// Skips `offset` attributes and returns at most `limit` of them (capped by `QUERY_PAGE_SIZE_LIMIT`)
+  pub fn asset_attributes(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    offset: u32,
+    limit: u32,
+  ) -> Vec<Attribute> {
+    if !Assets::<T>::contains_key(class_id, asset_id) {
+      return Vec::new();
+    }
+    let limit = limit.min(QUERY_PAGE_SIZE_LIMIT) as usize;
+    Attributes::<T>::iter_prefix(asset_id)
+      .chain(
+        ClassAttributes::<T>::iter_prefix(class_id)
+          .filter(|(key, _)| !Attributes::<T>::contains_key(asset_id, key)),
+      )
+      .skip(offset as usize)
+      .take(limit)
+      .map(|(key, value)| Attribute { key, value })
+      .collect()
+  }
+
+  /// Returns details of the asset class including its characteristics
+  pub fn class_details(class_id: &NonFungibleClassId) -> Option<ClassDetailsOf<T>> {
+    Classes::<T>::get(class_id)
+  }
+
+  /// Returns the beneficiary and the amount of the royalty paid when an asset of the class
+  /// is sold for the `price`.  \
+  /// Returns `None` if the class doesn't exist or has no royalty
+  pub fn royalty_of(
+    class_id: &NonFungibleClassId,
+    price: FungibleAssetBalance,
+  ) -> Option<(RoyaltyBeneficiary<T::AccountId>, FungibleAssetBalance)> {
+    let royalty = Classes::<T>::get(class_id)?.royalty?;
+    let amount = royalty.amount(price);
+    Some((royalty.beneficiary, amount))
+  }
+
+  /// Equips the asset to the parent asset. Both assets must be owned by `who`
+  pub(crate) fn do_equip(
+    who: &T::AccountId,
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+    parent_class_id: NonFungibleClassId,
+    parent_asset_id: NonFungibleAssetId,
+  ) -> DispatchResult {
+    let mut details = Assets::<T>::get(class_id, asset_id).ok_or(Error::<T>::UnknownAsset)?;
+    let parent_details =
+      Assets::<T>::get(parent_class_id, parent_asset_id).ok_or(Error::<T>::UnknownAsset)?;
+    ensure!(
+      &details.owner == who && &parent_details.owner == who,
+      Error::<T>::NoPermission
+    );
+    // equipped and locked assets can't be equipped
+    ensure!(details.locked == Locker::None, Error::<T>::Locked);
+
+    let (root_class_id, root_asset_id) = Self::root_of(parent_class_id, parent_asset_id);
+    ensure!(
+      (root_class_id, root_asset_id) != (class_id, asset_id),
+      Error::<T>::NestingLoop
+    );
+    // the tree of the parent must not be used in mechanics
+    let root_details =
+      Assets::<T>::get(root_class_id, root_asset_id).ok_or(Error::<T>::UnknownAsset)?;
+    ensure!(root_details.locked == Locker::None, Error::<T>::Locked);
+
+    let depth = Self::depth_of(parent_class_id, parent_asset_id)
+      .saturating_add(1)
+      .saturating_add(Self::height_of(class_id, asset_id));
+    ensure!(
+      depth <= T::MaxNestingDepth::get(),
+      Error::<T>::NestingTooDeep
+    );
+    let children = Children::<T>::iter_key_prefix((parent_class_id, parent_asset_id)).count();
+    ensure!(
+      children < T::MaxChildren::get() as usize,
+      Error::<T>::TooManyChildren
+    );
+
+    details.locked = Locker::Nfa(parent_class_id, parent_asset_id);
+    Assets::<T>::insert(class_id, asset_id, details);
+    Children::<T>::insert((parent_class_id, parent_asset_id, class_id, asset_id), ());
+
+    Self::deposit_event(Event::Equipped {
+      class_id,
+      asset_id,
+      parent_class_id,
+      parent_asset_id,
+    });
+    Ok(())
+  }
+
+  /// Unequips the asset from its parent
+  pub(crate) fn do_unequip(
+    who: &T::AccountId,
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+  ) -> DispatchResult {
+    let mut details = Assets::<T>::get(class_id, asset_id).ok_or(Error::<T>::UnknownAsset)?;
+    ensure!(&details.owner == who, Error::<T>::NoPermission);
+    let (parent_class_id, parent_asset_id) = match details.locked {
+      Locker::Nfa(parent_class_id, parent_asset_id) => (parent_class_id, parent_asset_id),
+      _ => return Err(Error::<T>::NotEquipped.into()),
+    };
+    // the tree of the parent must not be used in mechanics
+    let (root_class_id, root_asset_id) = Self::root_of(parent_class_id, parent_asset_id);
+    let root_details =
+      Assets::<T>::get(root_class_id, root_asset_id).ok_or(Error::<T>::UnknownAsset)?;
+    ensure!(root_details.locked == Locker::None, Error::<T>::Locked);
+
+    details.locked = Locker::None;
+    Assets::<T>::insert(class_id, asset_id, details);
+    Children::<T>::remove((parent_class_id, parent_asset_id, class_id, asset_id));
+
+    Self::deposit_event(Event::Unequipped {
+      class_id,
+      asset_id,
+      parent_class_id,
+      parent_asset_id,
+    });
+    Ok(())
+  }
+
+  /// Unequips all assets equipped directly to the asset. They stay owned by the owner of the asset
+  fn release_children(class_id: NonFungibleClassId, asset_id: NonFungibleAssetId) {
+    let children: Vec<_> = Children::<T>::drain_prefix((class_id, asset_id))
+      .map(|(key, _)| key)
+      .collect();
+    for (child_class_id, child_asset_id) in children {
+      Assets::<T>::mutate(child_class_id, child_asset_id, |maybe_child| {
+        if let Some(child) = maybe_child {
+          child.locked = Locker::None;
+        }
+      });
+      Self::deposit_event(Event::Unequipped {
+        class_id: child_class_id,
+        asset_id: child_asset_id,
+        parent_class_id: class_id,
+        parent_asset_id: asset_id,
+      });
+    }
+  }
+
+  /// Returns the parent of the asset if the asset is equipped
+  pub fn parent_of(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+  ) -> Option<(NonFungibleClassId, NonFungibleAssetId)> {
+    match Assets::<T>::get(class_id, asset_id)?.locked {
+      Locker::Nfa(parent_class_id, parent_asset_id) => Some((parent_class_id, parent_asset_id)),
+      _ => None,
+    }
+  }
+
+  /// Resolves the top-level asset of the nesting tree. Returns the asset itself if it isn't equipped
+  pub fn root_of(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+  ) -> (NonFungibleClassId, NonFungibleAssetId) {
+    let mut current = (class_id, asset_id);
+    while let Some(parent) = Self::parent_of(current.0, current.1) {
+      current = parent;
+    }
+    current
+  }
+
+  /// Resolves the account owning the asset through the nesting tree
+  pub fn owner_of(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+  ) -> Option<T::AccountId> {
+    let (root_class_id, root_asset_id) = Self::root_of(class_id, asset_id);
+    Assets::<T>::get(root_class_id, root_asset_id).map(|details| details.owner)
+  }
+
+  /// Returns the number of ancestors of the asset
+  fn depth_of(class_id: NonFungibleClassId, asset_id: NonFungibleAssetId) -> u32 {
+    let mut depth = 0u32;
+    let mut current = (class_id, asset_id);
+    while let Some(parent) = Self::parent_of(current.0, current.1) {
+      depth = depth.saturating_add(1);
+      current = parent;
+    }
+    depth
+  }
+
+  /// Returns the number of levels of assets nested into the asset
+  fn height_of(class_id: NonFungibleClassId, asset_id: NonFungibleAssetId) -> u32 {
+    Children::<T>::iter_key_prefix((class_id, asset_id))
+      .map(|(child_class_id, child_asset_id)| {
+        Self::height_of(child_class_id, child_asset_id).saturating_add(1)
+      })
+      .max()
+      .unwrap_or_default()
+  }
+
+  /// Returns all assets nested into the asset at any level
+  pub fn descendants_of(
+    class_id: NonFungibleClassId,
+    asset_id: NonFungibleAssetId,
+  ) -> Vec<(NonFungibleClassId, NonFungibleAssetId)> {
+    let mut descendants = Vec::new();
+    let mut queue: Vec<_> = Children::<T>::iter_key_prefix((class_id, asset_id)).collect();
+    while let Some((child_class_id, child_asset_id)) = queue.pop() {
+      queue.extend(Children::<T>::iter_key_prefix((
+        child_class_id,
+        child_asset_id,
+      )));
+      descendants.push((child_class_id, child_asset_id));
+    }
+    descendants
+  }
 }
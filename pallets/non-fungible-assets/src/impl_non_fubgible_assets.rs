@@ -1,4 +1,4 @@
-use pallet_support::Locker;
+use pallet_support::{purchased::Offer, Locker};
 
 use super::*;
 
@@ -20,10 +20,7 @@ impl<T: pallet::Config> pallet_support::traits::NonFungibleAssets<AccountIdOf<T>
     Self::do_burn(class_id, asset_id, maybe_check_owner)
   }
 
-  fn get_offer(
-    class_id: &NonFungibleClassId,
-    offer_id: &u32,
-  ) -> DispatchResultAs<(FungibleAssetId, FungibleAssetBalance, AttributeList)> {
+  fn get_offer(class_id: &NonFungibleClassId, offer_id: &u32) -> DispatchResultAs<Offer> {
     let details = Classes::<T>::get(class_id).ok_or(Error::<T>::UnknownClass)?;
     if let Some(purchased) = details.purchased {
       if let Some(offer) = purchased
@@ -31,7 +28,7 @@ impl<T: pallet::Config> pallet_support::traits::NonFungibleAssets<AccountIdOf<T>
         .into_inner()
         .get(usize::try_from(*offer_id).map_err(|_| Error::<T>::WrongParameter)?)
       {
-        Ok((offer.fa, offer.price, offer.attributes.clone()))
+        Ok(offer.clone())
       } else {
         Err(Error::<T>::WrongParameter.into())
       }
@@ -40,8 +37,19 @@ impl<T: pallet::Config> pallet_support::traits::NonFungibleAssets<AccountIdOf<T>
     }
   }
 
-  fn set_attributes(asset_id: &NonFungibleAssetId, attributes: AttributeList) -> DispatchResult {
-    Self::assign_attributes(asset_id, attributes)
+  fn set_attributes(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    attributes: AttributeList,
+  ) -> DispatchResult {
+    Self::set_attributes(class_id, asset_id, attributes)
+  }
+
+  fn validate_attributes(
+    class_id: &NonFungibleClassId,
+    attributes: &AttributeList,
+  ) -> DispatchResult {
+    Self::validate_attributes(class_id, attributes)
   }
 
   fn try_lock(
@@ -65,4 +73,32 @@ impl<T: pallet::Config> pallet_support::traits::NonFungibleAssets<AccountIdOf<T>
   fn get_class(class_id: &NonFungibleClassId) -> DispatchResultAs<ClassDetailsOf<T>> {
     Self::get_class_details(class_id)
   }
+
+  fn get_asset(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+  ) -> DispatchResultAs<AssetDetails<AccountIdOf<T>, IndexOf<T>>> {
+    Ok(Assets::<T>::get(class_id, asset_id).ok_or(Error::<T>::UnknownAsset)?)
+  }
+
+  fn get_attribute(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    key: &AttributeKey,
+  ) -> Option<AttributeValue> {
+    Self::attribute_of(class_id, asset_id, key)
+  }
+
+  fn transfer(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    maybe_check_owner: Option<&AccountIdOf<T>>,
+    dest: &AccountIdOf<T>,
+  ) -> DispatchResult {
+    Self::do_transfer(*class_id, *asset_id, maybe_check_owner, dest).map(|_| ())
+  }
+
+  fn ensure_transferable(class_id: &NonFungibleClassId) -> DispatchResult {
+    Self::ensure_transferable(class_id)
+  }
 }
@@ -0,0 +1,13 @@
+use pallet_support::{traits::FungibleAssets, FungibleAssetId, ValidationContext};
+
+use super::*;
+
+impl<T: pallet::Config> ValidationContext<AccountIdOf<T>> for Pallet<T> {
+  fn fa_owner(asset_id: &FungibleAssetId) -> Option<AccountIdOf<T>> {
+    T::FungibleAssets::owner_of(asset_id)
+  }
+
+  fn nfa_class_owner(class_id: &NonFungibleClassId) -> Option<AccountIdOf<T>> {
+    Classes::<T>::get(class_id).map(|details| details.owner)
+  }
+}
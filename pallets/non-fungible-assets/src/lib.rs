@@ -2,6 +2,8 @@
 
 mod functions;
 mod impl_non_fubgible_assets;
+mod impl_validation_context;
+pub mod migration;
 mod types;
 
 pub use types::*;
@@ -9,8 +11,8 @@ pub use types::*;
 pub use pallet_support::{
   self, purchased,
   types_nfa::{AssetDetails, ClassDetails},
-  AccountIdOf, Attribute, AttributeKey, AttributeList, AttributeValue, Characteristic,
-  DispatchResultAs, IndexOf, LockResultOf, NumberAttribute,
+  AccountIdOf, Attribute, AttributeKey, AttributeList, AttributeSchema, AttributeTemplate,
+  AttributeValue, Characteristic, DispatchResultAs, IndexOf, LockResultOf, NumberAttribute,
 };
 
 pub use pallet::*;
@@ -24,7 +26,7 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-use frame_support::traits::EnsureOriginWithArg;
+use frame_support::traits::{EnsureOriginWithArg, Randomness};
 use sp_runtime::{
   traits::{Saturating, StaticLookup, Zero},
   ArithmeticError,
@@ -39,8 +41,12 @@ pub mod pallet {
   use super::*;
   use pallet_support::{CommonError, Index};
 
+  /// The current storage version.
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
   #[pallet::pallet]
   #[pallet::generate_store(pub(super) trait Store)]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
   /// Configure the pallet by specifying the parameters and types on which it depends.
@@ -53,6 +59,15 @@ pub mod pallet {
     type CreateOrigin: EnsureOriginWithArg<Self::Origin, Self::AccountId>;
     /// Connector to fungible assets instances
     type FungibleAssets: pallet_support::traits::FungibleAssets<Self::AccountId>;
+    /// The maximum number of levels of assets nested into the top-level asset.
+    #[pallet::constant]
+    type MaxNestingDepth: Get<u32>;
+    /// The maximum number of assets which can be equipped directly to an asset.
+    #[pallet::constant]
+    type MaxChildren: Get<u32>;
+    /// Something that provides randomness in the runtime.
+    /// Used to roll attributes of the minted assets.
+    type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
   }
 
   #[pallet::storage]
@@ -72,6 +87,11 @@ pub mod pallet {
     OptionQuery,
   >;
 
+  #[pallet::storage]
+  /// Organizations to which the owners of classes offered to transfer ownership of the classes.
+  pub(super) type OwnershipOffers<T: Config> =
+    StorageMap<_, Blake2_128Concat, NonFungibleClassId, T::AccountId, OptionQuery>;
+
   #[pallet::storage]
   /// The assets held by any given account.
   pub(super) type Accounts<T: Config> = StorageNMap<
@@ -121,6 +141,75 @@ pub mod pallet {
     OptionQuery,
   >;
 
+  #[pallet::storage]
+  /// Templates of attributes rolled randomly for every minted asset of the class.
+  pub(super) type AttributeTemplates<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    NonFungibleClassId,
+    Blake2_128Concat,
+    AttributeKey,
+    AttributeTemplate,
+    OptionQuery,
+  >;
+
+  #[pallet::storage]
+  /// Schemas of attributes of an asset class.
+  ///
+  /// If a class has any schema, only the attributes described by schemas are allowed.
+  pub(super) type AttributeSchemas<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    NonFungibleClassId,
+    Blake2_128Concat,
+    AttributeKey,
+    AttributeSchema,
+    OptionQuery,
+  >;
+
+  #[pallet::storage]
+  /// Metadata of an asset class.
+  pub(super) type ClassMetadataOf<T: Config> =
+    StorageMap<_, Blake2_128Concat, NonFungibleClassId, Metadata, OptionQuery>;
+
+  #[pallet::storage]
+  /// Metadata of an asset instance. Overrides the metadata of the asset class.
+  pub(super) type AssetMetadataOf<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    NonFungibleClassId,
+    Blake2_128Concat,
+    NonFungibleAssetId,
+    Metadata,
+    OptionQuery,
+  >;
+
+  #[pallet::storage]
+  /// Approvals of assets owners to use the assets by delegates.
+  pub(super) type Approvals<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    NonFungibleClassId,
+    Blake2_128Concat,
+    NonFungibleAssetId,
+    ApprovalOf<T>,
+    OptionQuery,
+  >;
+
+  #[pallet::storage]
+  /// The assets equipped to (nested into) the parent asset.
+  pub(super) type Children<T: Config> = StorageNMap<
+    _,
+    (
+      NMapKey<Blake2_128Concat, NonFungibleClassId>,
+      NMapKey<Blake2_128Concat, NonFungibleAssetId>,
+      NMapKey<Blake2_128Concat, NonFungibleClassId>,
+      NMapKey<Blake2_128Concat, NonFungibleAssetId>,
+    ),
+    (),
+    OptionQuery,
+  >;
+
   #[pallet::storage]
   /// Storing the next asset id
   pub type NextAssetId<T: Config> = StorageValue<_, NonFungibleAssetId, ValueQuery>;
@@ -143,6 +232,23 @@ pub mod pallet {
     Destroyed { class_id: NonFungibleClassId },
     /// An asset class has been updated.
     Updated { class_id: NonFungibleClassId },
+    /// The owner of the asset class offered to transfer the ownership to another organization.
+    OwnershipOffered {
+      class_id: NonFungibleClassId,
+      owner: T::AccountId,
+      new_owner: T::AccountId,
+    },
+    /// The offer to transfer the ownership of the asset class was cancelled.
+    OwnershipOfferCancelled {
+      class_id: NonFungibleClassId,
+      owner: T::AccountId,
+    },
+    /// The ownership of the asset class was transferred to another organization.
+    OwnerChanged {
+      class_id: NonFungibleClassId,
+      old_owner: T::AccountId,
+      new_owner: T::AccountId,
+    },
     /// An asset `instance` has been issued.
     Issued {
       class_id: NonFungibleClassId,
@@ -160,12 +266,88 @@ pub mod pallet {
       class_id: NonFungibleClassId,
       key: AttributeKey,
     },
+    /// An attribute template has been set for the asset class.
+    AttributeTemplateSet {
+      class_id: NonFungibleClassId,
+      key: AttributeKey,
+      template: AttributeTemplate,
+    },
+    /// An attribute template has been removed from the asset class.
+    AttributeTemplateRemoved {
+      class_id: NonFungibleClassId,
+      key: AttributeKey,
+    },
+    /// An attribute schema has been set for the asset class.
+    AttributeSchemaSet {
+      class_id: NonFungibleClassId,
+      key: AttributeKey,
+      schema: AttributeSchema,
+    },
+    /// An attribute schema has been removed from the asset class.
+    AttributeSchemaRemoved {
+      class_id: NonFungibleClassId,
+      key: AttributeKey,
+    },
+    /// An asset `instance` was transferred.
+    Transferred {
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      from: T::AccountId,
+      to: T::AccountId,
+    },
+    /// An asset `instance` has been approved for use by a delegate.
+    ApprovedTransfer {
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      owner: T::AccountId,
+      delegate: T::AccountId,
+      deadline: Option<T::BlockNumber>,
+    },
+    /// An approval for a delegate to use an asset `instance` was cancelled by its owner.
+    ApprovalCancelled {
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      owner: T::AccountId,
+      delegate: T::AccountId,
+    },
+    /// An asset `instance` has been equipped to the parent asset.
+    Equipped {
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      parent_class_id: NonFungibleClassId,
+      parent_asset_id: NonFungibleAssetId,
+    },
+    /// An asset `instance` has been unequipped from the parent asset.
+    Unequipped {
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      parent_class_id: NonFungibleClassId,
+      parent_asset_id: NonFungibleAssetId,
+    },
     /// An asset `instance` was destroyed.
     Burned {
       class_id: NonFungibleClassId,
       asset_id: NonFungibleAssetId,
       owner: T::AccountId,
     },
+    /// New metadata has been set for the asset class.
+    ClassMetadataSet {
+      class_id: NonFungibleClassId,
+      data: Metadata,
+    },
+    /// Metadata has been cleared for the asset class.
+    ClassMetadataCleared { class_id: NonFungibleClassId },
+    /// New metadata has been set for the asset instance.
+    AssetMetadataSet {
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+      data: Metadata,
+    },
+    /// Metadata has been cleared for the asset instance.
+    AssetMetadataCleared {
+      class_id: NonFungibleClassId,
+      asset_id: NonFungibleAssetId,
+    },
     /// Event documentation should end with an array that provides descriptive names for event
     /// parameters. [something, who]
     SomethingStored(u32, T::AccountId),
@@ -194,6 +376,14 @@ pub mod pallet {
     WrongBettor,
     /// The purchased characteristic is wrong.
     WrongPurchased,
+    /// The upgradable characteristic is wrong.
+    WrongUpgradable,
+    /// The lootbox characteristic is wrong.
+    WrongLootbox,
+    /// The salvage characteristic is wrong.
+    WrongSalvage,
+    /// The royalty characteristic is wrong.
+    WrongRoyalty,
     /// Attribute value not supported
     AttributeConversionError,
     /// Attribute numeric value exceeds maximum value
@@ -210,6 +400,44 @@ pub mod pallet {
     WrongCharacteristic,
     /// The asset instance is locked
     Locked,
+    /// Metadata is too long.
+    MetadataTooLong,
+    /// Assets of the class are soulbound and can't change hands
+    Soulbound,
+    /// The approval of the delegate is expired
+    ApprovalExpired,
+    /// The asset has no approval to cancel
+    NotApproved,
+    /// The asset can't be equipped to itself or to one of its children
+    NestingLoop,
+    /// The nesting depth of assets exceeds the limit
+    NestingTooDeep,
+    /// The parent asset has the maximum number of equipped assets
+    TooManyChildren,
+    /// The asset isn't equipped to any asset
+    NotEquipped,
+    /// The asset has more equipped assets than the transfer is weighted for
+    TooManyDescendants,
+    /// The attribute template is invalid
+    WrongAttributeTemplate,
+    /// The class has the maximum number of attribute templates
+    TooManyAttributeTemplates,
+    /// The given attribute template is unknown
+    UnknownAttributeTemplate,
+    /// The attribute schema is invalid
+    WrongAttributeSchema,
+    /// The class has the maximum number of attribute schemas
+    TooManyAttributeSchemas,
+    /// The given attribute schema is unknown
+    UnknownAttributeSchema,
+    /// The attribute isn't described by the attribute schema of the class
+    AttributeNotInSchema,
+    /// The attribute value doesn't meet the attribute schema of the class
+    AttributeSchemaMismatch,
+    /// The attribute required by the attribute schema of the class is missing
+    RequiredAttributeMissing,
+    /// The class has no offer to transfer the ownership to the organization
+    NoOwnershipOffer,
     /// The common error
     CommonError(CommonError),
   }
@@ -219,6 +447,10 @@ pub mod pallet {
       match t {
         CommonError::WrongBettor => Error::<T>::WrongBettor,
         CommonError::WrongPurchased => Error::<T>::WrongPurchased,
+        CommonError::WrongUpgradable => Error::<T>::WrongUpgradable,
+        CommonError::WrongLootbox => Error::<T>::WrongLootbox,
+        CommonError::WrongSalvage => Error::<T>::WrongSalvage,
+        CommonError::WrongRoyalty => Error::<T>::WrongRoyalty,
         _ => Error::<T>::CommonError(t),
       }
     }
@@ -279,6 +511,74 @@ pub mod pallet {
       Ok(())
     }
 
+    /// Offers to transfer the ownership of the asset class to another organization.
+    /// The ownership is transferred when the new organization accepts the offer.
+    /// Any previous offer of the class is replaced.
+    ///
+    /// The origin must be Signed and must be a member of the organization which owns the class.
+    ///
+    /// Emits `OwnershipOffered` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+    pub fn offer_ownership(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      new_owner: <T::Lookup as StaticLookup>::Source,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+      let new_owner = T::Lookup::lookup(new_owner)?;
+
+      Self::do_offer_ownership(class_id, Some(owner), new_owner)?;
+
+      Ok(())
+    }
+
+    /// Cancels the offer to transfer the ownership of the asset class.
+    ///
+    /// The origin must be Signed and must be a member of the organization which owns the class.
+    ///
+    /// Emits `OwnershipOfferCancelled` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+    pub fn cancel_ownership_offer(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_cancel_ownership_offer(class_id, Some(owner))?;
+
+      Ok(())
+    }
+
+    /// Accepts the offer to transfer the ownership of the asset class.
+    ///
+    /// The origin must be Signed and must be a member of the organization
+    /// to which the ownership was offered.
+    ///
+    /// Emits `OwnerChanged` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(2, 4))]
+    pub fn accept_ownership(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+    ) -> DispatchResult {
+      // new owner of a class must be an orgnization
+      let new_owner = T::Lookup::lookup(organization_id)?;
+      // Only member of the organization can accept the offer
+      T::CreateOrigin::ensure_origin(origin, &new_owner)?;
+
+      Self::do_accept_ownership(class_id, new_owner)?;
+
+      Ok(())
+    }
+
     /// Creates an attribute for the non fungible asset class.
     /// The origin must be Signed, be a member of the organization
     /// and that organization must be a owner of the asset class.
@@ -319,12 +619,110 @@ pub mod pallet {
       Ok(())
     }
 
+    /// Sets an attribute template for the non fungible asset class.
+    /// Every asset minted afterwards gets the attribute with a value rolled randomly by the template.
+    /// The origin must be Signed, be a member of the organization
+    /// and that organization must be a owner of the asset class.
+    ///
+    /// Emits `AttributeTemplateSet` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      2 + pallet_support::AttributeListLengthLimit::get() as u64,
+      1
+    ))]
+    pub fn set_attribute_template(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      key: AttributeKey,
+      template: AttributeTemplate,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_set_attribute_template(class_id, Some(owner), key, template)?;
+
+      Ok(())
+    }
+
+    /// Removes an attribute template from the non fungible asset class.
+    /// The origin must be Signed, be a member of the organization
+    /// and that organization must be a owner of the asset class.
+    ///
+    /// Emits `AttributeTemplateRemoved` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+    pub fn remove_attribute_template(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      key: AttributeKey,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_remove_attribute_template(class_id, Some(owner), key)?;
+
+      Ok(())
+    }
+
+    /// Sets an attribute schema for the non fungible asset class.
+    /// Once a class has a schema, attributes of its assets are validated against it.
+    /// The origin must be Signed, be a member of the organization
+    /// and that organization must be a owner of the asset class.
+    ///
+    /// Emits `AttributeSchemaSet` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(
+      2 + pallet_support::AttributeListLengthLimit::get() as u64,
+      1
+    ))]
+    pub fn set_attribute_schema(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      key: AttributeKey,
+      schema: AttributeSchema,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_set_attribute_schema(class_id, Some(owner), key, schema)?;
+
+      Ok(())
+    }
+
+    /// Removes an attribute schema from the non fungible asset class.
+    /// The origin must be Signed, be a member of the organization
+    /// and that organization must be a owner of the asset class.
+    ///
+    /// Emits `AttributeSchemaRemoved` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+    pub fn remove_attribute_schema(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      key: AttributeKey,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_remove_attribute_schema(class_id, Some(owner), key)?;
+
+      Ok(())
+    }
+
     #[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
     pub fn set_characteristic(
       origin: OriginFor<T>,
       organization_id: <T::Lookup as StaticLookup>::Source,
       #[pallet::compact] class_id: NonFungibleClassId,
-      characteristic: Characteristic,
+      characteristic: Characteristic<T::AccountId>,
     ) -> DispatchResult {
       // owner of a class must be an orgnization
       let owner = T::Lookup::lookup(organization_id)?;
@@ -335,5 +733,206 @@ pub mod pallet {
 
       Ok(())
     }
+
+    /// Sets the metadata of the non fungible asset class.
+    /// The origin must be Signed, be a member of the organization
+    /// and that organization must be a owner of the asset class.
+    ///
+    /// Emits `ClassMetadataSet` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+    pub fn set_class_metadata(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      data: Vec<u8>,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_set_class_metadata(class_id, Some(owner), data)?;
+
+      Ok(())
+    }
+
+    /// Clears the metadata of the non fungible asset class.
+    /// The origin must be Signed, be a member of the organization
+    /// and that organization must be a owner of the asset class.
+    ///
+    /// Emits `ClassMetadataCleared` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+    pub fn clear_class_metadata(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_clear_class_metadata(class_id, Some(owner))?;
+
+      Ok(())
+    }
+
+    /// Sets the metadata of the asset instance, which overrides the metadata of its class.
+    /// The origin must be Signed, be a member of the organization
+    /// and that organization must be a owner of the asset class.
+    ///
+    /// Emits `AssetMetadataSet` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+    pub fn set_asset_metadata(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      #[pallet::compact] asset_id: NonFungibleAssetId,
+      data: Vec<u8>,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_set_asset_metadata(class_id, asset_id, Some(owner), data)?;
+
+      Ok(())
+    }
+
+    /// Clears the metadata of the asset instance.
+    /// The origin must be Signed, be a member of the organization
+    /// and that organization must be a owner of the asset class.
+    ///
+    /// Emits `AssetMetadataCleared` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+    pub fn clear_asset_metadata(
+      origin: OriginFor<T>,
+      organization_id: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      #[pallet::compact] asset_id: NonFungibleAssetId,
+    ) -> DispatchResult {
+      // owner of a class must be an orgnization
+      let owner = T::Lookup::lookup(organization_id)?;
+      // Only organization can manage an asset class
+      T::CreateOrigin::ensure_origin(origin, &owner)?;
+
+      Self::do_clear_asset_metadata(class_id, asset_id, Some(owner))?;
+
+      Ok(())
+    }
+
+    /// Approves an asset to be used by a delegate.
+    ///
+    /// The delegate is allowed to lock the asset in mechanics and to transfer it once.
+    /// Any previous approval of the asset is replaced.
+    ///
+    /// The origin must be Signed and must be the owner of the asset.
+    ///
+    /// Parameters:
+    /// - `delegate`: The account to delegate permission to use the asset.
+    /// - `maybe_deadline`: The last block at which the approval is valid.
+    ///
+    /// Emits `ApprovedTransfer` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+    pub fn approve(
+      origin: OriginFor<T>,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      #[pallet::compact] asset_id: NonFungibleAssetId,
+      delegate: <T::Lookup as StaticLookup>::Source,
+      maybe_deadline: Option<T::BlockNumber>,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+      let delegate = T::Lookup::lookup(delegate)?;
+
+      Self::do_approve(class_id, asset_id, &who, delegate, maybe_deadline)?;
+
+      Ok(())
+    }
+
+    /// Cancels the approval of an asset.
+    ///
+    /// The origin must be Signed and must be the owner of the asset.
+    ///
+    /// Emits `ApprovalCancelled` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+    pub fn cancel_approval(
+      origin: OriginFor<T>,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      #[pallet::compact] asset_id: NonFungibleAssetId,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_cancel_approval(class_id, asset_id, &who)?;
+
+      Ok(())
+    }
+
+    /// Transfers an asset to another account.
+    ///
+    /// The origin must be Signed and must be the owner of the asset or its approved delegate.
+    /// The approval is consumed by the transfer.
+    ///
+    /// `descendants` is the number of assets equipped to the asset directly or through other
+    /// assets. The weight is charged for it and the unused part is refunded.
+    ///
+    /// Emits `Transferred` event when successful.
+    #[pallet::weight(Pallet::<T>::transfer_weight(*descendants))]
+    pub fn transfer(
+      origin: OriginFor<T>,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      #[pallet::compact] asset_id: NonFungibleAssetId,
+      dest: <T::Lookup as StaticLookup>::Source,
+      #[pallet::compact] descendants: u32,
+    ) -> DispatchResultWithPostInfo {
+      let who = ensure_signed(origin)?;
+      let dest = T::Lookup::lookup(dest)?;
+
+      let moved = Self::do_transfer(class_id, asset_id, Some(&who), &dest)?;
+      ensure!(moved <= descendants, Error::<T>::TooManyDescendants);
+
+      Ok(Some(Self::transfer_weight(moved)).into())
+    }
+
+    /// Equips an asset to the parent asset.
+    ///
+    /// The equipped asset stays owned by the owner of the parent and is locked until it is
+    /// unequipped. It moves together with the parent when the parent is transferred.
+    ///
+    /// The origin must be Signed and must be the owner of the both assets.
+    ///
+    /// Emits `Equipped` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(4 + T::MaxNestingDepth::get() as u64, 2))]
+    pub fn equip(
+      origin: OriginFor<T>,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      #[pallet::compact] asset_id: NonFungibleAssetId,
+      #[pallet::compact] parent_class_id: NonFungibleClassId,
+      #[pallet::compact] parent_asset_id: NonFungibleAssetId,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_equip(&who, class_id, asset_id, parent_class_id, parent_asset_id)?;
+
+      Ok(())
+    }
+
+    /// Unequips an asset from its parent asset.
+    ///
+    /// The origin must be Signed and must be the owner of the asset.
+    ///
+    /// Emits `Unequipped` event when successful.
+    #[pallet::weight(T::DbWeight::get().reads_writes(2 + T::MaxNestingDepth::get() as u64, 2))]
+    pub fn unequip(
+      origin: OriginFor<T>,
+      #[pallet::compact] class_id: NonFungibleClassId,
+      #[pallet::compact] asset_id: NonFungibleAssetId,
+    ) -> DispatchResult {
+      let who = ensure_signed(origin)?;
+
+      Self::do_unequip(&who, class_id, asset_id)?;
+
+      Ok(())
+    }
   }
 }
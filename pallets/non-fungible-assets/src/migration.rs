@@ -0,0 +1,114 @@
+//! Storage migrations for the Non-Fungible Assets pallet.
+use super::*;
+use frame_support::{
+  log,
+  traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use pallet_support::{
+  bettor::Bettor,
+  purchased::{Offer, PaymentRouting, Purchased},
+  DefaultListLengthLimit, DefaultStringLimit, FungibleAssetBalance, FungibleAssetId,
+};
+use sp_std::marker::PhantomData;
+
+/// Migration of the class details to the layout with the characteristics added after the version 0:
+/// transferability, upgradable, lootbox, salvage, royalty and the extended purchase offers.
+pub mod v1 {
+  use super::*;
+
+  /// Offer of the Purchased characteristic stored by the version 0
+  #[derive(Encode, Decode)]
+  pub struct OldOffer {
+    pub fa: FungibleAssetId,
+    pub price: FungibleAssetBalance,
+    pub attributes: AttributeList,
+  }
+
+  /// Purchased characteristic stored by the version 0
+  #[derive(Encode, Decode)]
+  pub struct OldPurchased {
+    pub offers: BoundedVec<OldOffer, DefaultListLengthLimit>,
+  }
+
+  /// Class details stored by the version 0
+  #[derive(Encode, Decode)]
+  pub struct OldClassDetails<AccountId> {
+    pub owner: AccountId,
+    pub instances: u32,
+    pub attributes: u32,
+    pub name: BoundedVec<u8, DefaultStringLimit>,
+    pub bettor: Option<Bettor>,
+    pub purchased: Option<OldPurchased>,
+  }
+
+  impl OldOffer {
+    /// The offer is paid with the single fungible asset, the payment is burned as before
+    fn migrate(self) -> Offer {
+      Offer {
+        // a single item always fits the limit
+        price: sp_std::vec![(self.fa, self.price)]
+          .try_into()
+          .unwrap_or_default(),
+        trade_ins: Default::default(),
+        attributes: self.attributes,
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: PaymentRouting::Burn,
+      }
+    }
+  }
+
+  impl<AccountId> OldClassDetails<AccountId> {
+    /// New characteristics are absent, assets stay transferable
+    fn migrate(self) -> ClassDetails<AccountId> {
+      ClassDetails {
+        owner: self.owner,
+        instances: self.instances,
+        attributes: self.attributes,
+        name: self.name,
+        bettor: self.bettor,
+        purchased: self.purchased.map(|purchased| Purchased {
+          // the number of offers is bounded by the same limit
+          offers: purchased
+            .offers
+            .into_iter()
+            .map(|offer| offer.migrate())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap_or_default(),
+        }),
+        transferability: Default::default(),
+        upgradable: None,
+        lootbox: None,
+        salvage: None,
+        royalty: None,
+      }
+    }
+  }
+
+  pub struct MigrateToV1<T>(PhantomData<T>);
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let onchain_version = Pallet::<T>::on_chain_storage_version();
+      if onchain_version >= 1 {
+        log::info!(
+          "🧹 Non-fungible assets migration to v1 skipped [on-chain version: {:?}]",
+          onchain_version
+        );
+        return T::DbWeight::get().reads(1);
+      }
+
+      let mut count = 0u64;
+      Classes::<T>::translate::<OldClassDetails<T::AccountId>, _>(|_, old| {
+        count += 1;
+        Some(old.migrate())
+      });
+      StorageVersion::new(1).put::<Pallet<T>>();
+      log::info!("🧹 Non-fungible assets migrated to v1 [classes: {}]", count);
+
+      T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+  }
+}
@@ -10,6 +10,28 @@ use sp_runtime::{
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+/// Provides an implementation of [frame_support::traits::Randomness]
+/// that should only be used in tests!
+/// TestRandomness returns a block number as ramdom value
+pub struct TestRandomness<T>(sp_std::marker::PhantomData<T>);
+impl<Output: codec::Decode + Default, T> frame_support::traits::Randomness<Output, T::BlockNumber>
+  for TestRandomness<T>
+where
+  T: frame_system::Config,
+{
+  fn random(_subject: &[u8]) -> (Output, T::BlockNumber) {
+    use codec::Encode;
+    use sp_runtime::traits::TrailingZeroInput;
+    let block_number: u32 = frame_system::Pallet::<T>::block_number()
+      .try_into()
+      .unwrap_or_default();
+    (
+      Output::decode(&mut TrailingZeroInput::new(&block_number.encode())).unwrap_or_default(),
+      frame_system::Pallet::<T>::block_number(),
+    )
+  }
+}
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
   pub enum Test where
@@ -51,7 +73,31 @@ impl system::Config for Test {
 
 /// Mock of fungible-assets-pallet impl
 pub struct FAPallet {}
+thread_local! {
+  /// References to fungible assets counted by the FAPallet mock
+  static FA_REFERENCES: std::cell::RefCell<std::collections::BTreeMap<pallet_support::FungibleAssetId, u32>> =
+    Default::default();
+}
+
+/// Returns the number of references to the fungible asset counted by the FAPallet mock
+pub fn fa_references(asset: u32) -> u32 {
+  FA_REFERENCES.with(|r| r.borrow().get(&asset.into()).copied().unwrap_or_default())
+}
+
 impl pallet_support::traits::FungibleAssets<u64> for FAPallet {
+  fn owner_of(asset: &pallet_support::FungibleAssetId) -> Option<u64> {
+    if asset == &1.into() {
+      // test purchased_offers_meet_schema, set_characteristic_checks_references
+      return Some(2);
+    }
+    if asset == &3.into() {
+      // test set_characteristic_checks_references, set_characteristic_upgradable_works,
+      // set_characteristic_lootbox_checks_references
+      return Some(3);
+    }
+    None
+  }
+
   fn can_withdraw(
     _asset: pallet_support::FungibleAssetId,
     _who: &u64,
@@ -68,12 +114,47 @@ impl pallet_support::traits::FungibleAssets<u64> for FAPallet {
     todo!()
   }
 
-  fn inc_references(_asset: &pallet_support::FungibleAssetId) -> sp_runtime::DispatchResult {
-    Ok(())
+  fn transfer(
+    _asset: pallet_support::FungibleAssetId,
+    _source: &u64,
+    _dest: &u64,
+    _amount: pallet_support::FungibleAssetBalance,
+  ) -> pallet_support::DispatchResultAs<pallet_support::FungibleAssetBalance> {
+    todo!()
+  }
+
+  fn reserve(
+    _asset: pallet_support::FungibleAssetId,
+    _who: &u64,
+    _amount: pallet_support::FungibleAssetBalance,
+  ) -> sp_runtime::DispatchResult {
+    todo!()
   }
-  fn dec_references(_asset: &pallet_support::FungibleAssetId) -> sp_runtime::DispatchResult {
+
+  fn unreserve(
+    _asset: pallet_support::FungibleAssetId,
+    _who: &u64,
+    _amount: pallet_support::FungibleAssetBalance,
+  ) -> pallet_support::DispatchResultAs<pallet_support::FungibleAssetBalance> {
+    todo!()
+  }
+
+  fn inc_references(asset: &pallet_support::FungibleAssetId) -> sp_runtime::DispatchResult {
+    // test characteristics_maintain_fa_references
+    FA_REFERENCES.with(|r| *r.borrow_mut().entry(*asset).or_default() += 1);
     Ok(())
   }
+  fn dec_references(asset: &pallet_support::FungibleAssetId) -> sp_runtime::DispatchResult {
+    // test characteristics_maintain_fa_references
+    FA_REFERENCES.with(|r| {
+      let mut references = r.borrow_mut();
+      let counter = references.entry(*asset).or_default();
+      *counter = counter
+        .checked_sub(1)
+        .ok_or(sp_runtime::ArithmeticError::Underflow)?;
+      Ok(())
+    })
+  }
   fn mint_into(
     _asset: pallet_support::FungibleAssetId,
     _who: &u64,
@@ -87,6 +168,9 @@ impl pallet_non_fungible_assets::Config for Test {
   type Event = Event;
   type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
   type FungibleAssets = FAPallet;
+  type MaxNestingDepth = ConstU32<2>;
+  type MaxChildren = ConstU32<3>;
+  type Randomness = TestRandomness<Self>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -3,7 +3,11 @@ use super::*;
 use crate::{mock::*, ClassDetailsBuilder, Error, Event as NfaEvent};
 use frame_support::{assert_noop, assert_ok};
 use frame_system::{EventRecord, Phase};
-use pallet_support::{GamerAccount, Locker, MechanicId};
+use pallet_support::{
+  transferability::Transferability,
+  upgradable::{Upgradable, UpgradeCost, UpgradeLevel},
+  AttributeType, GamerAccount, Locker, MechanicId, WeightedText,
+};
 
 fn get_next_class_id() -> NonFungibleClassId {
   NextClassId::<Test>::get()
@@ -1003,3 +1007,1940 @@ fn unset_lock_for_unexisted_asset() {
     assert_ok!(NonFungibleAssets::unset_lock(&acc, &origin, &nfa_id, &id));
   });
 }
+
+#[test]
+fn set_class_metadata_works() {
+  new_test_ext().execute_with(|| {
+    let nfa_id = get_next_class_id();
+    let org = 2;
+    assert_ok!(NonFungibleAssets::create(
+      Origin::signed(1),
+      org,
+      br"nfa name".to_vec()
+    ));
+    System::reset_events();
+    let data = br"ipfs://class".to_vec();
+    assert_ok!(NonFungibleAssets::set_class_metadata(
+      Origin::signed(1),
+      org,
+      nfa_id,
+      data.clone()
+    ));
+    assert_eq!(
+      NonFungibleAssets::class_metadata(&nfa_id),
+      Some(data.clone())
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::ClassMetadataSet {
+          class_id: nfa_id,
+          data: data.try_into().unwrap(),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+
+    assert_ok!(NonFungibleAssets::clear_class_metadata(
+      Origin::signed(1),
+      org,
+      nfa_id
+    ));
+    assert_eq!(NonFungibleAssets::class_metadata(&nfa_id), None);
+  });
+}
+
+#[test]
+fn set_class_metadata_errors() {
+  new_test_ext().execute_with(|| {
+    let nfa_id = get_next_class_id();
+    let org = 2;
+    assert_ok!(NonFungibleAssets::create(
+      Origin::signed(1),
+      org,
+      br"nfa name".to_vec()
+    ));
+    assert_noop!(
+      NonFungibleAssets::do_set_class_metadata(nfa_id, Some(3), br"uri".to_vec()),
+      Error::<Test>::NoPermission
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_class_metadata(100.into(), Some(org), br"uri".to_vec()),
+      Error::<Test>::UnknownClass
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_class_metadata(nfa_id, Some(org), vec![1u8; 257]),
+      Error::<Test>::MetadataTooLong
+    );
+  });
+}
+
+#[test]
+fn set_asset_metadata_overrides_class() {
+  new_test_ext().execute_with(|| {
+    let nfa_id = get_next_class_id();
+    let org = 2;
+    let acc = 1;
+    assert_ok!(NonFungibleAssets::create(
+      Origin::signed(1),
+      org,
+      br"nfa name".to_vec()
+    ));
+    let id = NonFungibleAssets::do_mint(nfa_id, acc).unwrap();
+    assert_eq!(NonFungibleAssets::asset_metadata(&nfa_id, &id), None);
+
+    let class_data = br"ipfs://class".to_vec();
+    assert_ok!(NonFungibleAssets::do_set_class_metadata(
+      nfa_id,
+      Some(org),
+      class_data.clone()
+    ));
+    assert_eq!(
+      NonFungibleAssets::asset_metadata(&nfa_id, &id),
+      Some(class_data.clone())
+    );
+    // the unexisted instance has no metadata
+    assert_eq!(
+      NonFungibleAssets::asset_metadata(&nfa_id, &100.into()),
+      None
+    );
+
+    let asset_data = br"ipfs://asset".to_vec();
+    assert_noop!(
+      NonFungibleAssets::do_set_asset_metadata(nfa_id, 100.into(), Some(org), asset_data.clone()),
+      Error::<Test>::UnknownAsset
+    );
+    assert_ok!(NonFungibleAssets::set_asset_metadata(
+      Origin::signed(1),
+      org,
+      nfa_id,
+      id,
+      asset_data.clone()
+    ));
+    assert_eq!(
+      NonFungibleAssets::asset_metadata(&nfa_id, &id),
+      Some(asset_data)
+    );
+
+    assert_ok!(NonFungibleAssets::clear_asset_metadata(
+      Origin::signed(1),
+      org,
+      nfa_id,
+      id
+    ));
+    assert_eq!(
+      NonFungibleAssets::asset_metadata(&nfa_id, &id),
+      Some(class_data)
+    );
+  });
+}
+
+#[test]
+fn do_burn_removes_metadata() {
+  new_test_ext().execute_with(|| {
+    let nfa_id = get_next_class_id();
+    let org = 2;
+    let acc = 1;
+    assert_ok!(NonFungibleAssets::create(
+      Origin::signed(1),
+      org,
+      br"nfa name".to_vec()
+    ));
+    let id = NonFungibleAssets::do_mint(nfa_id, acc).unwrap();
+    assert_ok!(NonFungibleAssets::do_set_asset_metadata(
+      nfa_id,
+      id,
+      Some(org),
+      br"ipfs://asset".to_vec()
+    ));
+    assert_ok!(NonFungibleAssets::do_burn(nfa_id, id, None));
+    assert!(!AssetMetadataOf::<Test>::contains_key(nfa_id, id));
+
+    assert_ok!(NonFungibleAssets::do_set_class_metadata(
+      nfa_id,
+      Some(org),
+      br"ipfs://class".to_vec()
+    ));
+    assert_ok!(NonFungibleAssets::do_destroy_class(nfa_id, None));
+    assert!(!ClassMetadataOf::<Test>::contains_key(nfa_id));
+  });
+}
+
+/// Helper which creates a class with one asset minted to `acc`
+fn create_class_with_asset(org: u64, acc: u64) -> (NonFungibleClassId, NonFungibleAssetId) {
+  let class_id = get_next_class_id();
+  assert_ok!(NonFungibleAssets::create(
+    Origin::signed(1),
+    org,
+    br"nfa name".to_vec()
+  ));
+  let asset_id = NonFungibleAssets::do_mint(class_id, acc).unwrap();
+  (class_id, asset_id)
+}
+
+#[test]
+fn do_transfer_works() {
+  new_test_ext().execute_with(|| {
+    let (org, from, to) = (2, 1, 3);
+    let (class_id, asset_id) = create_class_with_asset(org, from);
+    System::reset_events();
+
+    assert_noop!(
+      NonFungibleAssets::do_transfer(class_id, asset_id, Some(&to), &to),
+      Error::<Test>::NoPermission
+    );
+    assert_ok!(NonFungibleAssets::do_transfer(
+      class_id,
+      asset_id,
+      Some(&from),
+      &to
+    ));
+    assert_eq!(Assets::<Test>::get(class_id, asset_id).unwrap().owner, to);
+    assert!(!Accounts::<Test>::contains_key((from, class_id, asset_id)));
+    assert!(Accounts::<Test>::contains_key((to, class_id, asset_id)));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::Transferred {
+          class_id,
+          asset_id,
+          from,
+          to,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn do_transfer_locked() {
+  new_test_ext().execute_with(|| {
+    let (org, from, to) = (2, 1, 3);
+    let (class_id, asset_id) = create_class_with_asset(org, from);
+    let origin = Locker::Mechanic(MechanicId {
+      gamer_account: GamerAccount {
+        account_id: from,
+        organization_id: org,
+      },
+      nonce: 2,
+    });
+    assert_ok!(NonFungibleAssets::set_lock(
+      &from, origin, &class_id, &asset_id
+    ));
+    assert_noop!(
+      NonFungibleAssets::do_transfer(class_id, asset_id, Some(&from), &to),
+      Error::<Test>::Locked
+    );
+  });
+}
+
+#[test]
+fn set_characteristic_transferability_works() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    assert_eq!(
+      Classes::<Test>::get(class_id).unwrap().transferability,
+      Transferability::Transferable
+    );
+    assert_ok!(NonFungibleAssets::set_characteristic(
+      Origin::signed(1),
+      org,
+      class_id,
+      Characteristic::Transferability(Transferability::Soulbound)
+    ));
+    assert_eq!(
+      Classes::<Test>::get(class_id).unwrap().transferability,
+      Transferability::Soulbound
+    );
+    assert_noop!(
+      NonFungibleAssets::ensure_transferable(&class_id),
+      Error::<Test>::Soulbound
+    );
+  });
+}
+
+#[test]
+fn soulbound_blocks_transfer_but_allows_lock_and_burn() {
+  new_test_ext().execute_with(|| {
+    let (org, from, to) = (2, 1, 3);
+    let (class_id, asset_id) = create_class_with_asset(org, from);
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      Some(org),
+      Characteristic::Transferability(Transferability::Soulbound)
+    ));
+    assert_noop!(
+      NonFungibleAssets::do_transfer(class_id, asset_id, Some(&from), &to),
+      Error::<Test>::Soulbound
+    );
+
+    let origin = Locker::Mechanic(MechanicId {
+      gamer_account: GamerAccount {
+        account_id: from,
+        organization_id: org,
+      },
+      nonce: 2,
+    });
+    assert_ok!(NonFungibleAssets::set_lock(
+      &from,
+      origin.clone(),
+      &class_id,
+      &asset_id
+    ));
+    assert_ok!(NonFungibleAssets::unset_lock(
+      &from, &origin, &class_id, &asset_id
+    ));
+    assert_ok!(NonFungibleAssets::do_burn(class_id, asset_id, Some(&from)));
+  });
+}
+
+#[test]
+fn approve_works() {
+  new_test_ext().execute_with(|| {
+    let (org, owner, delegate) = (2, 1, 3);
+    let (class_id, asset_id) = create_class_with_asset(org, owner);
+    System::reset_events();
+
+    assert_noop!(
+      NonFungibleAssets::approve(Origin::signed(delegate), class_id, asset_id, delegate, None),
+      Error::<Test>::NoPermission
+    );
+    assert_ok!(NonFungibleAssets::approve(
+      Origin::signed(owner),
+      class_id,
+      asset_id,
+      delegate,
+      Some(10)
+    ));
+    assert_eq!(
+      Approvals::<Test>::get(class_id, asset_id),
+      Some(Approval {
+        delegate,
+        deadline: Some(10),
+      })
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::ApprovedTransfer {
+          class_id,
+          asset_id,
+          owner,
+          delegate,
+          deadline: Some(10),
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+  });
+}
+
+#[test]
+fn cancel_approval_works() {
+  new_test_ext().execute_with(|| {
+    let (org, owner, delegate) = (2, 1, 3);
+    let (class_id, asset_id) = create_class_with_asset(org, owner);
+    assert_noop!(
+      NonFungibleAssets::cancel_approval(Origin::signed(owner), class_id, asset_id),
+      Error::<Test>::NotApproved
+    );
+    assert_ok!(NonFungibleAssets::approve(
+      Origin::signed(owner),
+      class_id,
+      asset_id,
+      delegate,
+      None
+    ));
+    assert_noop!(
+      NonFungibleAssets::cancel_approval(Origin::signed(delegate), class_id, asset_id),
+      Error::<Test>::NoPermission
+    );
+    System::reset_events();
+    assert_ok!(NonFungibleAssets::cancel_approval(
+      Origin::signed(owner),
+      class_id,
+      asset_id
+    ));
+    assert!(!Approvals::<Test>::contains_key(class_id, asset_id));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::ApprovalCancelled {
+          class_id,
+          asset_id,
+          owner,
+          delegate,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    assert_noop!(
+      NonFungibleAssets::transfer(Origin::signed(delegate), class_id, asset_id, delegate, 0),
+      Error::<Test>::NoPermission
+    );
+  });
+}
+
+#[test]
+fn delegate_transfers_once() {
+  new_test_ext().execute_with(|| {
+    let (org, owner, delegate, to) = (2, 1, 3, 4);
+    let (class_id, asset_id) = create_class_with_asset(org, owner);
+    assert_ok!(NonFungibleAssets::approve(
+      Origin::signed(owner),
+      class_id,
+      asset_id,
+      delegate,
+      None
+    ));
+    assert_ok!(NonFungibleAssets::transfer(
+      Origin::signed(delegate),
+      class_id,
+      asset_id,
+      to,
+      0
+    ));
+    assert_eq!(Assets::<Test>::get(class_id, asset_id).unwrap().owner, to);
+    assert!(!Approvals::<Test>::contains_key(class_id, asset_id));
+    assert_noop!(
+      NonFungibleAssets::transfer(Origin::signed(delegate), class_id, asset_id, delegate, 0),
+      Error::<Test>::NoPermission
+    );
+  });
+}
+
+#[test]
+fn delegate_locks_and_unlocks() {
+  new_test_ext().execute_with(|| {
+    let (org, owner, delegate) = (2, 1, 3);
+    let (class_id, asset_id) = create_class_with_asset(org, owner);
+    assert_ok!(NonFungibleAssets::approve(
+      Origin::signed(owner),
+      class_id,
+      asset_id,
+      delegate,
+      None
+    ));
+    let origin = Locker::Mechanic(MechanicId {
+      gamer_account: GamerAccount {
+        account_id: delegate,
+        organization_id: org,
+      },
+      nonce: 1,
+    });
+    assert_ok!(NonFungibleAssets::set_lock(
+      &delegate,
+      origin.clone(),
+      &class_id,
+      &asset_id
+    ));
+    assert_eq!(
+      Assets::<Test>::get(class_id, asset_id).unwrap().locked,
+      origin
+    );
+    // the owner can't move the asset until the lock is released
+    assert_noop!(
+      NonFungibleAssets::transfer(Origin::signed(owner), class_id, asset_id, delegate, 0),
+      Error::<Test>::Locked
+    );
+    // the delegate still able to release its lock after the approval cancelling
+    assert_ok!(NonFungibleAssets::cancel_approval(
+      Origin::signed(owner),
+      class_id,
+      asset_id
+    ));
+    assert_ok!(NonFungibleAssets::unset_lock(
+      &delegate, &origin, &class_id, &asset_id
+    ));
+    assert_eq!(
+      Assets::<Test>::get(class_id, asset_id).unwrap().locked,
+      Locker::None
+    );
+  });
+}
+
+#[test]
+fn expired_approval_not_allowed() {
+  new_test_ext().execute_with(|| {
+    let (org, owner, delegate) = (2, 1, 3);
+    let (class_id, asset_id) = create_class_with_asset(org, owner);
+    System::set_block_number(5);
+    assert_ok!(NonFungibleAssets::approve(
+      Origin::signed(owner),
+      class_id,
+      asset_id,
+      delegate,
+      Some(6)
+    ));
+    System::set_block_number(7);
+    let origin = Locker::Mechanic(MechanicId {
+      gamer_account: GamerAccount {
+        account_id: delegate,
+        organization_id: org,
+      },
+      nonce: 1,
+    });
+    assert_noop!(
+      NonFungibleAssets::set_lock(&delegate, origin, &class_id, &asset_id),
+      Error::<Test>::ApprovalExpired
+    );
+    assert_noop!(
+      NonFungibleAssets::transfer(Origin::signed(delegate), class_id, asset_id, delegate, 0),
+      Error::<Test>::ApprovalExpired
+    );
+  });
+}
+
+#[test]
+fn do_burn_removes_approval() {
+  new_test_ext().execute_with(|| {
+    let (org, owner, delegate) = (2, 1, 3);
+    let (class_id, asset_id) = create_class_with_asset(org, owner);
+    assert_ok!(NonFungibleAssets::approve(
+      Origin::signed(owner),
+      class_id,
+      asset_id,
+      delegate,
+      None
+    ));
+    assert_ok!(NonFungibleAssets::do_burn(class_id, asset_id, Some(&owner)));
+    assert!(!Approvals::<Test>::contains_key(class_id, asset_id));
+  });
+}
+
+#[test]
+fn equip_works() {
+  new_test_ext().execute_with(|| {
+    let (org, owner) = (2, 1);
+    let (class_id, hero) = create_class_with_asset(org, owner);
+    let sword = NonFungibleAssets::do_mint(class_id, owner).unwrap();
+    System::reset_events();
+
+    assert_noop!(
+      NonFungibleAssets::equip(Origin::signed(3), class_id, sword, class_id, hero),
+      Error::<Test>::NoPermission
+    );
+    assert_ok!(NonFungibleAssets::equip(
+      Origin::signed(owner),
+      class_id,
+      sword,
+      class_id,
+      hero
+    ));
+    assert_eq!(
+      Assets::<Test>::get(class_id, sword).unwrap().locked,
+      Locker::Nfa(class_id, hero)
+    );
+    assert!(Children::<Test>::contains_key((
+      class_id, hero, class_id, sword
+    )));
+    assert_eq!(
+      NonFungibleAssets::root_of(class_id, sword),
+      (class_id, hero)
+    );
+    assert_eq!(NonFungibleAssets::owner_of(class_id, sword), Some(owner));
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::Equipped {
+          class_id,
+          asset_id: sword,
+          parent_class_id: class_id,
+          parent_asset_id: hero,
+        }
+        .into(),
+        topics: vec![],
+      },]
+    );
+    // an equipped asset is locked
+    assert_noop!(
+      NonFungibleAssets::transfer(Origin::signed(owner), class_id, sword, 3, 0),
+      Error::<Test>::Locked
+    );
+    let origin = Locker::Mechanic(MechanicId {
+      gamer_account: GamerAccount {
+        account_id: owner,
+        organization_id: org,
+      },
+      nonce: 1,
+    });
+    assert_noop!(
+      NonFungibleAssets::set_lock(&owner, origin, &class_id, &sword),
+      Error::<Test>::Locked
+    );
+
+    assert_ok!(NonFungibleAssets::unequip(
+      Origin::signed(owner),
+      class_id,
+      sword
+    ));
+    assert_eq!(
+      Assets::<Test>::get(class_id, sword).unwrap().locked,
+      Locker::None
+    );
+    assert!(!Children::<Test>::contains_key((
+      class_id, hero, class_id, sword
+    )));
+    assert_noop!(
+      NonFungibleAssets::unequip(Origin::signed(owner), class_id, sword),
+      Error::<Test>::NotEquipped
+    );
+  });
+}
+
+#[test]
+fn equip_limits() {
+  new_test_ext().execute_with(|| {
+    let (org, owner) = (2, 1);
+    let (class_id, root) = create_class_with_asset(org, owner);
+    let ids: Vec<_> = (0..5)
+      .map(|_| NonFungibleAssets::do_mint(class_id, owner).unwrap())
+      .collect();
+    // root <- ids[0] <- ids[1]
+    assert_ok!(NonFungibleAssets::do_equip(
+      &owner, class_id, ids[0], class_id, root
+    ));
+    assert_ok!(NonFungibleAssets::do_equip(
+      &owner, class_id, ids[1], class_id, ids[0]
+    ));
+    assert_noop!(
+      NonFungibleAssets::do_equip(&owner, class_id, root, class_id, root),
+      Error::<Test>::NestingLoop
+    );
+    assert_noop!(
+      NonFungibleAssets::do_equip(&owner, class_id, root, class_id, ids[1]),
+      Error::<Test>::NestingLoop
+    );
+    assert_noop!(
+      NonFungibleAssets::do_equip(&owner, class_id, ids[2], class_id, ids[1]),
+      Error::<Test>::NestingTooDeep
+    );
+    // ids[2] <- ids[3] can't be equipped to ids[0]
+    assert_ok!(NonFungibleAssets::do_equip(
+      &owner, class_id, ids[3], class_id, ids[2]
+    ));
+    assert_noop!(
+      NonFungibleAssets::do_equip(&owner, class_id, ids[2], class_id, ids[0]),
+      Error::<Test>::NestingTooDeep
+    );
+    assert_ok!(NonFungibleAssets::do_equip(
+      &owner, class_id, ids[2], class_id, root
+    ));
+    assert_ok!(NonFungibleAssets::do_equip(
+      &owner, class_id, ids[4], class_id, root
+    ));
+    let extra = NonFungibleAssets::do_mint(class_id, owner).unwrap();
+    assert_noop!(
+      NonFungibleAssets::do_equip(&owner, class_id, extra, class_id, root),
+      Error::<Test>::TooManyChildren
+    );
+  });
+}
+
+#[test]
+fn equip_into_locked_tree_not_allowed() {
+  new_test_ext().execute_with(|| {
+    let (org, owner) = (2, 1);
+    let (class_id, hero) = create_class_with_asset(org, owner);
+    let sword = NonFungibleAssets::do_mint(class_id, owner).unwrap();
+    let shield = NonFungibleAssets::do_mint(class_id, owner).unwrap();
+    assert_ok!(NonFungibleAssets::do_equip(
+      &owner, class_id, sword, class_id, hero
+    ));
+    let origin = Locker::Mechanic(MechanicId {
+      gamer_account: GamerAccount {
+        account_id: owner,
+        organization_id: org,
+      },
+      nonce: 1,
+    });
+    assert_ok!(NonFungibleAssets::set_lock(
+      &owner, origin, &class_id, &hero
+    ));
+    assert_noop!(
+      NonFungibleAssets::do_equip(&owner, class_id, shield, class_id, sword),
+      Error::<Test>::Locked
+    );
+    assert_noop!(
+      NonFungibleAssets::do_unequip(&owner, class_id, sword),
+      Error::<Test>::Locked
+    );
+  });
+}
+
+#[test]
+fn transfer_moves_equipped_assets() {
+  new_test_ext().execute_with(|| {
+    let (org, from, to) = (2, 1, 3);
+    let (class_id, hero) = create_class_with_asset(org, from);
+    let sword = NonFungibleAssets::do_mint(class_id, from).unwrap();
+    let gem = NonFungibleAssets::do_mint(class_id, from).unwrap();
+    assert_ok!(NonFungibleAssets::do_equip(
+      &from, class_id, sword, class_id, hero
+    ));
+    assert_ok!(NonFungibleAssets::do_equip(
+      &from, class_id, gem, class_id, sword
+    ));
+
+    assert_ok!(NonFungibleAssets::do_transfer(
+      class_id,
+      hero,
+      Some(&from),
+      &to
+    ));
+    for asset_id in [hero, sword, gem] {
+      assert_eq!(Assets::<Test>::get(class_id, asset_id).unwrap().owner, to);
+      assert!(!Accounts::<Test>::contains_key((from, class_id, asset_id)));
+      assert!(Accounts::<Test>::contains_key((to, class_id, asset_id)));
+    }
+    assert_eq!(
+      Assets::<Test>::get(class_id, gem).unwrap().locked,
+      Locker::Nfa(class_id, sword)
+    );
+    assert_eq!(NonFungibleAssets::owner_of(class_id, gem), Some(to));
+  });
+}
+
+#[test]
+fn transfer_weight_covers_descendants() {
+  new_test_ext().execute_with(|| {
+    let (org, from, to) = (2, 1, 3);
+    let (class_id, hero) = create_class_with_asset(org, from);
+    let sword = NonFungibleAssets::do_mint(class_id, from).unwrap();
+    let gem = NonFungibleAssets::do_mint(class_id, from).unwrap();
+    assert_ok!(NonFungibleAssets::do_equip(
+      &from, class_id, sword, class_id, hero
+    ));
+    assert_ok!(NonFungibleAssets::do_equip(
+      &from, class_id, gem, class_id, sword
+    ));
+
+    // the hint must cover all equipped assets
+    assert_noop!(
+      NonFungibleAssets::transfer(Origin::signed(from), class_id, hero, to, 1),
+      Error::<Test>::TooManyDescendants
+    );
+    // the weight of the unused hint is refunded
+    let post_info =
+      NonFungibleAssets::transfer(Origin::signed(from), class_id, hero, to, 5).unwrap();
+    assert_eq!(
+      post_info.actual_weight,
+      Some(NonFungibleAssets::transfer_weight(2))
+    );
+    assert_eq!(Assets::<Test>::get(class_id, gem).unwrap().owner, to);
+  });
+}
+
+#[test]
+fn transfer_with_equipped_soulbound_not_allowed() {
+  new_test_ext().execute_with(|| {
+    let (org, from, to) = (2, 1, 3);
+    let (class_id, hero) = create_class_with_asset(org, from);
+    let (soulbound_class_id, badge) = create_class_with_asset(org, from);
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      soulbound_class_id,
+      Some(org),
+      Characteristic::Transferability(Transferability::Soulbound)
+    ));
+    assert_ok!(NonFungibleAssets::do_equip(
+      &from,
+      soulbound_class_id,
+      badge,
+      class_id,
+      hero
+    ));
+
+    assert_noop!(
+      NonFungibleAssets::do_transfer(class_id, hero, Some(&from), &to),
+      Error::<Test>::Soulbound
+    );
+    assert_eq!(
+      Assets::<Test>::get(soulbound_class_id, badge)
+        .unwrap()
+        .owner,
+      from
+    );
+  });
+}
+
+#[test]
+fn burn_releases_equipped_assets() {
+  new_test_ext().execute_with(|| {
+    let (org, owner) = (2, 1);
+    let (class_id, hero) = create_class_with_asset(org, owner);
+    let sword = NonFungibleAssets::do_mint(class_id, owner).unwrap();
+    let gem = NonFungibleAssets::do_mint(class_id, owner).unwrap();
+    assert_ok!(NonFungibleAssets::do_equip(
+      &owner, class_id, sword, class_id, hero
+    ));
+    assert_ok!(NonFungibleAssets::do_equip(
+      &owner, class_id, gem, class_id, sword
+    ));
+
+    // burning of a child detaches it from the parent
+    assert_ok!(NonFungibleAssets::do_burn(class_id, gem, Some(&owner)));
+    assert!(!Children::<Test>::contains_key((
+      class_id, sword, class_id, gem
+    )));
+
+    System::reset_events();
+    assert_ok!(NonFungibleAssets::do_burn(class_id, hero, Some(&owner)));
+    assert_eq!(
+      Assets::<Test>::get(class_id, sword).unwrap().locked,
+      Locker::None
+    );
+    assert!(Accounts::<Test>::contains_key((owner, class_id, sword)));
+    assert!(!Children::<Test>::contains_key((
+      class_id, hero, class_id, sword
+    )));
+    assert_eq!(
+      System::events()[0],
+      EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::Unequipped {
+          class_id,
+          asset_id: sword,
+          parent_class_id: class_id,
+          parent_asset_id: hero,
+        }
+        .into(),
+        topics: vec![],
+      }
+    );
+  });
+}
+
+#[test]
+fn set_characteristic_upgradable_works() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    let mut upgradable = Upgradable {
+      level_key: br"level".to_vec().try_into().unwrap(),
+      levels: vec![UpgradeLevel {
+        cost: UpgradeCost::Fa(1.into(), 0.into()),
+        deltas: Default::default(),
+      }]
+      .try_into()
+      .unwrap(),
+    };
+    assert_noop!(
+      NonFungibleAssets::set_characteristic(
+        Origin::signed(1),
+        org,
+        class_id,
+        Characteristic::Upgradable(Some(upgradable.clone()))
+      ),
+      Error::<Test>::WrongUpgradable
+    );
+    // the cost is paid by assets of the organization only
+    upgradable.levels[0].cost = UpgradeCost::Fa(3.into(), 10.into());
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        Characteristic::Upgradable(Some(upgradable.clone()))
+      ),
+      Error::<Test>::CommonError(pallet_support::CommonError::ForeignReferencedAsset)
+    );
+    let (foreign_class_id, _) = create_class_with_asset(3, acc);
+    upgradable.levels[0].cost = UpgradeCost::Nfa(foreign_class_id);
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        Characteristic::Upgradable(Some(upgradable.clone()))
+      ),
+      Error::<Test>::CommonError(pallet_support::CommonError::ForeignReferencedAsset)
+    );
+    upgradable.levels[0].cost = UpgradeCost::Fa(1.into(), 10.into());
+    assert_ok!(NonFungibleAssets::set_characteristic(
+      Origin::signed(1),
+      org,
+      class_id,
+      Characteristic::Upgradable(Some(upgradable.clone()))
+    ));
+    assert_eq!(
+      Classes::<Test>::get(class_id).unwrap().upgradable,
+      Some(upgradable)
+    );
+    assert_eq!(fa_references(1), 1);
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      Characteristic::Upgradable(None)
+    ));
+    assert_eq!(fa_references(1), 0);
+  });
+}
+
+#[test]
+fn set_characteristic_lootbox_checks_references() {
+  use pallet_support::{
+    lootbox::{Lootbox, LootboxDrop, LootboxReward},
+    CommonError,
+  };
+  new_test_ext().execute_with(|| {
+    // FA 1 is owned by the organization 2, FA 3 by the organization 3
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    let (foreign_class_id, _) = create_class_with_asset(3, acc);
+    let lootbox = |reward| {
+      Characteristic::Lootbox(Some(Lootbox {
+        drops: vec![LootboxDrop {
+          weight: 1,
+          rare: false,
+          reward,
+        }]
+        .try_into()
+        .unwrap(),
+        rolls: 1,
+        pity: None,
+      }))
+    };
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        lootbox(LootboxReward::Fa(3.into(), 10.into()))
+      ),
+      Error::<Test>::CommonError(CommonError::ForeignReferencedAsset)
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        lootbox(LootboxReward::Nfa(foreign_class_id, Default::default()))
+      ),
+      Error::<Test>::CommonError(CommonError::ForeignReferencedAsset)
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        lootbox(LootboxReward::Nfa(100.into(), Default::default()))
+      ),
+      Error::<Test>::CommonError(CommonError::UnknownReferencedAsset)
+    );
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      lootbox(LootboxReward::Fa(1.into(), 10.into()))
+    ));
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      lootbox(LootboxReward::Nfa(class_id, Default::default()))
+    ));
+  });
+}
+
+#[test]
+fn attribute_of_falls_back_to_class() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, asset_id) = create_class_with_asset(org, acc);
+    let key: AttributeKey = br"power".to_vec().try_into().unwrap();
+    assert_eq!(
+      NonFungibleAssets::attribute_of(&class_id, &asset_id, &key),
+      None
+    );
+    assert_ok!(NonFungibleAssets::do_create_attribute(
+      class_id,
+      None,
+      Attribute {
+        key: key.clone(),
+        value: 1u32.try_into().unwrap(),
+      }
+    ));
+    assert_eq!(
+      NonFungibleAssets::attribute_of(&class_id, &asset_id, &key),
+      Some(1u32.try_into().unwrap())
+    );
+    assert_ok!(NonFungibleAssets::assign_attributes(
+      &asset_id,
+      vec![Attribute {
+        key: key.clone(),
+        value: 5u32.try_into().unwrap(),
+      }]
+      .try_into()
+      .unwrap()
+    ));
+    assert_eq!(
+      NonFungibleAssets::attribute_of(&class_id, &asset_id, &key),
+      Some(5u32.try_into().unwrap())
+    );
+  });
+}
+
+#[test]
+fn attribute_templates_work() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    let key: AttributeKey = br"power".to_vec().try_into().unwrap();
+    let template = AttributeTemplate::NumberRange {
+      min: 10,
+      max: 20,
+      number_max: Some(50),
+    };
+    assert_noop!(
+      NonFungibleAssets::set_attribute_template(
+        Origin::signed(1),
+        3,
+        class_id,
+        key.clone(),
+        template.clone()
+      ),
+      Error::<Test>::NoPermission
+    );
+    assert_noop!(
+      NonFungibleAssets::set_attribute_template(
+        Origin::signed(1),
+        org,
+        class_id,
+        key.clone(),
+        AttributeTemplate::NumberRange {
+          min: 20,
+          max: 10,
+          number_max: None,
+        }
+      ),
+      Error::<Test>::WrongAttributeTemplate
+    );
+    assert_noop!(
+      NonFungibleAssets::set_attribute_template(
+        Origin::signed(1),
+        org,
+        class_id,
+        key.clone(),
+        AttributeTemplate::WeightedText(Default::default())
+      ),
+      Error::<Test>::WrongAttributeTemplate
+    );
+    System::reset_events();
+    assert_ok!(NonFungibleAssets::set_attribute_template(
+      Origin::signed(1),
+      org,
+      class_id,
+      key.clone(),
+      template.clone()
+    ));
+    assert_eq!(
+      AttributeTemplates::<Test>::get(class_id, &key),
+      Some(template.clone())
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::AttributeTemplateSet {
+          class_id,
+          key: key.clone(),
+          template,
+        }
+        .into(),
+        topics: vec![],
+      }]
+    );
+
+    assert_noop!(
+      NonFungibleAssets::remove_attribute_template(
+        Origin::signed(1),
+        org,
+        class_id,
+        br"other".to_vec().try_into().unwrap()
+      ),
+      Error::<Test>::UnknownAttributeTemplate
+    );
+    assert_ok!(NonFungibleAssets::remove_attribute_template(
+      Origin::signed(1),
+      org,
+      class_id,
+      key.clone()
+    ));
+    assert_eq!(AttributeTemplates::<Test>::get(class_id, &key), None);
+  });
+}
+
+#[test]
+fn mint_rolls_attribute_templates() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, asset_id) = create_class_with_asset(org, acc);
+    let power: AttributeKey = br"power".to_vec().try_into().unwrap();
+    let rarity: AttributeKey = br"rarity".to_vec().try_into().unwrap();
+    assert_ok!(NonFungibleAssets::do_set_attribute_template(
+      class_id,
+      None,
+      power.clone(),
+      AttributeTemplate::NumberRange {
+        min: 10,
+        max: 20,
+        number_max: Some(50),
+      }
+    ));
+    assert_ok!(NonFungibleAssets::do_set_attribute_template(
+      class_id,
+      None,
+      rarity.clone(),
+      AttributeTemplate::WeightedText(
+        vec![
+          WeightedText {
+            value: br"common".to_vec().try_into().unwrap(),
+            weight: 3,
+          },
+          WeightedText {
+            value: br"rare".to_vec().try_into().unwrap(),
+            weight: 1,
+          },
+        ]
+        .try_into()
+        .unwrap()
+      )
+    ));
+    // assets minted before the templates were set are untouched
+    assert_eq!(Attributes::<Test>::get(asset_id, &power), None);
+
+    // TestRandomness returns the block number as a random value
+    System::set_block_number(3);
+    let asset_id = NonFungibleAssets::do_mint(class_id, acc).unwrap();
+    assert_eq!(
+      Attributes::<Test>::get(asset_id, &power),
+      Some((13u32, 50u32).try_into().unwrap())
+    );
+    assert_eq!(
+      Attributes::<Test>::get(asset_id, &rarity),
+      Some("rare".try_into().unwrap())
+    );
+
+    System::set_block_number(12);
+    let asset_id = NonFungibleAssets::do_mint(class_id, acc).unwrap();
+    assert_eq!(
+      Attributes::<Test>::get(asset_id, &power),
+      Some((11u32, 50u32).try_into().unwrap())
+    );
+    assert_eq!(
+      Attributes::<Test>::get(asset_id, &rarity),
+      Some("common".try_into().unwrap())
+    );
+
+    // attributes assigned after the mint override rolled values
+    assert_ok!(NonFungibleAssets::assign_attributes(
+      &asset_id,
+      vec![Attribute {
+        key: power.clone(),
+        value: (40u32, 50u32).try_into().unwrap(),
+      }]
+      .try_into()
+      .unwrap()
+    ));
+    assert_eq!(
+      Attributes::<Test>::get(asset_id, &power),
+      Some((40u32, 50u32).try_into().unwrap())
+    );
+
+    assert_ok!(NonFungibleAssets::do_destroy_class(class_id, None));
+    assert_eq!(AttributeTemplates::<Test>::get(class_id, &power), None);
+  });
+}
+
+#[test]
+fn account_assets_query_works() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_a, asset_a0) = create_class_with_asset(org, acc);
+    let (class_b, asset_b0) = create_class_with_asset(org, acc);
+    let asset_a1 = NonFungibleAssets::do_mint(class_a, acc).unwrap();
+    let _ = NonFungibleAssets::do_mint(class_a, 3).unwrap();
+
+    let mut all = NonFungibleAssets::account_assets(&acc, None, 0, 10);
+    all.sort_by_key(|(class_id, asset_id)| (**class_id, **asset_id));
+    assert_eq!(
+      all,
+      vec![
+        (class_a, asset_a0),
+        (class_a, asset_a1),
+        (class_b, asset_b0)
+      ]
+    );
+
+    let mut of_class = NonFungibleAssets::account_assets(&acc, Some(class_a), 0, 10);
+    of_class.sort_by_key(|(_, asset_id)| **asset_id);
+    assert_eq!(of_class, vec![(class_a, asset_a0), (class_a, asset_a1)]);
+
+    // pages don't overlap
+    let first = NonFungibleAssets::account_assets(&acc, None, 0, 2);
+    let second = NonFungibleAssets::account_assets(&acc, None, 2, 2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 1);
+    assert!(!first.contains(&second[0]));
+
+    assert!(NonFungibleAssets::account_assets(&4, None, 0, 10).is_empty());
+  });
+}
+
+#[test]
+fn asset_queries_work() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, asset_id) = create_class_with_asset(org, acc);
+    let power: AttributeKey = br"power".to_vec().try_into().unwrap();
+    let speed: AttributeKey = br"speed".to_vec().try_into().unwrap();
+    for key in [&power, &speed] {
+      assert_ok!(NonFungibleAssets::do_create_attribute(
+        class_id,
+        None,
+        Attribute {
+          key: key.clone(),
+          value: 1u32.try_into().unwrap(),
+        }
+      ));
+    }
+    assert_ok!(NonFungibleAssets::assign_attributes(
+      &asset_id,
+      vec![Attribute {
+        key: power.clone(),
+        value: 5u32.try_into().unwrap(),
+      }]
+      .try_into()
+      .unwrap()
+    ));
+
+    let mut attributes = NonFungibleAssets::asset_attributes(&class_id, &asset_id, 0, 10);
+    attributes.sort_by_key(|a| a.key.to_vec());
+    assert_eq!(
+      attributes,
+      vec![
+        Attribute {
+          key: power,
+          value: 5u32.try_into().unwrap(),
+        },
+        Attribute {
+          key: speed,
+          value: 1u32.try_into().unwrap(),
+        },
+      ]
+    );
+    assert_eq!(
+      NonFungibleAssets::asset_attributes(&class_id, &asset_id, 1, 10).len(),
+      1
+    );
+    assert!(NonFungibleAssets::asset_attributes(&class_id, &999.into(), 0, 10).is_empty());
+
+    let mechanic_id = MechanicId {
+      gamer_account: GamerAccount {
+        account_id: acc,
+        organization_id: org,
+      },
+      nonce: 0,
+    };
+    assert_ok!(NonFungibleAssets::set_lock(
+      &acc,
+      Locker::Mechanic(mechanic_id.clone()),
+      &class_id,
+      &asset_id
+    ));
+    let details = NonFungibleAssets::asset_details(&class_id, &asset_id).unwrap();
+    assert_eq!(details.owner, acc);
+    assert_eq!(details.locked, Locker::Mechanic(mechanic_id));
+
+    assert_eq!(
+      NonFungibleAssets::class_details(&class_id)
+        .unwrap()
+        .attributes,
+      2
+    );
+    assert!(NonFungibleAssets::class_details(&999.into()).is_none());
+  });
+}
+
+#[test]
+fn class_ownership_transfer_works() {
+  new_test_ext().execute_with(|| {
+    let (org, new_org, acc) = (2, 3, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+
+    assert_noop!(
+      NonFungibleAssets::offer_ownership(Origin::signed(1), new_org, class_id, new_org),
+      Error::<Test>::NoPermission
+    );
+    assert_noop!(
+      NonFungibleAssets::accept_ownership(Origin::signed(1), new_org, class_id),
+      Error::<Test>::NoOwnershipOffer
+    );
+    assert_ok!(NonFungibleAssets::offer_ownership(
+      Origin::signed(1),
+      org,
+      class_id,
+      new_org
+    ));
+    assert_eq!(OwnershipOffers::<Test>::get(class_id), Some(new_org));
+    // only the organization the ownership offered to can accept it
+    assert_noop!(
+      NonFungibleAssets::accept_ownership(Origin::signed(1), 4, class_id),
+      Error::<Test>::NoOwnershipOffer
+    );
+
+    System::reset_events();
+    assert_ok!(NonFungibleAssets::accept_ownership(
+      Origin::signed(1),
+      new_org,
+      class_id
+    ));
+    assert_eq!(Classes::<Test>::get(class_id).unwrap().owner, new_org);
+    assert!(ClassAccounts::<Test>::get(org, class_id).is_none());
+    assert!(ClassAccounts::<Test>::get(new_org, class_id).is_some());
+    assert!(OwnershipOffers::<Test>::get(class_id).is_none());
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::OwnerChanged {
+          class_id,
+          old_owner: org,
+          new_owner: new_org,
+        }
+        .into(),
+        topics: vec![],
+      }]
+    );
+    // the previous owner can't manage the class anymore
+    assert_noop!(
+      NonFungibleAssets::destroy(Origin::signed(1), org, class_id),
+      Error::<Test>::NoPermission
+    );
+  });
+}
+
+#[test]
+fn class_ownership_offer_cancel_works() {
+  new_test_ext().execute_with(|| {
+    let (org, new_org, acc) = (2, 3, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+
+    assert_noop!(
+      NonFungibleAssets::cancel_ownership_offer(Origin::signed(1), org, class_id),
+      Error::<Test>::NoOwnershipOffer
+    );
+    assert_ok!(NonFungibleAssets::offer_ownership(
+      Origin::signed(1),
+      org,
+      class_id,
+      new_org
+    ));
+    assert_noop!(
+      NonFungibleAssets::cancel_ownership_offer(Origin::signed(1), new_org, class_id),
+      Error::<Test>::NoPermission
+    );
+    assert_ok!(NonFungibleAssets::cancel_ownership_offer(
+      Origin::signed(1),
+      org,
+      class_id
+    ));
+    assert_noop!(
+      NonFungibleAssets::accept_ownership(Origin::signed(1), new_org, class_id),
+      Error::<Test>::NoOwnershipOffer
+    );
+    assert_eq!(Classes::<Test>::get(class_id).unwrap().owner, org);
+  });
+}
+
+#[test]
+fn attribute_schemas_work() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    let key: AttributeKey = br"power".to_vec().try_into().unwrap();
+    let schema = AttributeSchema {
+      value_type: AttributeType::Number,
+      required: true,
+      min: Some(1),
+      max: Some(100),
+    };
+    assert_noop!(
+      NonFungibleAssets::set_attribute_schema(
+        Origin::signed(1),
+        3,
+        class_id,
+        key.clone(),
+        schema.clone()
+      ),
+      Error::<Test>::NoPermission
+    );
+    assert_noop!(
+      NonFungibleAssets::set_attribute_schema(
+        Origin::signed(1),
+        org,
+        class_id,
+        key.clone(),
+        AttributeSchema {
+          min: Some(100),
+          max: Some(1),
+          ..schema.clone()
+        }
+      ),
+      Error::<Test>::WrongAttributeSchema
+    );
+    System::reset_events();
+    assert_ok!(NonFungibleAssets::set_attribute_schema(
+      Origin::signed(1),
+      org,
+      class_id,
+      key.clone(),
+      schema.clone()
+    ));
+    assert_eq!(
+      AttributeSchemas::<Test>::get(class_id, &key),
+      Some(schema.clone())
+    );
+    assert_eq!(
+      System::events(),
+      vec![EventRecord {
+        phase: Phase::Initialization,
+        event: NfaEvent::AttributeSchemaSet {
+          class_id,
+          key: key.clone(),
+          schema,
+        }
+        .into(),
+        topics: vec![],
+      }]
+    );
+
+    assert_noop!(
+      NonFungibleAssets::remove_attribute_schema(
+        Origin::signed(1),
+        org,
+        class_id,
+        br"other".to_vec().try_into().unwrap()
+      ),
+      Error::<Test>::UnknownAttributeSchema
+    );
+    assert_ok!(NonFungibleAssets::remove_attribute_schema(
+      Origin::signed(1),
+      org,
+      class_id,
+      key.clone()
+    ));
+    assert_eq!(AttributeSchemas::<Test>::get(class_id, &key), None);
+  });
+}
+
+#[test]
+fn set_attributes_meets_schema() {
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, asset_id) = create_class_with_asset(org, acc);
+    let attributes = |power: u32, name: &str| -> AttributeList {
+      vec![
+        Attribute {
+          key: br"power".to_vec().try_into().unwrap(),
+          value: power.try_into().unwrap(),
+        },
+        Attribute {
+          key: br"name".to_vec().try_into().unwrap(),
+          value: name.try_into().unwrap(),
+        },
+      ]
+      .try_into()
+      .unwrap()
+    };
+    // any attributes are allowed without a schema
+    assert_ok!(NonFungibleAssets::set_attributes(
+      &class_id,
+      &asset_id,
+      attributes(1000, "name")
+    ));
+
+    assert_ok!(NonFungibleAssets::do_set_attribute_schema(
+      class_id,
+      None,
+      br"power".to_vec().try_into().unwrap(),
+      AttributeSchema {
+        value_type: AttributeType::Number,
+        required: true,
+        min: None,
+        max: Some(100),
+      }
+    ));
+    assert_noop!(
+      NonFungibleAssets::validate_attributes(&class_id, &attributes(10, "name")),
+      Error::<Test>::AttributeNotInSchema
+    );
+    assert_ok!(NonFungibleAssets::do_set_attribute_schema(
+      class_id,
+      None,
+      br"name".to_vec().try_into().unwrap(),
+      AttributeSchema {
+        value_type: AttributeType::Text,
+        required: false,
+        min: Some(1),
+        max: Some(8),
+      }
+    ));
+    assert_ok!(NonFungibleAssets::validate_attributes(
+      &class_id,
+      &attributes(10, "name")
+    ));
+    assert_noop!(
+      NonFungibleAssets::validate_attributes(&class_id, &attributes(101, "name")),
+      Error::<Test>::AttributeSchemaMismatch
+    );
+    assert_noop!(
+      NonFungibleAssets::validate_attributes(&class_id, &attributes(10, "long name")),
+      Error::<Test>::AttributeSchemaMismatch
+    );
+    // the required attribute is missing
+    let name_only: AttributeList = vec![attributes(10, "name")[1].clone()].try_into().unwrap();
+    assert_noop!(
+      NonFungibleAssets::validate_attributes(&class_id, &name_only),
+      Error::<Test>::RequiredAttributeMissing
+    );
+    // the asset already has the required attribute
+    assert_ok!(NonFungibleAssets::set_attributes(
+      &class_id,
+      &asset_id,
+      name_only.clone()
+    ));
+    let other_id = NonFungibleAssets::do_mint(class_id, acc).unwrap();
+    assert_noop!(
+      NonFungibleAssets::set_attributes(&class_id, &other_id, name_only),
+      Error::<Test>::RequiredAttributeMissing
+    );
+
+    // class attributes must meet the schema too
+    assert_noop!(
+      NonFungibleAssets::do_create_attribute(
+        class_id,
+        None,
+        Attribute {
+          key: br"power".to_vec().try_into().unwrap(),
+          value: "text".try_into().unwrap(),
+        }
+      ),
+      Error::<Test>::AttributeSchemaMismatch
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_attribute_template(
+        class_id,
+        None,
+        br"power".to_vec().try_into().unwrap(),
+        AttributeTemplate::NumberRange {
+          min: 50,
+          max: 150,
+          number_max: None,
+        }
+      ),
+      Error::<Test>::AttributeSchemaMismatch
+    );
+  });
+}
+
+#[test]
+fn purchased_offers_meet_schema() {
+  use pallet_support::purchased::{Offer, Purchased};
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    assert_ok!(NonFungibleAssets::do_set_attribute_schema(
+      class_id,
+      None,
+      br"power".to_vec().try_into().unwrap(),
+      AttributeSchema {
+        value_type: AttributeType::Number,
+        required: true,
+        min: None,
+        max: Some(100),
+      }
+    ));
+    let purchased = |attributes: Vec<Attribute>| {
+      Characteristic::Purchased(Some(Purchased {
+        offers: vec![Offer {
+          price: vec![(1.into(), 100.into())].try_into().unwrap(),
+          trade_ins: Default::default(),
+          attributes: attributes.try_into().unwrap(),
+          stock: None,
+          start_block: None,
+          end_block: None,
+          max_per_gamer: None,
+          payment: Default::default(),
+        }]
+        .try_into()
+        .unwrap(),
+      }))
+    };
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(class_id, None, purchased(vec![])),
+      Error::<Test>::RequiredAttributeMissing
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        purchased(vec![Attribute {
+          key: br"power".to_vec().try_into().unwrap(),
+          value: 101u32.try_into().unwrap(),
+        }])
+      ),
+      Error::<Test>::AttributeSchemaMismatch
+    );
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      purchased(vec![Attribute {
+        key: br"power".to_vec().try_into().unwrap(),
+        value: 100u32.try_into().unwrap(),
+      }])
+    ));
+  });
+}
+
+#[test]
+fn set_characteristic_checks_references() {
+  use pallet_support::{
+    bettor::{Bettor, BettorOutcome, BettorWinning, DrawOutcomeResult, OutcomeResult},
+    purchased::{Offer, Purchased},
+    CommonError,
+  };
+  new_test_ext().execute_with(|| {
+    // FA 1 is owned by the organization 2, FA 3 by the organization 3, others don't exist
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    let (foreign_class_id, _) = create_class_with_asset(3, acc);
+    let bettor = |winning| {
+      Characteristic::Bettor(Some(Bettor {
+        outcomes: vec![
+          BettorOutcome {
+            name: br"o1".to_vec().try_into().unwrap(),
+            probability: 1,
+            result: OutcomeResult::Win,
+          },
+          BettorOutcome {
+            name: br"o2".to_vec().try_into().unwrap(),
+            probability: 1,
+            result: OutcomeResult::Lose,
+          },
+        ]
+        .try_into()
+        .unwrap(),
+        winnings: vec![winning].try_into().unwrap(),
+        rounds: 1,
+        draw_outcome: DrawOutcomeResult::Keep,
+      }))
+    };
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        bettor(BettorWinning::Fa(1.into(), 0.into()))
+      ),
+      Error::<Test>::WrongBettor
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        bettor(BettorWinning::Fa(5.into(), 10.into()))
+      ),
+      Error::<Test>::CommonError(CommonError::UnknownReferencedAsset)
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        bettor(BettorWinning::Fa(3.into(), 10.into()))
+      ),
+      Error::<Test>::CommonError(CommonError::ForeignReferencedAsset)
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        bettor(BettorWinning::Nfa(100.into()))
+      ),
+      Error::<Test>::CommonError(CommonError::UnknownReferencedAsset)
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        bettor(BettorWinning::Nfa(foreign_class_id))
+      ),
+      Error::<Test>::CommonError(CommonError::ForeignReferencedAsset)
+    );
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      bettor(BettorWinning::Fa(1.into(), 10.into()))
+    ));
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      bettor(BettorWinning::Nfa(class_id))
+    ));
+
+    let purchased = |fa: u32, trade_in: NonFungibleClassId| {
+      Characteristic::Purchased(Some(Purchased {
+        offers: vec![Offer {
+          price: vec![(fa.into(), 100.into())].try_into().unwrap(),
+          trade_ins: vec![trade_in].try_into().unwrap(),
+          attributes: Default::default(),
+          stock: None,
+          start_block: None,
+          end_block: None,
+          max_per_gamer: None,
+          payment: Default::default(),
+        }]
+        .try_into()
+        .unwrap(),
+      }))
+    };
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(class_id, None, purchased(5, class_id)),
+      Error::<Test>::CommonError(CommonError::UnknownReferencedAsset)
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(class_id, None, purchased(3, class_id)),
+      Error::<Test>::CommonError(CommonError::ForeignReferencedAsset)
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(class_id, None, purchased(1, foreign_class_id)),
+      Error::<Test>::CommonError(CommonError::ForeignReferencedAsset)
+    );
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      purchased(1, class_id)
+    ));
+  });
+}
+
+#[test]
+fn characteristics_maintain_fa_references() {
+  use pallet_support::{
+    bettor::{Bettor, BettorOutcome, BettorWinning, DrawOutcomeResult, OutcomeResult},
+    purchased::{Offer, Purchased},
+  };
+  new_test_ext().execute_with(|| {
+    // FA 1 is owned by the organization 2
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    let bettor = |winning| {
+      Characteristic::Bettor(Some(Bettor {
+        outcomes: vec![
+          BettorOutcome {
+            name: br"o1".to_vec().try_into().unwrap(),
+            probability: 1,
+            result: OutcomeResult::Win,
+          },
+          BettorOutcome {
+            name: br"o2".to_vec().try_into().unwrap(),
+            probability: 1,
+            result: OutcomeResult::Lose,
+          },
+        ]
+        .try_into()
+        .unwrap(),
+        winnings: vec![winning].try_into().unwrap(),
+        rounds: 1,
+        draw_outcome: DrawOutcomeResult::Keep,
+      }))
+    };
+    let offer = Offer {
+      price: vec![(1.into(), 100.into())].try_into().unwrap(),
+      trade_ins: Default::default(),
+      attributes: Default::default(),
+      stock: None,
+      start_block: None,
+      end_block: None,
+      max_per_gamer: None,
+      payment: Default::default(),
+    };
+
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      bettor(BettorWinning::Fa(1.into(), 10.into()))
+    ));
+    assert_eq!(fa_references(1), 1);
+    // replacing the characteristic moves references
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      bettor(BettorWinning::Nfa(class_id))
+    ));
+    assert_eq!(fa_references(1), 0);
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      bettor(BettorWinning::Fa(1.into(), 10.into()))
+    ));
+    // each offer references the asset
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      Characteristic::Purchased(Some(Purchased {
+        offers: vec![offer.clone(), offer].try_into().unwrap(),
+      }))
+    ));
+    assert_eq!(fa_references(1), 3);
+    // clearing the characteristic releases references
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      Characteristic::Purchased(None)
+    ));
+    assert_eq!(fa_references(1), 1);
+    // destroying the class releases the rest
+    assert_ok!(NonFungibleAssets::do_destroy_class(class_id, None));
+    assert_eq!(fa_references(1), 0);
+  });
+}
+
+#[test]
+fn set_salvage_characteristic() {
+  use pallet_support::{
+    salvage::{Salvage, SalvageRefund},
+    CommonError,
+  };
+  new_test_ext().execute_with(|| {
+    // FA 1 is owned by the organization 2
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    let salvage = |refunds: Vec<SalvageRefund>| {
+      Characteristic::Salvage(Some(Salvage {
+        refunds: refunds.try_into().unwrap(),
+      }))
+    };
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(class_id, None, salvage(vec![])),
+      Error::<Test>::WrongSalvage
+    );
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        salvage(vec![SalvageRefund::Fa(5.into(), 10.into())])
+      ),
+      Error::<Test>::CommonError(CommonError::UnknownReferencedAsset)
+    );
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      salvage(vec![
+        SalvageRefund::Fa(1.into(), 10.into()),
+        SalvageRefund::Nfa(class_id, Default::default())
+      ])
+    ));
+    assert!(Classes::<Test>::get(class_id).unwrap().salvage.is_some());
+    assert_eq!(fa_references(1), 1);
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      Characteristic::Salvage(None)
+    ));
+    assert!(Classes::<Test>::get(class_id).unwrap().salvage.is_none());
+    assert_eq!(fa_references(1), 0);
+  });
+}
+
+#[test]
+fn royalty_characteristic_works() {
+  use pallet_support::royalty::{Royalty, RoyaltyBeneficiary};
+  use sp_runtime::Percent;
+  new_test_ext().execute_with(|| {
+    let (org, acc) = (2, 1);
+    let (class_id, _) = create_class_with_asset(org, acc);
+    assert_eq!(NonFungibleAssets::royalty_of(&class_id, 1000.into()), None);
+    assert_noop!(
+      NonFungibleAssets::do_set_characteristic(
+        class_id,
+        None,
+        Characteristic::Royalty(Some(Royalty {
+          percent: Percent::from_percent(0),
+          beneficiary: RoyaltyBeneficiary::Treasury,
+        }))
+      ),
+      Error::<Test>::WrongRoyalty
+    );
+    assert_ok!(NonFungibleAssets::set_characteristic(
+      Origin::signed(org),
+      org,
+      class_id,
+      Characteristic::Royalty(Some(Royalty {
+        percent: Percent::from_percent(5),
+        beneficiary: RoyaltyBeneficiary::Account(7),
+      }))
+    ));
+    assert_eq!(
+      NonFungibleAssets::royalty_of(&class_id, 1000.into()),
+      Some((RoyaltyBeneficiary::Account(7), 50.into()))
+    );
+    assert_ok!(NonFungibleAssets::do_set_characteristic(
+      class_id,
+      None,
+      Characteristic::Royalty(None)
+    ));
+    assert_eq!(NonFungibleAssets::royalty_of(&class_id, 1000.into()), None);
+  });
+}
+
+#[test]
+fn migration_to_v1_works() {
+  use crate::migration::v1::{MigrateToV1, OldClassDetails, OldOffer, OldPurchased};
+  use codec::Encode;
+  use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(0).put::<NonFungibleAssets>();
+    let class_id: NonFungibleClassId = 7.into();
+    let old = OldClassDetails::<u64> {
+      owner: 2,
+      instances: 1,
+      attributes: 0,
+      name: br"nfa name".to_vec().try_into().unwrap(),
+      bettor: None,
+      purchased: Some(OldPurchased {
+        offers: vec![OldOffer {
+          fa: 5.into(),
+          price: 10.into(),
+          attributes: Default::default(),
+        }]
+        .try_into()
+        .unwrap(),
+      }),
+    };
+    frame_support::storage::unhashed::put_raw(
+      &Classes::<Test>::hashed_key_for(class_id),
+      &old.encode(),
+    );
+
+    MigrateToV1::<Test>::on_runtime_upgrade();
+
+    let details = Classes::<Test>::get(class_id).unwrap();
+    assert_eq!(details.owner, 2);
+    assert_eq!(details.instances, 1);
+    assert_eq!(details.transferability, Transferability::Transferable);
+    assert_eq!(details.upgradable, None);
+    assert_eq!(details.royalty, None);
+    let offer = &details.purchased.unwrap().offers[0];
+    assert_eq!(
+      offer.price.clone().into_inner(),
+      vec![(5.into(), 10.into())]
+    );
+    assert_eq!(offer.payment, purchased::PaymentRouting::Burn);
+    assert_eq!(NonFungibleAssets::on_chain_storage_version(), 1);
+  });
+}
@@ -1,6 +1,7 @@
 use super::*;
 use pallet_support::{
-  AssetCharacteristic, CharacteristicBettor, CharacteristicPurchased, DefaultStringLimit, Locker,
+  transferability::Transferability, AssetCharacteristic, CharacteristicBettor,
+  CharacteristicPurchased, DefaultStringLimit, Locker, MetadataStringLimit,
 };
 
 /// Type of the non-fungible asset instance ids
@@ -11,6 +12,17 @@ pub type NonFungibleClassId = pallet_support::NonFungibleClassId;
 pub type FungibleAssetId = pallet_support::FungibleAssetId;
 /// The units in which we record balances of the fungible assets
 pub type FungibleAssetBalance = pallet_support::FungibleAssetBalance;
+/// Metadata of the class or the asset instance. Limited in length by `MetadataStringLimit`
+pub type Metadata = BoundedVec<u8, MetadataStringLimit>;
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// An approval of the asset owner to use the asset by a delegate
+pub struct Approval<AccountId, BlockNumber> {
+  /// The account which is allowed to lock the asset in mechanics or to transfer it once
+  pub delegate: AccountId,
+  /// The last block at which the approval is valid. If `None`, the approval never expires
+  pub deadline: Option<BlockNumber>,
+}
 
 // region: Builders
 #[cfg_attr(test, derive(Debug, PartialEq))]
@@ -19,6 +31,7 @@ pub struct ClassDetailsBuilder<T: Config> {
   name: BoundedVec<u8, DefaultStringLimit>,
   bettor: CharacteristicBettor,
   purchased: CharacteristicPurchased,
+  transferability: Transferability,
 }
 impl<T: pallet::Config> ClassDetailsBuilder<T> {
   pub fn new(owner: T::AccountId, name: Vec<u8>) -> ClassDetailsBuilderResult<T> {
@@ -32,6 +45,7 @@ impl<T: pallet::Config> ClassDetailsBuilder<T> {
       name,
       bettor: None,
       purchased: None,
+      transferability: Default::default(),
     })
   }
 
@@ -56,6 +70,15 @@ impl<T: pallet::Config> ClassDetailsBuilder<T> {
     Ok(self)
   }
 
+  /// Set the Transferability chracteristic of the NFA
+  pub fn transferability(
+    mut self,
+    transferability: Transferability,
+  ) -> ClassDetailsBuilderResult<T> {
+    self.transferability = transferability;
+    Ok(self)
+  }
+
   /// Validation of the all class details.
   fn validate(&self) -> DispatchResult {
     Ok(())
@@ -70,6 +93,11 @@ impl<T: pallet::Config> ClassDetailsBuilder<T> {
       attributes: Zero::zero(),
       bettor: None,
       purchased: None,
+      transferability: self.transferability,
+      upgradable: None,
+      lootbox: None,
+      salvage: None,
+      royalty: None,
     })
   }
 }
@@ -94,6 +122,7 @@ impl<T: pallet::Config> AssetDetailsBuilder<T> {
 type ClassDetailsBuilderResult<T> = DispatchResultAs<ClassDetailsBuilder<T>>;
 type AssetDetailsBuilderResult<T> = DispatchResultAs<AssetDetailsBuilder<T>>;
 pub type ClassDetailsOf<T> = ClassDetails<AccountIdOf<T>>;
+pub type ApprovalOf<T> = Approval<AccountIdOf<T>, BlockNumberFor<T>>;
 
 // region: Genesis Types
 pub type GenesisClassesConfigOf<T> = Vec<(NonFungibleClassId, AccountIdOf<T>, Vec<u8>)>;
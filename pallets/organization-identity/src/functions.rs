@@ -13,12 +13,30 @@ impl<T: Config> Pallet<T> {
     assets: OnboardingAssets,
   ) -> DispatchResultWithPostInfo {
     let mut details = Organizations::<T>::get(target).ok_or(Error::<T>::NotOrganization)?;
+    // airdropped assets must meet the attribute schemas of their classes
+    if let Some(assets) = &assets {
+      for asset in assets.iter() {
+        if let AirDropAsset::Nfa(class_id, attributes) = asset {
+          T::NonFungibleAssets::validate_attributes(class_id, attributes)?;
+        }
+      }
+    }
     details.onboarding_assets = assets;
 
     Organizations::<T>::insert(target, &details);
     Ok(().into())
   }
 
+  pub(crate) fn do_set_treasury(organization_id: &T::AccountId, treasury: Option<T::AccountId>) {
+    match treasury {
+      // the organization itself is the treasury by default
+      Some(treasury) if &treasury != organization_id => {
+        Treasuries::<T>::insert(organization_id, treasury)
+      },
+      _ => Treasuries::<T>::remove(organization_id),
+    }
+  }
+
   pub(crate) fn do_onboarding(
     organization_id: &T::AccountId,
     target: &T::AccountId,
@@ -59,7 +77,7 @@ impl<T: Config> Pallet<T> {
     // mint nfa
     let asset_id = T::NonFungibleAssets::mint_into(&class_id, target)?;
     // set attributes
-    T::NonFungibleAssets::set_attributes(&asset_id, attributes)?;
+    T::NonFungibleAssets::set_attributes(&class_id, &asset_id, attributes)?;
     Ok(().into())
   }
 }
@@ -108,6 +108,17 @@ pub mod pallet {
     ValueQuery,
   >;
 
+  #[pallet::storage]
+  /// Treasuries of organizations.
+  ///
+  /// Stores accounts which receive the funds of organizations, e.g. payments for purchases
+  pub(super) type Treasuries<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    OrganizationIdOf<T>, // account_id of the organization
+    T::AccountId,        // account id of the treasury
+  >;
+
   // Pallets use events to inform users when important changes are made.
   // https://docs.substrate.io/v3/runtime/events-and-errors
   #[pallet::event]
@@ -126,6 +137,8 @@ pub mod pallet {
     MemberRemoved(OrganizationIdOf<T>, T::AccountId),
     /// Assets for the game has been airdropped.
     Onboard(OrganizationIdOf<T>, T::AccountId),
+    /// The treasury of an organization was set. [organization, treasury]
+    TreasurySet(OrganizationIdOf<T>, Option<T::AccountId>),
   }
 
   // Errors inform users that something went wrong.
@@ -273,6 +286,30 @@ pub mod pallet {
       Ok(().into())
     }
 
+    /// Sets the account which receives the funds of an organization.
+    ///
+    /// If `treasury` is `None`, the funds are received by the organization itself.
+    ///
+    /// # Events
+    /// * `TreasurySet`
+    ///
+    /// # Errors
+    /// * `NotOrganization` if origin not an organization
+    #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+    pub fn set_treasury(
+      origin: OriginFor<T>,
+      treasury: Option<T::AccountId>,
+    ) -> DispatchResultWithPostInfo {
+      let org = ensure_signed(origin)?;
+      // only organization's account can set the treasury
+      ensure!(Self::is_organization(&org), Error::<T>::NotOrganization);
+
+      Self::do_set_treasury(&org, treasury.clone());
+
+      Self::deposit_event(Event::TreasurySet(org, treasury));
+      Ok(().into())
+    }
+
     /// Set assets which will be airdroped at game onboarding
     #[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,2))]
     pub fn set_onboarding_assets(
@@ -388,3 +425,9 @@ impl<T: Config> EnsureOrigin<T::Origin> for EnsureUser<T> {
     T::Origin::from(RawOrigin::Signed(Default::default()))
   }
 }
+
+impl<T: Config> pallet_support::traits::OrganizationTreasury<T::AccountId> for Pallet<T> {
+  fn treasury_of(organization_id: &T::AccountId) -> T::AccountId {
+    Treasuries::<T>::get(organization_id).unwrap_or_else(|| organization_id.clone())
+  }
+}
@@ -57,6 +57,10 @@ impl system::Config for Test {
 /// Mock of fungible-assets-pallet impl
 pub struct FAPallet {}
 impl pallet_support::traits::FungibleAssets<u64> for FAPallet {
+  fn owner_of(_asset: &FungibleAssetId) -> Option<u64> {
+    todo!()
+  }
+
   fn can_withdraw(
     _asset: FungibleAssetId,
     _who: &u64,
@@ -73,6 +77,31 @@ impl pallet_support::traits::FungibleAssets<u64> for FAPallet {
     todo!()
   }
 
+  fn transfer(
+    _asset_id: FungibleAssetId,
+    _source: &u64,
+    _dest: &u64,
+    _amount: FungibleAssetBalance,
+  ) -> pallet_support::DispatchResultAs<FungibleAssetBalance> {
+    todo!()
+  }
+
+  fn reserve(
+    _asset_id: FungibleAssetId,
+    _who: &u64,
+    _amount: FungibleAssetBalance,
+  ) -> sp_runtime::DispatchResult {
+    todo!()
+  }
+
+  fn unreserve(
+    _asset_id: FungibleAssetId,
+    _who: &u64,
+    _amount: FungibleAssetBalance,
+  ) -> pallet_support::DispatchResultAs<FungibleAssetBalance> {
+    todo!()
+  }
+
   fn inc_references(_asset: &FungibleAssetId) -> sp_runtime::DispatchResult {
     Ok(())
   }
@@ -130,15 +159,12 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
   fn get_offer(
     _class_id: &pallet_support::NonFungibleClassId,
     _offer_id: &u32,
-  ) -> pallet_support::DispatchResultAs<(
-    pallet_support::FungibleAssetId,
-    pallet_support::FungibleAssetBalance,
-    pallet_support::AttributeList,
-  )> {
+  ) -> pallet_support::DispatchResultAs<pallet_support::purchased::Offer> {
     todo!()
   }
 
   fn set_attributes(
+    _class_id: &pallet_support::NonFungibleClassId,
     asset_id: &pallet_support::NonFungibleAssetId,
     attributes: pallet_support::AttributeList,
   ) -> frame_support::dispatch::DispatchResult {
@@ -158,6 +184,16 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
     }
     todo!()
   }
+  fn validate_attributes(
+    class_id: &pallet_support::NonFungibleClassId,
+    _attributes: &pallet_support::AttributeList,
+  ) -> frame_support::dispatch::DispatchResult {
+    if class_id == &14.into() {
+      // test do_set_onboarding_assets_works
+      return Err(DispatchError::Other("mock_validate_attributes"));
+    }
+    Ok(())
+  }
   fn try_lock(
     _who: &u64,
     _origin: Locker<u64, u32>,
@@ -184,6 +220,30 @@ impl pallet_support::traits::NonFungibleAssets<u64, u32> for NFAPallet {
   ) -> sp_runtime::DispatchResult {
     todo!()
   }
+  fn transfer(
+    _class_id: &NonFungibleClassId,
+    _asset_id: &NonFungibleAssetId,
+    _maybe_check_owner: Option<&u64>,
+    _dest: &u64,
+  ) -> sp_runtime::DispatchResult {
+    todo!()
+  }
+  fn ensure_transferable(_class_id: &NonFungibleClassId) -> sp_runtime::DispatchResult {
+    todo!()
+  }
+  fn get_asset(
+    _class_id: &NonFungibleClassId,
+    _asset_id: &NonFungibleAssetId,
+  ) -> DispatchResultAs<pallet_support::types_nfa::AssetDetails<u64, u32>> {
+    todo!()
+  }
+  fn get_attribute(
+    _class_id: &NonFungibleClassId,
+    _asset_id: &NonFungibleAssetId,
+    _key: &pallet_support::AttributeKey,
+  ) -> Option<pallet_support::AttributeValue> {
+    todo!()
+  }
 }
 
 impl pallet_organization_identity::Config for Test {
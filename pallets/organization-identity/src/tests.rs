@@ -1,10 +1,10 @@
 use crate::{
   mock::*, AirDropAsset, Error, Event as OrgEvent, Members, OnboardingAssets, Organizations,
-  UsersOf,
+  Treasuries, UsersOf,
 };
 use frame_support::{assert_noop, assert_ok};
 use frame_system::{ensure_signed, EventRecord, Phase};
-use pallet_support::Attribute;
+use pallet_support::{traits::OrganizationTreasury, Attribute};
 use sp_runtime::DispatchError;
 
 #[macro_export]
@@ -233,6 +233,15 @@ fn do_set_onboarding_assets_works() {
       assets
     );
 
+    // attributes of airdropped assets must meet the schema
+    assert_noop!(
+      OrganizationIdentity::do_set_onboarding_assets(
+        &1,
+        Some(bvec![AirDropAsset::Nfa(14.into(), bvec![])])
+      ),
+      DispatchError::Other("mock_validate_attributes")
+    );
+
     assert_ok!(OrganizationIdentity::do_set_onboarding_assets(&1, None));
     assert_eq!(
       Organizations::<Test>::get(&1).unwrap().onboarding_assets,
@@ -509,3 +518,35 @@ fn onboarding_works_with_empty_airdrops() {
     );
   })
 }
+
+#[test]
+fn set_treasury_works() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(OrganizationIdentity::create_organization(
+      Origin::signed(1),
+      br"some name".to_vec()
+    ));
+    // the organization itself is the treasury by default
+    assert_eq!(OrganizationIdentity::treasury_of(&1), 1);
+
+    assert_noop!(
+      OrganizationIdentity::set_treasury(Origin::signed(2), Some(3)),
+      Error::<Test>::NotOrganization
+    );
+
+    assert_ok!(OrganizationIdentity::set_treasury(
+      Origin::signed(1),
+      Some(3)
+    ));
+    assert_eq!(Treasuries::<Test>::get(1), Some(3));
+    assert_eq!(OrganizationIdentity::treasury_of(&1), 3);
+    assert_eq!(
+      System::events().last().unwrap().event,
+      OrgEvent::TreasurySet(1, Some(3)).into()
+    );
+
+    assert_ok!(OrganizationIdentity::set_treasury(Origin::signed(1), None));
+    assert_eq!(Treasuries::<Test>::get(1), None);
+    assert_eq!(OrganizationIdentity::treasury_of(&1), 1);
+  })
+}
@@ -1,11 +1,19 @@
 use crate::errors::CommonError;
 
-use self::{bettor::Bettor, purchased::Purchased};
+use self::{
+  bettor::Bettor, lootbox::Lootbox, purchased::Purchased, royalty::Royalty, salvage::Salvage,
+  transferability::Transferability, upgradable::Upgradable,
+};
 
 use super::*;
 
 pub mod bettor;
+pub mod lootbox;
 pub mod purchased;
+pub mod royalty;
+pub mod salvage;
+pub mod transferability;
+pub mod upgradable;
 
 pub trait AssetCharacteristic {
   fn is_valid(&self) -> bool;
@@ -16,14 +24,63 @@ pub trait AssetCharacteristic {
     }
     Ok(())
   }
+  /// Check that the assets referenced by the characteristic exist and belong to the
+  /// `organization_id`
+  fn ensure_references<AccountId: PartialEq, C: ValidationContext<AccountId>>(
+    &self,
+    _organization_id: &AccountId,
+  ) -> Result<(), CommonError> {
+    Ok(())
+  }
+  /// Returns fungible assets referenced by the characteristic, one item per reference
+  fn fa_references(&self) -> Vec<FungibleAssetId> {
+    Vec::new()
+  }
+}
+
+/// Gives characteristics access to the assets they reference
+pub trait ValidationContext<AccountId: PartialEq> {
+  /// Returns the owner of the fungible asset or `None` if the asset doesn't exist
+  fn fa_owner(asset_id: &FungibleAssetId) -> Option<AccountId>;
+  /// Returns the owner of the non-fungible asset class or `None` if the class doesn't exist
+  fn nfa_class_owner(class_id: &NonFungibleClassId) -> Option<AccountId>;
+
+  /// Check that the fungible asset exists and belongs to the `organization_id`
+  fn ensure_fa(asset_id: &FungibleAssetId, organization_id: &AccountId) -> Result<(), CommonError> {
+    match Self::fa_owner(asset_id) {
+      None => Err(CommonError::UnknownReferencedAsset),
+      Some(owner) if &owner != organization_id => Err(CommonError::ForeignReferencedAsset),
+      Some(_) => Ok(()),
+    }
+  }
+  /// Check that the non-fungible asset class exists and belongs to the `organization_id`
+  fn ensure_nfa_class(
+    class_id: &NonFungibleClassId,
+    organization_id: &AccountId,
+  ) -> Result<(), CommonError> {
+    match Self::nfa_class_owner(class_id) {
+      None => Err(CommonError::UnknownReferencedAsset),
+      Some(owner) if &owner != organization_id => Err(CommonError::ForeignReferencedAsset),
+      Some(_) => Ok(()),
+    }
+  }
 }
 
 /// Represent a some single characteristic of NFA class
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub enum Characteristic {
+pub enum Characteristic<AccountId> {
   Bettor(CharacteristicBettor),
   Purchased(CharacteristicPurchased),
+  Transferability(Transferability),
+  Upgradable(CharacteristicUpgradable),
+  Lootbox(CharacteristicLootbox),
+  Salvage(CharacteristicSalvage),
+  Royalty(CharacteristicRoyalty<AccountId>),
 }
 
 pub type CharacteristicBettor = Option<Bettor>;
 pub type CharacteristicPurchased = Option<Purchased>;
+pub type CharacteristicUpgradable = Option<Upgradable>;
+pub type CharacteristicLootbox = Option<Lootbox>;
+pub type CharacteristicSalvage = Option<Salvage>;
+pub type CharacteristicRoyalty<AccountId> = Option<Royalty<AccountId>>;
@@ -3,6 +3,7 @@ use super::*;
 
 /// Parameters of the Bettor Characteristic
 #[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Bettor {
   /// Options of outcomes
   pub outcomes: Outcomes,
@@ -43,18 +44,15 @@ impl AssetCharacteristic for Bettor {
       return false;
     }
 
-    // Assets must exist and FAs should have amount more than 0
-    // for winning in self.winnings.iter() {
-    // TODO: check asset existence
-    // TODO: FAs should have amount more than 0
-
     // FAs should have amount more than 0
-    // if let BettorWinning::FA(id, amount) = winning {
-    //   if amount == Zero::zero() {
-    //     return false
-    //   }2
-    // }
-    // }
+    // The existence of assets is checked by `ensure_references`
+    for winning in self.winnings.iter() {
+      if let BettorWinning::Fa(_, amount) = winning {
+        if amount.is_zero() {
+          return false;
+        }
+      }
+    }
     true
   }
   fn ensure(&self) -> Result<(), CommonError> {
@@ -63,9 +61,32 @@ impl AssetCharacteristic for Bettor {
     }
     Ok(())
   }
+  fn ensure_references<AccountId: PartialEq, C: ValidationContext<AccountId>>(
+    &self,
+    organization_id: &AccountId,
+  ) -> Result<(), CommonError> {
+    for winning in self.winnings.iter() {
+      match winning {
+        BettorWinning::Fa(asset_id, _) => C::ensure_fa(asset_id, organization_id)?,
+        BettorWinning::Nfa(class_id) => C::ensure_nfa_class(class_id, organization_id)?,
+      }
+    }
+    Ok(())
+  }
+  fn fa_references(&self) -> Vec<FungibleAssetId> {
+    self
+      .winnings
+      .iter()
+      .filter_map(|winning| match winning {
+        BettorWinning::Fa(asset_id, _) => Some(*asset_id),
+        BettorWinning::Nfa(_) => None,
+      })
+      .collect()
+  }
 }
 
 #[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct BettorOutcome {
   /// Name of the outcome
   pub name: BoundedVec<u8, DefaultStringLimit>,
@@ -77,6 +98,7 @@ pub struct BettorOutcome {
 
 /// A type of the asset with given params of winning results
 #[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum BettorWinning {
   /// Fungible asset \
   /// Represented as (FA id, amount)
@@ -87,6 +109,7 @@ pub enum BettorWinning {
 }
 
 #[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum DrawOutcomeResult {
   Win,
   Lose,
@@ -94,6 +117,7 @@ pub enum DrawOutcomeResult {
 }
 
 #[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum OutcomeResult {
   Win,
   Lose,
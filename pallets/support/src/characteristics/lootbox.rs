@@ -0,0 +1,114 @@
+//! The Lootbox Characteristic code
+use super::*;
+
+/// Parameters of the Lootbox Characteristic
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Lootbox {
+  /// Weighted table of the drops
+  pub drops: BoundedVec<LootboxDrop, DefaultListLengthLimit>,
+  /// Number of drops received by opening of one instance
+  pub rolls: u32,
+  /// Guarantees a rare drop after the number of openings without it
+  pub pity: Option<LootboxPity>,
+}
+
+impl AssetCharacteristic for Lootbox {
+  fn is_valid(&self) -> bool {
+    // number of drops must be more than 0
+    if self.drops.is_empty() {
+      return false;
+    }
+    // weights of drops must be more than 0
+    if self.drops.iter().any(|d| d.weight == 0) {
+      return false;
+    }
+    // the total weight of drops must fit u32 to be rolled
+    if self
+      .drops
+      .iter()
+      .try_fold(0u32, |acc, d| acc.checked_add(d.weight))
+      .is_none()
+    {
+      return false;
+    }
+    // FAs should have amount more than 0
+    // The existence of assets is checked by `ensure_references`
+    if self.drops.iter().any(|d| match d.reward {
+      LootboxReward::Fa(_, amount) => amount.is_zero(),
+      LootboxReward::Nfa(_, _) => false,
+    }) {
+      return false;
+    }
+    // rolls must be in [1..LOOTBOX_MAX_ROLLS]
+    if self.rolls < 1 || self.rolls > LOOTBOX_MAX_ROLLS {
+      return false;
+    }
+    // the pity counter needs at least one rare drop
+    if let Some(pity) = &self.pity {
+      if pity.threshold == 0 || !self.drops.iter().any(|d| d.rare) {
+        return false;
+      }
+    }
+    true
+  }
+  fn ensure(&self) -> Result<(), CommonError> {
+    if !self.is_valid() {
+      return Err(CommonError::WrongLootbox);
+    }
+    Ok(())
+  }
+  fn ensure_references<AccountId: PartialEq, C: ValidationContext<AccountId>>(
+    &self,
+    organization_id: &AccountId,
+  ) -> Result<(), CommonError> {
+    for drop in self.drops.iter() {
+      match &drop.reward {
+        LootboxReward::Fa(asset_id, _) => C::ensure_fa(asset_id, organization_id)?,
+        LootboxReward::Nfa(class_id, _) => C::ensure_nfa_class(class_id, organization_id)?,
+      }
+    }
+    Ok(())
+  }
+  fn fa_references(&self) -> Vec<FungibleAssetId> {
+    self
+      .drops
+      .iter()
+      .filter_map(|drop| match drop.reward {
+        LootboxReward::Fa(asset_id, _) => Some(asset_id),
+        LootboxReward::Nfa(_, _) => None,
+      })
+      .collect()
+  }
+}
+
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// A drop of the Lootbox Characteristic
+pub struct LootboxDrop {
+  /// The weight of the drop in the table
+  pub weight: u32,
+  /// Is the drop counted as rare by the pity counter
+  pub rare: bool,
+  pub reward: LootboxReward,
+}
+
+/// A reward of the lootbox drop
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum LootboxReward {
+  /// Fungible asset \
+  /// Represented as (FA id, amount)
+  Fa(FungibleAssetId, FungibleAssetBalance),
+  /// Non-fungible asset \
+  /// Represented as (NFA class id, attributes of the minted asset)
+  Nfa(NonFungibleClassId, AttributeList),
+}
+
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// Parameters of the pity counter
+pub struct LootboxPity {
+  /// The number of openings after which a rare drop is guaranteed
+  pub threshold: u32,
+}
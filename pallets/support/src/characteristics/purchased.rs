@@ -1,7 +1,9 @@
 //! The Purchased Characteristic code
 use super::*;
+use sp_runtime::Percent;
 
 #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 /// Parameters of the Purchased Characteristic
 pub struct Purchased {
   pub offers: BoundedVec<Offer, DefaultListLengthLimit>,
@@ -14,10 +16,14 @@ impl AssetCharacteristic for Purchased {
       return false;
     }
     // price must be more than 0
-    if self.offers.iter().any(|o| o.price.is_zero()) {
+    if self.offers.iter().any(|o| !o.price_is_valid()) {
       return false;
     }
-    // TODO: check for the existence of an FA
+    // limits must allow at least one purchase
+    if self.offers.iter().any(|o| !o.limits_are_valid()) {
+      return false;
+    }
+    // The existence of assets is checked by `ensure_references`
     // TODO: check that no attributes with default values (like in class)
     true
   }
@@ -27,12 +33,131 @@ impl AssetCharacteristic for Purchased {
     }
     Ok(())
   }
+  fn ensure_references<AccountId: PartialEq, C: ValidationContext<AccountId>>(
+    &self,
+    organization_id: &AccountId,
+  ) -> Result<(), CommonError> {
+    for offer in self.offers.iter() {
+      for (asset_id, _) in offer.price.iter() {
+        C::ensure_fa(asset_id, organization_id)?;
+      }
+      for class_id in offer.trade_ins.iter() {
+        C::ensure_nfa_class(class_id, organization_id)?;
+      }
+    }
+    Ok(())
+  }
+  fn fa_references(&self) -> Vec<FungibleAssetId> {
+    self
+      .offers
+      .iter()
+      .flat_map(|offer| offer.price.iter().map(|(asset_id, _)| *asset_id))
+      .collect()
+  }
 }
 
 #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 /// An offer of the Purchased Characteristic
 pub struct Offer {
-  pub fa: FungibleAssetId,
-  pub price: FungibleAssetBalance,
+  /// Amounts of fungible assets paid for the asset
+  pub price: BoundedVec<(FungibleAssetId, FungibleAssetBalance), OfferItemsLimit>,
+  /// Classes of non-fungible assets traded in for the asset, an instance of each class
+  pub trade_ins: BoundedVec<NonFungibleClassId, OfferItemsLimit>,
   pub attributes: AttributeList,
+  /// The total number of assets which can be sold by the offer
+  pub stock: Option<u32>,
+  /// The block number from which the offer is on sale
+  pub start_block: Option<u32>,
+  /// The last block number at which the offer is on sale
+  pub end_block: Option<u32>,
+  /// The maximum number of assets which can be bought by one gamer
+  pub max_per_gamer: Option<u32>,
+  /// Where the fungible assets paid for the asset go
+  pub payment: PaymentRouting,
+}
+
+impl Offer {
+  fn price_is_valid(&self) -> bool {
+    // the asset can't be free
+    if self.price.is_empty() && self.trade_ins.is_empty() {
+      return false;
+    }
+    // each amount must be more than 0
+    if self.price.iter().any(|(_, amount)| amount.is_zero()) {
+      return false;
+    }
+    // each fungible asset must be paid once
+    self
+      .price
+      .iter()
+      .enumerate()
+      .all(|(i, (fa, _))| self.price.iter().skip(i + 1).all(|(other, _)| other != fa))
+  }
+
+  fn limits_are_valid(&self) -> bool {
+    if self.stock == Some(0) || self.max_per_gamer == Some(0) {
+      return false;
+    }
+    if let (Some(start_block), Some(end_block)) = (self.start_block, self.end_block) {
+      if start_block > end_block {
+        return false;
+      }
+    }
+    true
+  }
+
+  /// Checks that the offer is on sale at the `block_number`
+  pub fn is_on_sale(&self, block_number: u32) -> Result<(), OfferUnavailable> {
+    if matches!(self.start_block, Some(start_block) if block_number < start_block) {
+      return Err(OfferUnavailable::NotStarted);
+    }
+    if matches!(self.end_block, Some(end_block) if block_number > end_block) {
+      return Err(OfferUnavailable::Ended);
+    }
+    Ok(())
+  }
+}
+
+/// The reason why the offer can't be bought
+#[derive(RuntimeDebug, PartialEq, Eq)]
+pub enum OfferUnavailable {
+  /// The sale of the offer hasn't started yet
+  NotStarted,
+  /// The sale of the offer is over
+  Ended,
+}
+
+#[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// Routing of the fungible assets paid for a purchase
+pub enum PaymentRouting {
+  /// The payment is burned
+  Burn,
+  /// The payment is moved to the treasury of the organization
+  Treasury,
+  /// The given part of the payment is moved to the treasury of the organization,
+  /// the rest is burned
+  Split(Percent),
+}
+
+impl Default for PaymentRouting {
+  fn default() -> Self {
+    PaymentRouting::Burn
+  }
+}
+
+impl PaymentRouting {
+  /// Splits the `amount` to the parts which are burned and moved to the treasury
+  pub fn split(
+    &self,
+    amount: FungibleAssetBalance,
+  ) -> (FungibleAssetBalance, FungibleAssetBalance) {
+    let to_treasury: FungibleAssetBalance = match self {
+      PaymentRouting::Burn => Zero::zero(),
+      PaymentRouting::Treasury => amount,
+      PaymentRouting::Split(part) => part.mul_floor(*amount).into(),
+    };
+    (amount.saturating_sub(&to_treasury), to_treasury)
+  }
 }
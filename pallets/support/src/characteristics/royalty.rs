@@ -0,0 +1,43 @@
+//! The Royalty Characteristic code
+use super::*;
+use sp_runtime::Percent;
+
+/// Parameters of the Royalty Characteristic  \
+/// Defines the share of the price paid to the beneficiary when an asset of the class is sold
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Royalty<AccountId> {
+  /// The share of the price
+  pub percent: Percent,
+  pub beneficiary: RoyaltyBeneficiary<AccountId>,
+}
+
+impl<AccountId> AssetCharacteristic for Royalty<AccountId> {
+  fn is_valid(&self) -> bool {
+    // the royalty must be more than 0
+    !self.percent.is_zero()
+  }
+  fn ensure(&self) -> Result<(), CommonError> {
+    if !self.is_valid() {
+      return Err(CommonError::WrongRoyalty);
+    }
+    Ok(())
+  }
+}
+
+impl<AccountId> Royalty<AccountId> {
+  /// Returns the royalty paid from the `price`
+  pub fn amount(&self, price: FungibleAssetBalance) -> FungibleAssetBalance {
+    self.percent.mul_floor(*price).into()
+  }
+}
+
+/// Receiver of the royalty
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RoyaltyBeneficiary<AccountId> {
+  /// The treasury of the organization which owns the class
+  Treasury,
+  /// The given account
+  Account(AccountId),
+}
@@ -0,0 +1,66 @@
+//! The Salvage Characteristic code
+use super::*;
+
+/// Parameters of the Salvage Characteristic  \
+/// Defines assets refunded to the owner when the asset is burned
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Salvage {
+  /// Assets minted to the owner of the burned asset
+  pub refunds: BoundedVec<SalvageRefund, SalvageRefundsLimit>,
+}
+
+impl AssetCharacteristic for Salvage {
+  fn is_valid(&self) -> bool {
+    // number of refunds must be more than 0
+    if self.refunds.is_empty() {
+      return false;
+    }
+    // FAs should have amount more than 0
+    // The existence of assets is checked by `ensure_references`
+    !self.refunds.iter().any(|r| match r {
+      SalvageRefund::Fa(_, amount) => amount.is_zero(),
+      SalvageRefund::Nfa(_, _) => false,
+    })
+  }
+  fn ensure(&self) -> Result<(), CommonError> {
+    if !self.is_valid() {
+      return Err(CommonError::WrongSalvage);
+    }
+    Ok(())
+  }
+  fn ensure_references<AccountId: PartialEq, C: ValidationContext<AccountId>>(
+    &self,
+    organization_id: &AccountId,
+  ) -> Result<(), CommonError> {
+    for refund in self.refunds.iter() {
+      match refund {
+        SalvageRefund::Fa(asset_id, _) => C::ensure_fa(asset_id, organization_id)?,
+        SalvageRefund::Nfa(class_id, _) => C::ensure_nfa_class(class_id, organization_id)?,
+      }
+    }
+    Ok(())
+  }
+  fn fa_references(&self) -> Vec<FungibleAssetId> {
+    self
+      .refunds
+      .iter()
+      .filter_map(|refund| match refund {
+        SalvageRefund::Fa(asset_id, _) => Some(*asset_id),
+        SalvageRefund::Nfa(_, _) => None,
+      })
+      .collect()
+  }
+}
+
+/// A refund of the Salvage Characteristic
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SalvageRefund {
+  /// Fungible asset \
+  /// Represented as (FA id, amount)
+  Fa(FungibleAssetId, FungibleAssetBalance),
+  /// Non-fungible asset \
+  /// Represented as (NFA class id, attributes of the minted asset)
+  Nfa(NonFungibleClassId, AttributeList),
+}
@@ -0,0 +1,26 @@
+//! The Transferability Characteristic code
+use super::*;
+
+#[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// Defines whether assets of the class can change hands
+pub enum Transferability {
+  /// Assets can be transferred, listed on a marketplace or traded
+  Transferable,
+  /// Assets are bound to the account of the owner and never leave it.  \
+  /// They still can be burned or locked by mechanics
+  Soulbound,
+}
+
+impl Transferability {
+  /// Returns true if assets can change hands
+  pub fn is_transferable(&self) -> bool {
+    matches!(self, Transferability::Transferable)
+  }
+}
+
+impl Default for Transferability {
+  fn default() -> Self {
+    Transferability::Transferable
+  }
+}
@@ -0,0 +1,111 @@
+//! The Upgradable Characteristic code
+use super::*;
+
+/// Parameters of the Upgradable Characteristic
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Upgradable {
+  /// Key of the number attribute of the instance which holds the current level.  \
+  /// An instance without this attribute has the level 0
+  pub level_key: AttributeKey,
+  /// Upgrade levels. The element with index `n` upgrades an instance from the level `n` to `n + 1`
+  pub levels: BoundedVec<UpgradeLevel, DefaultListLengthLimit>,
+}
+
+impl AssetCharacteristic for Upgradable {
+  fn is_valid(&self) -> bool {
+    // number of levels must be more than 0
+    if self.levels.is_empty() {
+      return false;
+    }
+    // the level attribute must be named
+    if self.level_key.is_empty() {
+      return false;
+    }
+    for level in self.levels.iter() {
+      // price must be more than 0
+      // The existence of assets is checked by `ensure_references`
+      if let UpgradeCost::Fa(_, amount) = level.cost {
+        if amount.is_zero() {
+          return false;
+        }
+      }
+      // the level attribute can be changed by the level only
+      if level.deltas.iter().any(|d| d.key == self.level_key) {
+        return false;
+      }
+    }
+    true
+  }
+  fn ensure(&self) -> Result<(), CommonError> {
+    if !self.is_valid() {
+      return Err(CommonError::WrongUpgradable);
+    }
+    Ok(())
+  }
+  fn ensure_references<AccountId: PartialEq, C: ValidationContext<AccountId>>(
+    &self,
+    organization_id: &AccountId,
+  ) -> Result<(), CommonError> {
+    for level in self.levels.iter() {
+      match &level.cost {
+        UpgradeCost::Fa(asset_id, _) => C::ensure_fa(asset_id, organization_id)?,
+        UpgradeCost::Nfa(class_id) => C::ensure_nfa_class(class_id, organization_id)?,
+      }
+    }
+    Ok(())
+  }
+  fn fa_references(&self) -> Vec<FungibleAssetId> {
+    self
+      .levels
+      .iter()
+      .filter_map(|level| match level.cost {
+        UpgradeCost::Fa(asset_id, _) => Some(asset_id),
+        UpgradeCost::Nfa(_) => None,
+      })
+      .collect()
+  }
+}
+
+impl Upgradable {
+  /// Returns the maximum reachable level
+  pub fn max_level(&self) -> u32 {
+    self.levels.len() as u32
+  }
+
+  /// Returns the params of the upgrade from the `current` level.  \
+  /// Returns `None` if the maximum level is reached
+  pub fn next_level(&self, current: u32) -> Option<&UpgradeLevel> {
+    self.levels.get(current as usize)
+  }
+}
+
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// A level of the Upgradable Characteristic
+pub struct UpgradeLevel {
+  /// What the gamer pays for the upgrade
+  pub cost: UpgradeCost,
+  /// Increments of the number attributes of the instance
+  pub deltas: BoundedVec<AttributeDelta, UpgradeDeltasLimit>,
+}
+
+/// A cost of the upgrade
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum UpgradeCost {
+  /// Fungible asset \
+  /// Represented as (FA id, amount)
+  Fa(FungibleAssetId, FungibleAssetBalance),
+  /// Non-fungible asset \
+  /// Represented as (NFA class id). One instance of the class will be burned
+  Nfa(NonFungibleClassId),
+}
+
+#[derive(Clone, Encode, Decode, MaxEncodedLen, TypeInfo, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// An increment of the number attribute
+pub struct AttributeDelta {
+  pub key: AttributeKey,
+  pub delta: u32,
+}
@@ -2,6 +2,10 @@ use super::*;
 
 /// Lenght limit of the string attribute value
 pub type AttributeValueStringLimit = ConstU32<64>;
+/// Maximum number of items of the list attribute value
+pub type AttributeValueListLimit = ConstU32<8>;
+/// Maximum number of variants of the enum attribute type
+pub type AttributeEnumVariantsLimit = ConstU32<32>;
 /// Lenght limit of the string attribute key
 pub type AttributeKeyStringLimit = ConstU32<32>;
 /// Maximum capacity of attribute lists
@@ -10,10 +14,36 @@ pub type AttributeListLengthLimit = ConstU32<{ u8::MAX as u32 }>;
 pub type DefaultStringLimit = ConstU32<64>;
 /// Default length of the vec data type
 pub type DefaultListLengthLimit = ConstU32<{ u8::MAX as u32 }>;
+/// Maximum number of attributes changed by a level of the Upgradable characteristic
+pub type UpgradeDeltasLimit = ConstU32<16>;
+/// Maximum number of inputs and outputs of a crafting recipe
+pub type RecipeItemsLimit = ConstU32<16>;
+/// Maximum number of fungible assets in the price and non-fungible assets traded in of an offer
+pub type OfferItemsLimit = ConstU32<16>;
+/// Maximum number of assets refunded by burning of an asset with the Salvage characteristic
+pub type SalvageRefundsLimit = ConstU32<16>;
+/// Length limit of the metadata of NFA classes and instances (e.g. an URI of the off-chain JSON)
+pub type MetadataStringLimit = ConstU32<256>;
 
 pub(crate) const ERROR_VALIDATE_NUMBER_ATTRIBUTE: &str =
   "Attribute numeric value exceeds the maximum value";
 pub(crate) const ERROR_VALIDATE_TEXT_ATTRIBUTE: &str = "String attribute length out of bound";
+pub(crate) const ERROR_VALIDATE_FIXED_ATTRIBUTE: &str =
+  "Fixed-point attribute has too many decimal places";
+pub(crate) const ERROR_VALIDATE_ENUM_ATTRIBUTE: &str =
+  "Enum attribute variant is empty or too long";
+pub(crate) const ERROR_VALIDATE_LIST_ATTRIBUTE: &str = "List attribute length out of bound";
+pub(crate) const ERROR_VALIDATE_ATTRIBUTE_TEMPLATE: &str = "Attribute template is invalid";
+pub(crate) const ERROR_VALIDATE_ATTRIBUTE_SCHEMA: &str = "Attribute schema is invalid";
+
+/// The maximum number of decimal places of the fixed-point attribute value
+pub const FIXED_ATTRIBUTE_MAX_DECIMALS: u8 = 9;
 
 /// The maximum number of rounds accessible in Bet mechanic
 pub const BETTOR_MAX_NUMBER_OF_ROUNDS: u32 = 255;
+
+/// The maximum number of drops received by opening of one lootbox
+pub const LOOTBOX_MAX_ROLLS: u32 = 16;
+
+/// The maximum number of items returned by one page of the runtime API queries
+pub const QUERY_PAGE_SIZE_LIMIT: u32 = 256;
@@ -8,4 +8,16 @@ pub enum CommonError {
   WrongBettor,
   /// The purchased characteristic is wrong.
   WrongPurchased,
+  /// The upgradable characteristic is wrong.
+  WrongUpgradable,
+  /// The lootbox characteristic is wrong.
+  WrongLootbox,
+  /// The characteristic references an asset which doesn't exist.
+  UnknownReferencedAsset,
+  /// The characteristic references an asset of another organization.
+  ForeignReferencedAsset,
+  /// The salvage characteristic is wrong.
+  WrongSalvage,
+  /// The royalty characteristic is wrong.
+  WrongRoyalty,
 }
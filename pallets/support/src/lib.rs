@@ -6,6 +6,8 @@ use frame_support::{inherent::Vec, pallet_prelude::*};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::PalletError;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 
 // use frame_system::pallet_prelude::*;
 use sp_runtime::traits::Zero;
@@ -51,6 +53,7 @@ pub type AssetDetailsOf<T> = AssetDetails<AccountIdOf<T>, IndexOf<T>>;
 pub type LockResultOf<T> = LockResult<AccountIdOf<T>, IndexOf<T>>;
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 /// Represent a single attribute of NFA as a key and value
 pub struct Attribute {
   pub key: AttributeKey,
@@ -58,13 +61,23 @@ pub struct Attribute {
 }
 
 /// An attribute data of the asset. \
-/// Can be Number or String.
+/// Can be Number, String, Bool, Signed number, Fixed-point decimal, Enum variant or List.
+///
+/// New variants must be appended to keep the encoding of the stored values.
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum AttributeValue {
   Number(NumberAttribute),
   Text(BoundedVec<u8, AttributeValueStringLimit>),
+  Bool(bool),
+  Signed(i32),
+  Fixed(FixedAttribute),
+  /// A variant of the set defined by the class of the asset
+  Enum(AttributeKey),
+  List(BoundedVec<AttributeListItem, AttributeValueListLimit>),
 }
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct NumberAttribute {
   pub number_value: u32,
   pub number_max: Option<u32>,
@@ -125,17 +138,165 @@ impl TryFrom<Vec<u8>> for AttributeValue {
   }
 }
 
+impl TryFrom<bool> for AttributeValue {
+  type Error = &'static str;
+
+  fn try_from(value: bool) -> Result<Self, Self::Error> {
+    Ok(AttributeValue::Bool(value))
+  }
+}
+impl TryFrom<i32> for AttributeValue {
+  type Error = &'static str;
+
+  fn try_from(value: i32) -> Result<Self, Self::Error> {
+    Ok(AttributeValue::Signed(value))
+  }
+}
+impl TryFrom<(i64, u8)> for AttributeValue {
+  type Error = &'static str;
+
+  /// Creates fixed-point value from the `(value, decimals)` pair, i.e. `(125, 1)` is `12.5`
+  fn try_from(value: (i64, u8)) -> Result<Self, Self::Error> {
+    let value = FixedAttribute {
+      value: value.0,
+      decimals: value.1,
+    };
+    if value.is_valid() {
+      Ok(AttributeValue::Fixed(value))
+    } else {
+      Err(ERROR_VALIDATE_FIXED_ATTRIBUTE)
+    }
+  }
+}
+impl TryFrom<Vec<AttributeListItem>> for AttributeValue {
+  type Error = &'static str;
+
+  fn try_from(value: Vec<AttributeListItem>) -> Result<Self, Self::Error> {
+    match value.try_into() {
+      Ok(val) => Ok(AttributeValue::List(val)),
+      Err(_) => Err(ERROR_VALIDATE_LIST_ATTRIBUTE),
+    }
+  }
+}
+
 impl AttributeValue {
+  /// Creates the enum value from the name of the variant
+  pub fn enum_variant(variant: &str) -> Result<Self, &'static str> {
+    match variant.as_bytes().to_vec().try_into() {
+      Ok(val) => {
+        let value = AttributeValue::Enum(val);
+        match value.validate() {
+          Ok(_) => Ok(value),
+          Err(_) => Err(ERROR_VALIDATE_ENUM_ATTRIBUTE),
+        }
+      },
+      Err(_) => Err(ERROR_VALIDATE_ENUM_ATTRIBUTE),
+    }
+  }
+
   pub fn validate(&self) -> DispatchResult {
-    if let AttributeValue::Number(value) = self {
-      if let Some(max_val) = value.number_max {
-        if value.number_value > max_val {
-          return Err(DispatchError::Other(ERROR_VALIDATE_NUMBER_ATTRIBUTE));
+    match self {
+      AttributeValue::Number(value) => {
+        if let Some(max_val) = value.number_max {
+          if value.number_value > max_val {
+            return Err(DispatchError::Other(ERROR_VALIDATE_NUMBER_ATTRIBUTE));
+          }
+        }
+      },
+      AttributeValue::Fixed(value) => {
+        if !value.is_valid() {
+          return Err(DispatchError::Other(ERROR_VALIDATE_FIXED_ATTRIBUTE));
         }
-      }
+      },
+      AttributeValue::Enum(variant) => {
+        if variant.is_empty() {
+          return Err(DispatchError::Other(ERROR_VALIDATE_ENUM_ATTRIBUTE));
+        }
+      },
+      _ => (),
     };
     Ok(())
   }
+
+  /// Returns the value as a boolean, if it is
+  pub fn as_bool(&self) -> Option<bool> {
+    match self {
+      AttributeValue::Bool(value) => Some(*value),
+      _ => None,
+    }
+  }
+
+  /// Returns the value as a signed number, if it is an integer
+  pub fn as_signed(&self) -> Option<i64> {
+    match self {
+      AttributeValue::Number(value) => Some(value.number_value.into()),
+      AttributeValue::Signed(value) => Some((*value).into()),
+      _ => None,
+    }
+  }
+
+  /// Returns the value as a fixed-point decimal, if it is a number
+  pub fn as_fixed(&self) -> Option<FixedAttribute> {
+    match self {
+      AttributeValue::Fixed(value) => Some(value.clone()),
+      _ => self
+        .as_signed()
+        .map(|value| FixedAttribute { value, decimals: 0 }),
+    }
+  }
+}
+
+impl NumberAttribute {
+  /// Increases the value by `delta`. The value is capped by `number_max`
+  pub fn saturating_increase(&mut self, delta: u32) {
+    let value = self.number_value.saturating_add(delta);
+    self.number_value = match self.number_max {
+      Some(max_val) => value.min(max_val),
+      None => value,
+    };
+  }
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// A fixed-point decimal number represented as `value / 10^decimals`, i.e. `12.5` is `125` with
+/// `1` decimal place
+pub struct FixedAttribute {
+  pub value: i64,
+  pub decimals: u8,
+}
+
+impl FixedAttribute {
+  /// Number of decimal places must not exceed `FIXED_ATTRIBUTE_MAX_DECIMALS`
+  pub fn is_valid(&self) -> bool {
+    self.decimals <= FIXED_ATTRIBUTE_MAX_DECIMALS
+  }
+
+  /// Returns the value scaled to the given number of decimal places, truncating extra digits
+  pub fn scaled(&self, decimals: u8) -> i128 {
+    let value = i128::from(self.value);
+    if decimals >= self.decimals {
+      value.saturating_mul(10i128.saturating_pow((decimals - self.decimals).into()))
+    } else {
+      value / 10i128.saturating_pow((self.decimals - decimals).into())
+    }
+  }
+
+  /// Compares the numeric values regardless of the number of decimal places
+  pub fn compare(&self, other: &FixedAttribute) -> sp_std::cmp::Ordering {
+    let decimals = self.decimals.max(other.decimals);
+    self.scaled(decimals).cmp(&other.scaled(decimals))
+  }
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+/// An item of the list attribute value
+pub enum AttributeListItem {
+  Number(u32),
+  Signed(i32),
+  Bool(bool),
+  Text(BoundedVec<u8, AttributeKeyStringLimit>),
 }
 
 impl Attribute {
@@ -143,6 +304,187 @@ impl Attribute {
     self.value.validate()
   }
 }
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// A template of the attribute value which is rolled randomly when an asset is minted
+pub enum AttributeTemplate {
+  /// A number in the range `min..=max`
+  NumberRange {
+    min: u32,
+    max: u32,
+    number_max: Option<u32>,
+  },
+  /// A weighted choice among text values
+  WeightedText(BoundedVec<WeightedText, DefaultListLengthLimit>),
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// A text value of the attribute template with its weight
+pub struct WeightedText {
+  pub value: BoundedVec<u8, AttributeValueStringLimit>,
+  pub weight: u32,
+}
+
+impl AttributeTemplate {
+  pub fn validate(&self) -> DispatchResult {
+    let is_valid = match self {
+      AttributeTemplate::NumberRange {
+        min,
+        max,
+        number_max,
+      } => min <= max && number_max.map_or(true, |number_max| *max <= number_max),
+      AttributeTemplate::WeightedText(variants) => {
+        !variants.is_empty()
+          && variants.iter().all(|v| v.weight > 0)
+          && variants
+            .iter()
+            .try_fold(0u32, |acc, v| acc.checked_add(v.weight))
+            .is_some()
+      },
+    };
+    if !is_valid {
+      return Err(DispatchError::Other(ERROR_VALIDATE_ATTRIBUTE_TEMPLATE));
+    }
+    Ok(())
+  }
+
+  /// Returns the attribute value chosen by the `random` number.  \
+  /// Note that there is potential bias introduced by using modulus operator.  \
+  /// **Warn**: the template must be valid
+  pub fn roll(&self, random: u32) -> AttributeValue {
+    match self {
+      AttributeTemplate::NumberRange {
+        min,
+        max,
+        number_max,
+      } => {
+        let number_value = match (max - min).checked_add(1) {
+          Some(span) => min + random % span,
+          // the full range of u32
+          None => random,
+        };
+        AttributeValue::Number(NumberAttribute {
+          number_value,
+          number_max: *number_max,
+        })
+      },
+      AttributeTemplate::WeightedText(variants) => {
+        let probs = misc::cumsum_owned(variants.iter().map(|v| v.weight).collect());
+        let random_variant = random % probs[probs.len() - 1];
+        let idx = probs
+          .iter()
+          .position(|prob| *prob > random_variant)
+          .unwrap_or(probs.len() - 1);
+        AttributeValue::Text(variants[idx].value.clone())
+      },
+    }
+  }
+
+  /// Returns the extreme values which can be rolled by the template
+  pub fn extremes(&self) -> Vec<AttributeValue> {
+    match self {
+      AttributeTemplate::NumberRange {
+        min,
+        max,
+        number_max,
+      } => [min, max]
+        .iter()
+        .map(|number_value| {
+          AttributeValue::Number(NumberAttribute {
+            number_value: **number_value,
+            number_max: *number_max,
+          })
+        })
+        .collect(),
+      AttributeTemplate::WeightedText(variants) => variants
+        .iter()
+        .map(|v| AttributeValue::Text(v.value.clone()))
+        .collect(),
+    }
+  }
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// A type of the attribute value allowed by the attribute schema
+pub enum AttributeType {
+  Number,
+  Text,
+  Bool,
+  Signed,
+  /// A fixed-point decimal with at most `decimals` decimal places
+  Fixed {
+    decimals: u8,
+  },
+  /// A variant of the given set
+  Enum(BoundedVec<AttributeKey, AttributeEnumVariantsLimit>),
+  List,
+}
+
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+/// A schema of the attribute of the class.  \
+/// `min` and `max` bound the numeric value (the fixed-point one is scaled to `decimals`),
+/// the length of the text or the number of items of the list
+pub struct AttributeSchema {
+  pub value_type: AttributeType,
+  /// Whether every asset of the class must have the attribute
+  pub required: bool,
+  pub min: Option<i64>,
+  pub max: Option<i64>,
+}
+
+impl AttributeSchema {
+  pub fn validate(&self) -> DispatchResult {
+    let bounds_allowed = !matches!(
+      self.value_type,
+      AttributeType::Bool | AttributeType::Enum(_)
+    );
+    let is_valid = match (self.min, self.max) {
+      (None, None) => true,
+      (Some(min), Some(max)) => bounds_allowed && min <= max,
+      _ => bounds_allowed,
+    } && match &self.value_type {
+      AttributeType::Fixed { decimals } => *decimals <= FIXED_ATTRIBUTE_MAX_DECIMALS,
+      AttributeType::Enum(variants) => {
+        !variants.is_empty()
+          && variants.iter().enumerate().all(|(i, variant)| {
+            !variant.is_empty() && variants.iter().skip(i + 1).all(|other| other != variant)
+          })
+      },
+      _ => true,
+    };
+    if !is_valid {
+      return Err(DispatchError::Other(ERROR_VALIDATE_ATTRIBUTE_SCHEMA));
+    }
+    Ok(())
+  }
+
+  /// Checks that the value has the type of the schema and lies within the bounds
+  pub fn is_met_by(&self, value: &AttributeValue) -> bool {
+    let measure: Option<i128> = match (&self.value_type, value) {
+      (AttributeType::Number, AttributeValue::Number(value)) => Some(value.number_value.into()),
+      (AttributeType::Text, AttributeValue::Text(value)) => Some(value.len() as i128),
+      (AttributeType::Bool, AttributeValue::Bool(_)) => None,
+      (AttributeType::Signed, AttributeValue::Signed(value)) => Some((*value).into()),
+      (AttributeType::Fixed { decimals }, AttributeValue::Fixed(value))
+        if value.decimals <= *decimals =>
+      {
+        Some(value.scaled(*decimals))
+      },
+      (AttributeType::Enum(variants), AttributeValue::Enum(value)) => {
+        return variants.contains(value)
+      },
+      (AttributeType::List, AttributeValue::List(value)) => Some(value.len() as i128),
+      _ => return false,
+    };
+    match measure {
+      Some(measure) => {
+        self.min.map_or(true, |min| measure >= min.into())
+          && self.max.map_or(true, |max| measure <= max.into())
+      },
+      None => true,
+    }
+  }
+}
+
 /// Type of the attribute key for NFA
 pub type AttributeKey = BoundedVec<u8, AttributeKeyStringLimit>;
 /// Represent a list of the attributes
@@ -150,14 +492,18 @@ pub type AttributeList = BoundedVec<Attribute, AttributeListLengthLimit>;
 
 /// Represent the origin of lock
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Locker<AccountId, Index> {
   /// Not locked
   None,
   /// Locked by mechanic
   Mechanic(MechanicId<AccountId, Index>),
+  /// Equipped to (nested into) the parent NFA instance
+  Nfa(NonFungibleClassId, NonFungibleAssetId),
 }
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 /// Represents a user associated with the organization
 pub struct GamerAccount<AccountId> {
   /// User account
@@ -188,6 +534,7 @@ where
 }
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 /// Structure to represent of the Mechanic Id
 pub struct MechanicId<AccountId, Index> {
   pub gamer_account: GamerAccount<AccountId>,
@@ -5,10 +5,20 @@ use frame_support::assert_ok;
 use crate::{
   bettor::*,
   characteristics::*,
+  lootbox::*,
   misc::{cumsum_array_owned, cumsum_owned},
   purchased::*,
-  AttributeValue, NumberAttribute, BETTOR_MAX_NUMBER_OF_ROUNDS,
+  royalty::*,
+  salvage::*,
+  transferability::*,
+  upgradable::*,
+  AttributeListItem, AttributeSchema, AttributeTemplate, AttributeType, AttributeValue,
+  CommonError, FixedAttribute, NumberAttribute, WeightedText, BETTOR_MAX_NUMBER_OF_ROUNDS,
+  FIXED_ATTRIBUTE_MAX_DECIMALS, LOOTBOX_MAX_ROLLS,
 };
+use codec::{Decode, Encode, MaxEncodedLen};
+use sp_runtime::{traits::Zero, Percent};
+use sp_std::cmp::Ordering;
 
 #[test]
 fn template_test() {
@@ -149,6 +159,215 @@ fn attribute_validate_no_max_val_err() {
   val.validate().unwrap();
 }
 
+#[test]
+fn attribute_value_from_bool_and_signed() {
+  let val: AttributeValue = true.try_into().unwrap();
+  assert_eq!(val, AttributeValue::Bool(true));
+  assert_eq!(val.as_bool(), Some(true));
+  assert_eq!(val.as_signed(), None);
+
+  let val: AttributeValue = (-15i32).try_into().unwrap();
+  assert_eq!(val, AttributeValue::Signed(-15));
+  assert_eq!(val.as_signed(), Some(-15));
+  assert_eq!(val.as_bool(), None);
+  let val: AttributeValue = 15u32.try_into().unwrap();
+  assert_eq!(val.as_signed(), Some(15));
+}
+
+#[test]
+fn attribute_value_fixed() {
+  // 12.5
+  let val: AttributeValue = (125i64, 1u8).try_into().unwrap();
+  assert_eq!(
+    val,
+    AttributeValue::Fixed(FixedAttribute {
+      value: 125,
+      decimals: 1
+    })
+  );
+  assert_ok!(val.validate());
+  let fixed = val.as_fixed().unwrap();
+  assert_eq!(fixed.scaled(3), 12500);
+  assert_eq!(fixed.scaled(0), 12);
+  // 12.50 == 12.5 < 13
+  let other = FixedAttribute {
+    value: 1250,
+    decimals: 2,
+  };
+  assert_eq!(fixed.compare(&other), Ordering::Equal);
+  let val: AttributeValue = 13u32.try_into().unwrap();
+  assert_eq!(fixed.compare(&val.as_fixed().unwrap()), Ordering::Less);
+
+  let res: Result<AttributeValue, _> = (125i64, FIXED_ATTRIBUTE_MAX_DECIMALS + 1).try_into();
+  assert!(res.is_err());
+  let val = AttributeValue::Fixed(FixedAttribute {
+    value: 1,
+    decimals: FIXED_ATTRIBUTE_MAX_DECIMALS + 1,
+  });
+  assert!(val.validate().is_err());
+}
+
+#[test]
+fn attribute_value_enum() {
+  let val = AttributeValue::enum_variant("rare").unwrap();
+  assert_eq!(
+    val,
+    AttributeValue::Enum(br"rare".to_vec().try_into().unwrap())
+  );
+  assert_ok!(val.validate());
+  assert!(AttributeValue::enum_variant("").is_err());
+  assert!(AttributeValue::enum_variant("looooong___string_________looooong").is_err());
+  assert!(AttributeValue::Enum(Default::default()).validate().is_err());
+}
+
+#[test]
+fn attribute_value_list() {
+  let items = vec![
+    AttributeListItem::Number(1),
+    AttributeListItem::Signed(-1),
+    AttributeListItem::Bool(false),
+    AttributeListItem::Text(br"fire".to_vec().try_into().unwrap()),
+  ];
+  let val: AttributeValue = items.clone().try_into().unwrap();
+  assert_eq!(val, AttributeValue::List(items.try_into().unwrap()));
+  assert_ok!(val.validate());
+
+  let res: Result<AttributeValue, _> = vec![AttributeListItem::Bool(true); 9].try_into();
+  assert!(res.is_err());
+}
+
+#[test]
+fn attribute_value_codec() {
+  // encoding of the existing variants stays the same
+  let val: AttributeValue = (15u32, 100u32).try_into().unwrap();
+  assert_eq!(val.encode(), vec![0, 15, 0, 0, 0, 1, 100, 0, 0, 0]);
+  let val: AttributeValue = "ab".try_into().unwrap();
+  assert_eq!(val.encode(), vec![1, 8, b'a', b'b']);
+  assert_eq!(
+    AttributeValue::decode(&mut &[0u8, 15, 0, 0, 0, 0][..]).unwrap(),
+    15u32.try_into().unwrap()
+  );
+
+  let values: Vec<AttributeValue> = vec![
+    true.try_into().unwrap(),
+    (-15i32).try_into().unwrap(),
+    (125i64, 1u8).try_into().unwrap(),
+    AttributeValue::enum_variant("rare").unwrap(),
+    vec![AttributeListItem::Number(1), AttributeListItem::Bool(true)]
+      .try_into()
+      .unwrap(),
+  ];
+  for val in values {
+    let encoded = val.encode();
+    assert!(encoded.len() <= AttributeValue::max_encoded_len());
+    assert_eq!(AttributeValue::decode(&mut &encoded[..]).unwrap(), val);
+  }
+  assert_eq!(AttributeValue::Bool(true).encode(), vec![2, 1]);
+  assert_eq!(
+    AttributeValue::Signed(-1).encode(),
+    vec![3, 255, 255, 255, 255]
+  );
+}
+
+#[test]
+fn attribute_schema_validate() {
+  let schema = |value_type, min, max| AttributeSchema {
+    value_type,
+    required: false,
+    min,
+    max,
+  };
+  assert_ok!(schema(AttributeType::Number, Some(1), Some(10)).validate());
+  assert_ok!(schema(AttributeType::Text, None, Some(10)).validate());
+  assert_ok!(schema(AttributeType::Bool, None, None).validate());
+  assert!(schema(AttributeType::Number, Some(10), Some(1))
+    .validate()
+    .is_err());
+  assert!(schema(AttributeType::Bool, Some(0), None)
+    .validate()
+    .is_err());
+  assert!(schema(
+    AttributeType::Fixed {
+      decimals: FIXED_ATTRIBUTE_MAX_DECIMALS + 1
+    },
+    None,
+    None
+  )
+  .validate()
+  .is_err());
+  let variants = |v: Vec<&str>| {
+    AttributeType::Enum(
+      v.into_iter()
+        .map(|v| v.as_bytes().to_vec().try_into().unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap(),
+    )
+  };
+  assert_ok!(schema(variants(vec!["common", "rare"]), None, None).validate());
+  assert!(schema(variants(vec![]), None, None).validate().is_err());
+  assert!(schema(variants(vec!["rare", "rare"]), None, None)
+    .validate()
+    .is_err());
+  assert!(schema(variants(vec!["rare"]), None, Some(1))
+    .validate()
+    .is_err());
+}
+
+#[test]
+fn attribute_schema_is_met_by() {
+  let schema = |value_type, min, max| AttributeSchema {
+    value_type,
+    required: false,
+    min,
+    max,
+  };
+  let number = schema(AttributeType::Number, Some(1), Some(10));
+  assert!(number.is_met_by(&5u32.try_into().unwrap()));
+  assert!(!number.is_met_by(&11u32.try_into().unwrap()));
+  assert!(!number.is_met_by(&5i32.try_into().unwrap()));
+
+  let signed = schema(AttributeType::Signed, Some(-5), None);
+  assert!(signed.is_met_by(&(-5i32).try_into().unwrap()));
+  assert!(!signed.is_met_by(&(-6i32).try_into().unwrap()));
+
+  // from 0.0% to 100.0%
+  let fixed = schema(AttributeType::Fixed { decimals: 1 }, Some(0), Some(1000));
+  assert!(fixed.is_met_by(&(125i64, 1u8).try_into().unwrap()));
+  assert!(fixed.is_met_by(&(100i64, 0u8).try_into().unwrap()));
+  assert!(!fixed.is_met_by(&(1001i64, 1u8).try_into().unwrap()));
+  assert!(!fixed.is_met_by(&(1255i64, 2u8).try_into().unwrap()));
+
+  let text = schema(AttributeType::Text, Some(1), Some(3));
+  assert!(text.is_met_by(&"abc".try_into().unwrap()));
+  assert!(!text.is_met_by(&"abcd".try_into().unwrap()));
+
+  let list = schema(AttributeType::List, None, Some(1));
+  assert!(list.is_met_by(&vec![AttributeListItem::Number(1)].try_into().unwrap()));
+  assert!(!list.is_met_by(
+    &vec![AttributeListItem::Number(1), AttributeListItem::Number(2)]
+      .try_into()
+      .unwrap()
+  ));
+
+  let enumeration = schema(
+    AttributeType::Enum(
+      vec![br"rare".to_vec().try_into().unwrap()]
+        .try_into()
+        .unwrap(),
+    ),
+    None,
+    None,
+  );
+  assert!(enumeration.is_met_by(&AttributeValue::enum_variant("rare").unwrap()));
+  assert!(!enumeration.is_met_by(&AttributeValue::enum_variant("epic").unwrap()));
+  assert!(!enumeration.is_met_by(&"rare".try_into().unwrap()));
+
+  let boolean = schema(AttributeType::Bool, None, None);
+  assert!(boolean.is_met_by(&false.try_into().unwrap()));
+  assert!(!boolean.is_met_by(&0u32.try_into().unwrap()));
+}
+
 #[test]
 fn bettor_empty() {
   let b: Bettor = Bettor {
@@ -459,19 +678,34 @@ fn purchased_has_0_price() {
   let b: Purchased = Purchased {
     offers: vec![
       Offer {
-        fa: 1.into(),
-        price: 10.into(),
+        price: vec![(1.into(), 10.into())].try_into().unwrap(),
+        trade_ins: vec![].try_into().unwrap(),
         attributes: vec![].try_into().unwrap(),
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       },
       Offer {
-        fa: 2.into(),
-        price: 100.into(),
+        price: vec![(2.into(), 100.into())].try_into().unwrap(),
+        trade_ins: vec![].try_into().unwrap(),
         attributes: vec![].try_into().unwrap(),
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       },
       Offer {
-        fa: 3.into(),
-        price: 0.into(),
+        price: vec![(3.into(), 0.into())].try_into().unwrap(),
+        trade_ins: vec![].try_into().unwrap(),
         attributes: vec![].try_into().unwrap(),
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       },
     ]
     .try_into()
@@ -485,19 +719,34 @@ fn purchased_has_0_price_2() {
   let b: Purchased = Purchased {
     offers: vec![
       Offer {
-        fa: 1.into(),
-        price: 10.into(),
+        price: vec![(1.into(), 10.into())].try_into().unwrap(),
+        trade_ins: vec![].try_into().unwrap(),
         attributes: vec![].try_into().unwrap(),
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       },
       Offer {
-        fa: 2.into(),
-        price: 100.into(),
+        price: vec![(2.into(), 100.into())].try_into().unwrap(),
+        trade_ins: vec![].try_into().unwrap(),
         attributes: vec![].try_into().unwrap(),
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       },
       Offer {
-        fa: 3.into(),
-        price: 1000.into(),
+        price: vec![(3.into(), 1000.into())].try_into().unwrap(),
+        trade_ins: vec![].try_into().unwrap(),
         attributes: vec![].try_into().unwrap(),
+        stock: None,
+        start_block: None,
+        end_block: None,
+        max_per_gamer: None,
+        payment: Default::default(),
       },
     ]
     .try_into()
@@ -506,6 +755,13 @@ fn purchased_has_0_price_2() {
   assert!(AssetCharacteristic::is_valid(&b))
 }
 
+#[test]
+fn transferability_default() {
+  assert_eq!(Transferability::default(), Transferability::Transferable);
+  assert!(Transferability::Transferable.is_transferable());
+  assert!(!Transferability::Soulbound.is_transferable());
+}
+
 #[test]
 fn test_cumsums() {
   let a: [i32; 0] = [];
@@ -517,3 +773,436 @@ fn test_cumsums() {
   assert_eq!(cumsum_owned(vec![1]), vec![1]);
   assert_eq!(cumsum_owned(vec![1, 2, 3]), vec![1, 3, 6]);
 }
+
+#[test]
+fn number_attribute_saturating_increase() {
+  let mut attr = NumberAttribute {
+    number_value: 5,
+    number_max: Some(10),
+  };
+  attr.saturating_increase(3);
+  assert_eq!(attr.number_value, 8);
+  attr.saturating_increase(3);
+  assert_eq!(attr.number_value, 10);
+  let mut attr = NumberAttribute {
+    number_value: u32::MAX - 1,
+    number_max: None,
+  };
+  attr.saturating_increase(3);
+  assert_eq!(attr.number_value, u32::MAX);
+}
+
+fn upgradable_level(cost: UpgradeCost, key: &[u8]) -> UpgradeLevel {
+  UpgradeLevel {
+    cost,
+    deltas: vec![AttributeDelta {
+      key: key.to_vec().try_into().unwrap(),
+      delta: 1,
+    }]
+    .try_into()
+    .unwrap(),
+  }
+}
+
+#[test]
+fn upgradable_validation() {
+  let mut upgradable = Upgradable {
+    level_key: br"level".to_vec().try_into().unwrap(),
+    levels: Default::default(),
+  };
+  // no levels
+  assert!(!upgradable.is_valid());
+  upgradable.levels = vec![
+    upgradable_level(UpgradeCost::Fa(1.into(), 10.into()), br"power"),
+    upgradable_level(UpgradeCost::Nfa(1.into()), br"power"),
+  ]
+  .try_into()
+  .unwrap();
+  assert_ok!(upgradable.ensure());
+  assert_eq!(upgradable.max_level(), 2);
+  assert_eq!(upgradable.next_level(1), Some(&upgradable.levels[1]));
+  assert_eq!(upgradable.next_level(2), None);
+  assert_eq!(upgradable.fa_references(), vec![1.into()]);
+  // zero price
+  let mut wrong = upgradable.clone();
+  wrong.levels[0] = upgradable_level(UpgradeCost::Fa(1.into(), 0.into()), br"power");
+  assert_eq!(wrong.ensure(), Err(CommonError::WrongUpgradable));
+  // the level attribute is changed by deltas
+  let mut wrong = upgradable.clone();
+  wrong.levels[0] = upgradable_level(UpgradeCost::Fa(1.into(), 10.into()), br"level");
+  assert!(!wrong.is_valid());
+  // no level key
+  let mut wrong = upgradable;
+  wrong.level_key = Default::default();
+  assert!(!wrong.is_valid());
+}
+
+fn lootbox_drop(weight: u32, rare: bool) -> LootboxDrop {
+  LootboxDrop {
+    weight,
+    rare,
+    reward: LootboxReward::Fa(1.into(), 10.into()),
+  }
+}
+
+#[test]
+fn lootbox_validation() {
+  let mut lootbox = Lootbox {
+    drops: Default::default(),
+    rolls: 1,
+    pity: None,
+  };
+  // no drops
+  assert!(!lootbox.is_valid());
+  lootbox.drops = vec![lootbox_drop(10, false), lootbox_drop(1, true)]
+    .try_into()
+    .unwrap();
+  assert_ok!(lootbox.ensure());
+  assert_eq!(lootbox.fa_references(), vec![1.into(), 1.into()]);
+  // zero weight
+  let mut wrong = lootbox.clone();
+  wrong.drops[0].weight = 0;
+  assert_eq!(wrong.ensure(), Err(CommonError::WrongLootbox));
+  // the total weight overflows
+  let mut wrong = lootbox.clone();
+  wrong.drops[0].weight = u32::MAX / 2 + 1;
+  wrong.drops[1].weight = u32::MAX / 2 + 1;
+  assert!(!wrong.is_valid());
+  // zero FA amount
+  let mut wrong = lootbox.clone();
+  wrong.drops[0].reward = LootboxReward::Fa(1.into(), 0.into());
+  assert!(!wrong.is_valid());
+  // rolls out of bounds
+  let mut wrong = lootbox.clone();
+  wrong.rolls = 0;
+  assert!(!wrong.is_valid());
+  wrong.rolls = LOOTBOX_MAX_ROLLS + 1;
+  assert!(!wrong.is_valid());
+  // pity without rare drops
+  lootbox.pity = Some(LootboxPity { threshold: 10 });
+  assert!(lootbox.is_valid());
+  let mut wrong = lootbox.clone();
+  wrong.drops[1].rare = false;
+  assert!(!wrong.is_valid());
+  let mut wrong = lootbox;
+  wrong.pity = Some(LootboxPity { threshold: 0 });
+  assert!(!wrong.is_valid());
+}
+
+#[test]
+fn salvage_validation() {
+  let mut salvage = Salvage {
+    refunds: Default::default(),
+  };
+  // no refunds
+  assert_eq!(salvage.ensure(), Err(CommonError::WrongSalvage));
+  salvage.refunds = vec![
+    SalvageRefund::Fa(1.into(), 10.into()),
+    SalvageRefund::Nfa(1.into(), Default::default()),
+    SalvageRefund::Fa(1.into(), 5.into()),
+  ]
+  .try_into()
+  .unwrap();
+  assert_ok!(salvage.ensure());
+  assert_eq!(salvage.fa_references(), vec![1.into(), 1.into()]);
+  assert_ok!(salvage.ensure_references::<_, TestContext>(&1));
+  assert_eq!(
+    salvage.ensure_references::<_, TestContext>(&2),
+    Err(CommonError::ForeignReferencedAsset)
+  );
+  // zero FA amount
+  let mut wrong = salvage;
+  wrong.refunds[0] = SalvageRefund::Fa(1.into(), 0.into());
+  assert!(!wrong.is_valid());
+}
+
+#[test]
+fn royalty_validation() {
+  let mut royalty = Royalty::<u64> {
+    percent: Percent::zero(),
+    beneficiary: RoyaltyBeneficiary::Treasury,
+  };
+  // zero royalty
+  assert_eq!(royalty.ensure(), Err(CommonError::WrongRoyalty));
+  royalty.percent = Percent::from_percent(10);
+  assert_ok!(royalty.ensure());
+  assert_eq!(royalty.amount(1005.into()), 100.into());
+  assert_eq!(royalty.amount(5.into()), 0.into());
+}
+
+#[test]
+fn attribute_template_validate() {
+  let range = |min, max, number_max| AttributeTemplate::NumberRange {
+    min,
+    max,
+    number_max,
+  };
+  assert_ok!(range(10, 20, None).validate());
+  assert_ok!(range(10, 10, Some(10)).validate());
+  assert!(range(20, 10, None).validate().is_err());
+  assert!(range(10, 20, Some(15)).validate().is_err());
+
+  let text = |weights: Vec<u32>| {
+    AttributeTemplate::WeightedText(
+      weights
+        .into_iter()
+        .map(|weight| WeightedText {
+          value: br"v".to_vec().try_into().unwrap(),
+          weight,
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap(),
+    )
+  };
+  assert_ok!(text(vec![1, 2]).validate());
+  assert!(text(vec![]).validate().is_err());
+  assert!(text(vec![1, 0]).validate().is_err());
+  assert!(text(vec![u32::MAX, 1]).validate().is_err());
+}
+
+#[test]
+fn attribute_template_roll() {
+  let range = AttributeTemplate::NumberRange {
+    min: 10,
+    max: 20,
+    number_max: Some(30),
+  };
+  assert_eq!(range.roll(0), (10u32, 30u32).try_into().unwrap());
+  assert_eq!(range.roll(10), (20u32, 30u32).try_into().unwrap());
+  assert_eq!(range.roll(11), (10u32, 30u32).try_into().unwrap());
+  let full = AttributeTemplate::NumberRange {
+    min: 0,
+    max: u32::MAX,
+    number_max: None,
+  };
+  assert_eq!(full.roll(u32::MAX), u32::MAX.try_into().unwrap());
+
+  let text = AttributeTemplate::WeightedText(
+    vec![
+      WeightedText {
+        value: br"common".to_vec().try_into().unwrap(),
+        weight: 3,
+      },
+      WeightedText {
+        value: br"rare".to_vec().try_into().unwrap(),
+        weight: 1,
+      },
+    ]
+    .try_into()
+    .unwrap(),
+  );
+  let common: AttributeValue = "common".try_into().unwrap();
+  let rare: AttributeValue = "rare".try_into().unwrap();
+  assert_eq!(text.roll(0), common);
+  assert_eq!(text.roll(2), common);
+  assert_eq!(text.roll(3), rare);
+  assert_eq!(text.roll(4), common);
+}
+
+#[test]
+fn purchased_offer_limits() {
+  let offer = |stock, start_block, end_block, max_per_gamer| Offer {
+    price: vec![(1.into(), 10.into())].try_into().unwrap(),
+    trade_ins: vec![].try_into().unwrap(),
+    attributes: vec![].try_into().unwrap(),
+    stock,
+    start_block,
+    end_block,
+    max_per_gamer,
+    payment: Default::default(),
+  };
+  let purchased = |offer| Purchased {
+    offers: vec![offer].try_into().unwrap(),
+  };
+  assert!(purchased(offer(Some(1), Some(5), Some(5), Some(1))).is_valid());
+  assert!(!purchased(offer(Some(0), None, None, None)).is_valid());
+  assert!(!purchased(offer(None, None, None, Some(0))).is_valid());
+  assert!(!purchased(offer(None, Some(6), Some(5), None)).is_valid());
+
+  let o = offer(None, Some(5), Some(10), None);
+  assert_eq!(o.is_on_sale(4), Err(OfferUnavailable::NotStarted));
+  assert_ok!(o.is_on_sale(5));
+  assert_ok!(o.is_on_sale(10));
+  assert_eq!(o.is_on_sale(11), Err(OfferUnavailable::Ended));
+  assert_ok!(offer(None, None, None, None).is_on_sale(0));
+}
+
+#[test]
+fn purchased_offer_price() {
+  let offer = |price: Vec<(u32, u128)>, trade_ins: Vec<u32>| Offer {
+    price: price
+      .into_iter()
+      .map(|(fa, amount)| (fa.into(), amount.into()))
+      .collect::<Vec<_>>()
+      .try_into()
+      .unwrap(),
+    trade_ins: trade_ins
+      .into_iter()
+      .map(Into::into)
+      .collect::<Vec<_>>()
+      .try_into()
+      .unwrap(),
+    attributes: vec![].try_into().unwrap(),
+    stock: None,
+    start_block: None,
+    end_block: None,
+    max_per_gamer: None,
+    payment: Default::default(),
+  };
+  let purchased = |offer| Purchased {
+    offers: vec![offer].try_into().unwrap(),
+  };
+  assert!(purchased(offer(vec![(1, 100), (2, 5)], vec![])).is_valid());
+  assert!(purchased(offer(vec![(1, 100)], vec![3, 3])).is_valid());
+  assert!(purchased(offer(vec![], vec![3])).is_valid());
+  assert!(!purchased(offer(vec![], vec![])).is_valid());
+  assert!(!purchased(offer(vec![(1, 100), (2, 0)], vec![])).is_valid());
+  assert!(!purchased(offer(vec![(1, 100), (1, 5)], vec![])).is_valid());
+}
+
+#[test]
+fn purchased_payment_routing_split() {
+  let amount = 1001.into();
+  assert_eq!(PaymentRouting::Burn.split(amount), (amount, 0.into()));
+  assert_eq!(PaymentRouting::Treasury.split(amount), (0.into(), amount));
+  assert_eq!(
+    PaymentRouting::Split(sp_runtime::Percent::from_percent(30)).split(amount),
+    (701.into(), 300.into())
+  );
+  assert_eq!(
+    PaymentRouting::Split(sp_runtime::Percent::from_percent(100)).split(amount),
+    (0.into(), amount)
+  );
+  assert_eq!(PaymentRouting::default(), PaymentRouting::Burn);
+}
+
+/// FA 1 and NFA class 1 are owned by the organization 1, FA 2 and NFA class 2 - by 2
+struct TestContext;
+impl ValidationContext<u64> for TestContext {
+  fn fa_owner(asset_id: &crate::FungibleAssetId) -> Option<u64> {
+    [1, 2].contains(&**asset_id).then(|| (**asset_id).into())
+  }
+  fn nfa_class_owner(class_id: &crate::NonFungibleClassId) -> Option<u64> {
+    [1, 2].contains(&**class_id).then(|| (**class_id).into())
+  }
+}
+
+#[test]
+fn bettor_ensure_references() {
+  let bettor = |winning| Bettor {
+    outcomes: vec![
+      BettorOutcome {
+        name: br"o1".to_vec().try_into().unwrap(),
+        probability: 1,
+        result: OutcomeResult::Win,
+      },
+      BettorOutcome {
+        name: br"o2".to_vec().try_into().unwrap(),
+        probability: 1,
+        result: OutcomeResult::Lose,
+      },
+    ]
+    .try_into()
+    .unwrap(),
+    winnings: vec![winning].try_into().unwrap(),
+    rounds: 1,
+    draw_outcome: DrawOutcomeResult::Keep,
+  };
+  assert!(!bettor(BettorWinning::Fa(1.into(), 0.into())).is_valid());
+  assert_ok!(bettor(BettorWinning::Fa(1.into(), 1.into())).ensure_references::<_, TestContext>(&1));
+  assert_ok!(bettor(BettorWinning::Nfa(1.into())).ensure_references::<_, TestContext>(&1));
+  assert_eq!(
+    bettor(BettorWinning::Fa(3.into(), 1.into())).ensure_references::<_, TestContext>(&1),
+    Err(CommonError::UnknownReferencedAsset)
+  );
+  assert_eq!(
+    bettor(BettorWinning::Nfa(2.into())).ensure_references::<_, TestContext>(&1),
+    Err(CommonError::ForeignReferencedAsset)
+  );
+}
+
+#[test]
+fn purchased_ensure_references() {
+  let purchased = |fa: u32, trade_ins: Vec<u32>| Purchased {
+    offers: vec![Offer {
+      price: vec![(fa.into(), 100.into())].try_into().unwrap(),
+      trade_ins: trade_ins
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap(),
+      attributes: Default::default(),
+      stock: None,
+      start_block: None,
+      end_block: None,
+      max_per_gamer: None,
+      payment: Default::default(),
+    }]
+    .try_into()
+    .unwrap(),
+  };
+  assert_ok!(purchased(2, vec![2]).ensure_references::<_, TestContext>(&2));
+  assert_eq!(
+    purchased(3, vec![]).ensure_references::<_, TestContext>(&2),
+    Err(CommonError::UnknownReferencedAsset)
+  );
+  assert_eq!(
+    purchased(1, vec![]).ensure_references::<_, TestContext>(&2),
+    Err(CommonError::ForeignReferencedAsset)
+  );
+  assert_eq!(
+    purchased(2, vec![2, 1]).ensure_references::<_, TestContext>(&2),
+    Err(CommonError::ForeignReferencedAsset)
+  );
+}
+
+#[test]
+fn upgradable_ensure_references() {
+  let upgradable = |cost| Upgradable {
+    level_key: br"level".to_vec().try_into().unwrap(),
+    levels: vec![upgradable_level(cost, br"power")].try_into().unwrap(),
+  };
+  assert_ok!(
+    upgradable(UpgradeCost::Fa(2.into(), 10.into())).ensure_references::<_, TestContext>(&2)
+  );
+  assert_ok!(upgradable(UpgradeCost::Nfa(2.into())).ensure_references::<_, TestContext>(&2));
+  assert_eq!(
+    upgradable(UpgradeCost::Fa(3.into(), 10.into())).ensure_references::<_, TestContext>(&2),
+    Err(CommonError::UnknownReferencedAsset)
+  );
+  assert_eq!(
+    upgradable(UpgradeCost::Nfa(1.into())).ensure_references::<_, TestContext>(&2),
+    Err(CommonError::ForeignReferencedAsset)
+  );
+}
+
+#[test]
+fn lootbox_ensure_references() {
+  let lootbox = |reward| Lootbox {
+    drops: vec![LootboxDrop {
+      weight: 1,
+      rare: false,
+      reward,
+    }]
+    .try_into()
+    .unwrap(),
+    rolls: 1,
+    pity: None,
+  };
+  assert_ok!(
+    lootbox(LootboxReward::Fa(1.into(), 10.into())).ensure_references::<_, TestContext>(&1)
+  );
+  assert_ok!(lootbox(LootboxReward::Nfa(1.into(), Default::default()))
+    .ensure_references::<_, TestContext>(&1));
+  assert_eq!(
+    lootbox(LootboxReward::Fa(3.into(), 10.into())).ensure_references::<_, TestContext>(&1),
+    Err(CommonError::UnknownReferencedAsset)
+  );
+  assert_eq!(
+    lootbox(LootboxReward::Nfa(2.into(), Default::default()))
+      .ensure_references::<_, TestContext>(&1),
+    Err(CommonError::ForeignReferencedAsset)
+  );
+}
@@ -1,5 +1,7 @@
 mod fa;
 mod nfa;
+mod organization;
 
 pub use fa::*;
 pub use nfa::*;
+pub use organization::*;
@@ -4,6 +4,8 @@ use sp_runtime::DispatchResult;
 
 /// Trait for providing an interface to a fungible assets instances.
 pub trait FungibleAssets<AccountId> {
+  /// Returns the owner of the `asset` or `None` if the asset doesn't exist.
+  fn owner_of(asset: &FungibleAssetId) -> Option<AccountId>;
   /// Returns `Failed` if the asset `balance` of `who` may not be decreased by `amount`, otherwise
   /// the consequence.
   fn can_withdraw(
@@ -25,6 +27,39 @@ pub trait FungibleAssets<AccountId> {
     amount: FungibleAssetBalance,
   ) -> DispatchResultAs<FungibleAssetBalance>;
 
+  /// Attempt to move `amount` of the `asset` from `source` to `dest`.
+  ///
+  /// If not possible then don't do anything. Possible reasons for failure include:
+  /// - Less funds in the `source` account than amount
+  /// - Account `dest` cannot be created or its balance would overflow
+  ///
+  /// The overall supply of the underlying token stays unchanged.
+  fn transfer(
+    asset: FungibleAssetId,
+    source: &AccountId,
+    dest: &AccountId,
+    amount: FungibleAssetBalance,
+  ) -> DispatchResultAs<FungibleAssetBalance>;
+
+  /// Attempt to move `amount` of the `asset` from the balance of `who` to the reserved balance.
+  ///
+  /// The reserved balance can't be spent until it's unreserved.  \
+  /// If not possible then don't do anything.
+  fn reserve(
+    asset: FungibleAssetId,
+    who: &AccountId,
+    amount: FungibleAssetBalance,
+  ) -> DispatchResult;
+
+  /// Move up to `amount` of the `asset` from the reserved balance of `who` back to the balance.
+  ///
+  /// Returns the amount which was actually unreserved.
+  fn unreserve(
+    asset: FungibleAssetId,
+    who: &AccountId,
+    amount: FungibleAssetBalance,
+  ) -> DispatchResultAs<FungibleAssetBalance>;
+
   /// Increment the references counter on an asset.
   fn inc_references(asset: &FungibleAssetId) -> DispatchResult;
   /// Increment the references counter on an asset.
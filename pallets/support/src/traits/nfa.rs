@@ -1,6 +1,8 @@
 use crate::{
-  types_nfa::ClassDetails, AttributeList, DispatchResultAs, FungibleAssetBalance, FungibleAssetId,
-  LockResult, Locker, NonFungibleAssetId, NonFungibleClassId,
+  purchased::Offer,
+  types_nfa::{AssetDetails, ClassDetails},
+  AttributeKey, AttributeList, AttributeValue, DispatchResultAs, LockResult, Locker,
+  NonFungibleAssetId, NonFungibleClassId,
 };
 use sp_runtime::DispatchResult;
 
@@ -19,14 +21,22 @@ pub trait NonFungibleAssets<AccountId, Index> {
   ) -> DispatchResult;
 
   /// Returns offer by given id
-  fn get_offer(
-    class_id: &NonFungibleClassId,
-    offer_id: &u32,
-  ) -> DispatchResultAs<(FungibleAssetId, FungibleAssetBalance, AttributeList)>;
+  fn get_offer(class_id: &NonFungibleClassId, offer_id: &u32) -> DispatchResultAs<Offer>;
 
   /// Assigns an attributes to asset  \
-  /// The method doesn't check for the existance of either the class or the asset
-  fn set_attributes(asset_id: &NonFungibleAssetId, attributes: AttributeList) -> DispatchResult;
+  /// The method doesn't check for the existance of either the class or the asset.  \
+  /// Attributes are validated against the attribute schema of the class
+  fn set_attributes(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    attributes: AttributeList,
+  ) -> DispatchResult;
+
+  /// Checks that the attributes of a new asset of the class meet the attribute schema of the class
+  fn validate_attributes(
+    class_id: &NonFungibleClassId,
+    attributes: &AttributeList,
+  ) -> DispatchResult;
 
   /// Attempt to block an asset from the specified origin
   fn try_lock(
@@ -48,4 +58,30 @@ pub trait NonFungibleAssets<AccountId, Index> {
 
   /// Returns a class details
   fn get_class(class_id: &NonFungibleClassId) -> DispatchResultAs<ClassDetails<AccountId>>;
+
+  /// Returns an asset details
+  fn get_asset(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+  ) -> DispatchResultAs<AssetDetails<AccountId, Index>>;
+
+  /// Returns an attribute of the asset.  \
+  /// If the asset doesn't have the attribute, the attribute of the class is returned
+  fn get_attribute(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    key: &AttributeKey,
+  ) -> Option<AttributeValue>;
+
+  /// Transfer an unlocked asset to the `dest` account.  \
+  /// If `maybe_check_owner` is set, the asset must be owned by that account
+  fn transfer(
+    class_id: &NonFungibleClassId,
+    asset_id: &NonFungibleAssetId,
+    maybe_check_owner: Option<&AccountId>,
+    dest: &AccountId,
+  ) -> DispatchResult;
+
+  /// Ensure that assets of the class can change hands
+  fn ensure_transferable(class_id: &NonFungibleClassId) -> DispatchResult;
 }
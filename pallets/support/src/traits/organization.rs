@@ -0,0 +1,7 @@
+/// Trait for providing an interface to the treasuries of organizations.
+pub trait OrganizationTreasury<AccountId> {
+  /// Returns the account which receives the funds of the `organization_id` organization.
+  ///
+  /// If an organization hasn't set a treasury, the account of the organization itself is used.
+  fn treasury_of(organization_id: &AccountId) -> AccountId;
+}
@@ -1,8 +1,12 @@
-use crate::{bettor::Bettor, purchased::Purchased};
+use crate::{
+  bettor::Bettor, lootbox::Lootbox, purchased::Purchased, royalty::Royalty, salvage::Salvage,
+  transferability::Transferability, upgradable::Upgradable,
+};
 
 use super::*;
 
 #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 // #[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct ClassDetails<AccountId> {
   pub owner: AccountId,
@@ -16,9 +20,20 @@ pub struct ClassDetails<AccountId> {
   pub bettor: Option<Bettor>,
   /// Characteristic of purchases
   pub purchased: Option<Purchased>,
+  /// Characteristic of transferability
+  pub transferability: Transferability,
+  /// Characteristic of upgrades
+  pub upgradable: Option<Upgradable>,
+  /// Characteristic of lootboxes
+  pub lootbox: Option<Lootbox>,
+  /// Characteristic of refunds received when an asset is burned
+  pub salvage: Option<Salvage>,
+  /// Characteristic of royalties paid when an asset is sold
+  pub royalty: Option<Royalty<AccountId>>,
 }
 
 #[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct AssetDetails<AccountId, Index> {
   /// The owner of this asset.
   pub owner: AccountId,
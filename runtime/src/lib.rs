@@ -39,7 +39,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::CurrencyAdapter;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Percent, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -105,12 +105,12 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
   // The version of the runtime specification. A full node will not attempt to use its native
   //   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
   //   `spec_version`, and `authoring_version` are the same between Wasm and native.
-  // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
+  // This value is kept above 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
   //   the compatible custom types.
-  spec_version: 100,
+  spec_version: 101,
   impl_version: 1,
   apis: RUNTIME_API_VERSIONS,
-  transaction_version: 1,
+  transaction_version: 2,
   state_version: 1,
 };
 
@@ -318,16 +318,26 @@ impl pallet_non_fungible_assets::Config for Runtime {
   type Event = Event;
   type CreateOrigin = pallet_organization_identity::EnsureMemberOfOrganization<Runtime>;
   type FungibleAssets = FungibleAssets;
+  type MaxNestingDepth = ConstU32<4>;
+  type MaxChildren = ConstU32<16>;
+  type Randomness = RandomnessCollectiveFlip;
+}
+
+parameter_types! {
+  pub const CancelPenalty: Percent = Percent::from_percent(10);
 }
 
 impl pallet_mechanics::Config for Runtime {
   type Event = Event;
   type FungibleAssets = FungibleAssets;
   type NonFungibleAssets = NonFungibleAssets;
+  type OrganizationTreasury = OrganizationIdentity;
   type Randomness = RandomnessCollectiveFlip;
   type AssetsListLimit = ConstU32<64>;
   type MechanicsLifeTime = ConstU32<300>;
+  type CancelPenalty = CancelPenalty;
   type ExecuteOrigin = pallet_organization_identity::EnsureUser<Runtime>;
+  type CreateOrigin = pallet_organization_identity::EnsureMemberOfOrganization<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -383,8 +393,16 @@ pub type Executive = frame_executive::Executive<
   frame_system::ChainContext<Runtime>,
   Runtime,
   AllPalletsWithSystem,
+  Migrations,
 >;
 
+/// Storage migrations executed on the runtime upgrade.
+type Migrations = (
+  pallet_non_fungible_assets::migration::v1::MigrateToV1<Runtime>,
+  pallet_fungible_assets::migration::v1::MigrateToV1<Runtime>,
+  pallet_mechanics::migration::v1::MigrateToV1<Runtime>,
+);
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -553,6 +571,54 @@ impl_runtime_apis! {
     }
   }
 
+  impl pallet_non_fungible_assets_rpc_runtime_api::NonFungibleAssetsApi<Block, AccountId, Index> for Runtime {
+    fn class_metadata(class_id: pallet_support::NonFungibleClassId) -> Option<Vec<u8>> {
+      NonFungibleAssets::class_metadata(&class_id)
+    }
+    fn asset_metadata(
+      class_id: pallet_support::NonFungibleClassId,
+      asset_id: pallet_support::NonFungibleAssetId,
+    ) -> Option<Vec<u8>> {
+      NonFungibleAssets::asset_metadata(&class_id, &asset_id)
+    }
+    fn account_assets(
+      account: AccountId,
+      class_id: Option<pallet_support::NonFungibleClassId>,
+      offset: u32,
+      limit: u32,
+    ) -> Vec<(pallet_support::NonFungibleClassId, pallet_support::NonFungibleAssetId)> {
+      NonFungibleAssets::account_assets(&account, class_id, offset, limit)
+    }
+    fn asset_details(
+      class_id: pallet_support::NonFungibleClassId,
+      asset_id: pallet_support::NonFungibleAssetId,
+    ) -> Option<pallet_support::types_nfa::AssetDetails<AccountId, Index>> {
+      NonFungibleAssets::asset_details(&class_id, &asset_id)
+    }
+    fn asset_attributes(
+      class_id: pallet_support::NonFungibleClassId,
+      asset_id: pallet_support::NonFungibleAssetId,
+      offset: u32,
+      limit: u32,
+    ) -> Vec<pallet_support::Attribute> {
+      NonFungibleAssets::asset_attributes(&class_id, &asset_id, offset, limit)
+    }
+    fn class_details(
+      class_id: pallet_support::NonFungibleClassId,
+    ) -> Option<pallet_support::types_nfa::ClassDetails<AccountId>> {
+      NonFungibleAssets::class_details(&class_id)
+    }
+    fn royalty(
+      class_id: pallet_support::NonFungibleClassId,
+      price: pallet_support::FungibleAssetBalance,
+    ) -> Option<(
+      pallet_support::royalty::RoyaltyBeneficiary<AccountId>,
+      pallet_support::FungibleAssetBalance,
+    )> {
+      NonFungibleAssets::royalty_of(&class_id, price)
+    }
+  }
+
   #[cfg(feature = "runtime-benchmarks")]
   impl frame_benchmarking::Benchmark<Block> for Runtime {
     fn benchmark_metadata(extra: bool) -> (